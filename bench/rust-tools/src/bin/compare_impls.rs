@@ -12,10 +12,8 @@
 //   cargo run --release --bin compare-impls -- modules "Hello World"
 //   cargo run --release --bin compare-impls -- full "Test"
 
-use qrcode_fast::{QRCode as QRCodeFast, QRErrorCorrectLevel as QRErrorCorrectLevelFast};
-use qrcode_rust::{
-    QRCode as QRCodeRust, QRCodeOptions, QRErrorCorrectLevel as QRErrorCorrectLevelRust,
-};
+use qrcode_fast::QRCode as QRCodeFast;
+use qrcode_rust::{EcLevel, QRCode as QRCodeRust, QRCodeOptions, QrEncoder};
 use std::env;
 
 fn main() {
@@ -32,39 +30,49 @@ fn main() {
         "Hello World".to_string()
     };
 
+    let mut qr_rust = QRCodeRust::with_options(QRCodeOptions {
+        correct_level: EcLevel::M,
+        ..Default::default()
+    });
+    let mut qr_fast = QRCodeFast::with_options(EcLevel::M);
+    for (name, result) in [
+        ("qrcode-rust", qr_rust.encode(text.as_bytes())),
+        ("qrcode-fast", qr_fast.encode(text.as_bytes())),
+    ] {
+        if let Err(err) = result {
+            eprintln!("{}: {}", name, err);
+            std::process::exit(1);
+        }
+    }
+
     match mode.as_str() {
-        "full" => compare_full(&text),
-        "modules" => compare_modules(&text),
-        _ => compare_modules(&text),
+        "full" => compare_full(text.as_str(), &qr_rust, &qr_fast),
+        _ => compare_modules(text.as_str(), &qr_rust, &qr_fast),
     }
 }
 
-fn compare_modules(text: &str) {
+/// 对比两个实现的模块图案，任何实现了 `QrEncoder` 的后端都可以传入
+fn compare_modules(text: &str, qr_rust: &impl QrEncoder, qr_fast: &impl QrEncoder) {
     println!("🔍 Comparing Module Patterns");
     println!("═══════════════════════════════════════");
     println!("Text: {}\n", text);
 
-    let mut qr_rust = QRCodeRust::with_options(QRCodeOptions {
-        width: 256,
-        height: 256,
-        color_dark: String::from("#000000"),
-        color_light: String::from("#ffffff"),
-        correct_level: QRErrorCorrectLevelRust::M,
-    });
-    qr_rust.make_code(text);
-
-    let mut qr_fast = QRCodeFast::with_options(QRErrorCorrectLevelFast::M);
-    qr_fast.make_code(text);
-
-    println!("Module counts:");
-    println!(
-        "  qrcode-rust: {}x{}",
-        qr_rust.module_count, qr_rust.module_count
-    );
-    println!(
-        "  qrcode-fast: {}x{}\n",
-        qr_fast.module_count, qr_fast.module_count
-    );
+    println!("Symbols:");
+    for (name, qr) in [
+        ("qrcode-rust", qr_rust as &dyn QrEncoder),
+        ("qrcode-fast", qr_fast),
+    ] {
+        println!(
+            "  {}: {}x{}, version {:?}, {:?}, mask {:?}",
+            name,
+            qr.module_count(),
+            qr.module_count(),
+            qr.version().map(|v| v.get()),
+            qr.ec_level(),
+            qr.mask().map(|m| m.get())
+        );
+    }
+    println!();
 
     // Check finder patterns
     println!("Top-left finder pattern (7x7):");
@@ -104,8 +112,9 @@ fn compare_modules(text: &str) {
 
     // Count differences
     let mut diff_count = 0;
-    for r in 0..qr_rust.module_count {
-        for c in 0..qr_rust.module_count {
+    let count = qr_rust.module_count().max(qr_fast.module_count());
+    for r in 0..count {
+        for c in 0..count {
             if qr_rust.is_dark(r, c) != qr_fast.is_dark(r, c) {
                 diff_count += 1;
             }
@@ -115,33 +124,23 @@ fn compare_modules(text: &str) {
     println!(
         "\nTotal differences: {} out of {} modules",
         diff_count,
-        qr_rust.module_count * qr_rust.module_count
+        count * count
     );
 }
 
-fn compare_full(text: &str) {
+fn compare_full(text: &str, qr_rust: &impl QrEncoder, qr_fast: &impl QrEncoder) {
     println!("🔍 Full Module Comparison");
     println!("═══════════════════════════════════════");
     println!("Text: {}\n", text);
 
-    let mut qr_rust = QRCodeRust::with_options(QRCodeOptions {
-        width: 256,
-        height: 256,
-        color_dark: String::from("#000000"),
-        color_light: String::from("#ffffff"),
-        correct_level: QRErrorCorrectLevelRust::M,
-    });
-    qr_rust.make_code(text);
-
-    let mut qr_fast = QRCodeFast::with_options(QRErrorCorrectLevelFast::M);
-    qr_fast.make_code(text);
+    let count = qr_rust.module_count().max(qr_fast.module_count());
 
     println!("Full QR Code (D=Rust dark, d=Fast dark only, .=Rust light only, space=both light):");
     println!("════════════════════════════════════════════════════════════════════════════════");
 
-    for r in 0..21 {
+    for r in 0..count {
         print!("Row {:2}: ", r);
-        for c in 0..21 {
+        for c in 0..count {
             let rust_dark = qr_rust.is_dark(r, c);
             let fast_dark = qr_fast.is_dark(r, c);
 
@@ -163,8 +162,8 @@ fn compare_full(text: &str) {
     let mut rust_only_dark = 0;
     let mut fast_only_dark = 0;
 
-    for r in 0..21 {
-        for c in 0..21 {
+    for r in 0..count {
+        for c in 0..count {
             let rust_dark = qr_rust.is_dark(r, c);
             let fast_dark = qr_fast.is_dark(r, c);
 
@@ -179,7 +178,11 @@ fn compare_full(text: &str) {
         }
     }
 
-    println!("\nTotal differences: {} out of 441", diff_count);
+    println!(
+        "\nTotal differences: {} out of {}",
+        diff_count,
+        count * count
+    );
     println!("  Rust-only dark: {}", rust_only_dark);
     println!("  Fast-only dark: {}", fast_only_dark);
 }
@@ -131,7 +131,7 @@ fn main() {
                 } else {
                     ((b1 & 0x0F) << 4) | (b2 & 0x0F)
                 };
-                println!("    [{}] 0x{:02X} = '{}'", i, val, val as u8 as char);
+                println!("    [{}] 0x{:02X} = '{}'", i, val, val as char);
             }
         }
     }
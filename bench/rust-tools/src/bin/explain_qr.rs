@@ -0,0 +1,24 @@
+// 输出编码过程报告（JSON），用于对比不同实现的中间数据
+//
+// 用法: cargo run --bin explain-qr -- "文本" [L|M|Q|H]
+
+use qrcode_rust::{QRErrorCorrectLevel, QrBuilder};
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let text = args.get(1).map(String::as_str).unwrap_or("Hello World");
+    let level = match args.get(2).map(String::as_str) {
+        Some("L") => QRErrorCorrectLevel::L,
+        Some("M") => QRErrorCorrectLevel::M,
+        Some("Q") => QRErrorCorrectLevel::Q,
+        _ => QRErrorCorrectLevel::H,
+    };
+
+    match QrBuilder::new(text).ec_level(level).explain() {
+        Ok(report) => println!("{}", report.to_json().unwrap()),
+        Err(err) => {
+            eprintln!("❌ 编码失败: {}", err);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+// 在终端中显示二维码
+//
+// 用法: cargo run --bin terminal-qr -- "文本" [half|braille|ascii|sixel|kitty|auto]
+//
+// auto（默认）：终端支持图像协议时输出图像，否则输出半块字符；
+// 深色背景（COLORFGBG）自动反色，支持 24 位颜色时显式指定前景和背景
+
+use qrcode_rust::{
+    render_ascii, render_braille, render_half_blocks, render_kitty, render_sixel, GraphicsProtocol,
+    QrBuilder, TerminalHints, TerminalOptions,
+};
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let text = args.get(1).map(String::as_str).unwrap_or("Hello World");
+    let mode = args.get(2).map(String::as_str).unwrap_or("auto");
+
+    let symbol = match QrBuilder::new(text).build() {
+        Ok(symbol) => symbol,
+        Err(err) => {
+            eprintln!("❌ 编码失败: {}", err);
+            std::process::exit(1);
+        }
+    };
+    let matrix = symbol.matrix();
+
+    let hints = TerminalHints::from_env();
+    let mut options = TerminalOptions::for_terminal(&hints);
+    if hints.truecolor {
+        options.colors = Some(([0, 0, 0], [255, 255, 255]));
+    }
+
+    let mode = match (mode, hints.graphics) {
+        ("auto", Some(GraphicsProtocol::Kitty)) => "kitty",
+        ("auto", Some(GraphicsProtocol::Sixel)) => "sixel",
+        ("auto", None) => "half",
+        (mode, _) => mode,
+    };
+    let output = match mode {
+        "braille" => render_braille(matrix, &options),
+        "ascii" => render_ascii(matrix, "##", "  ", &options),
+        "sixel" => render_sixel(matrix, 4, 4, [0, 0, 0], [255, 255, 255]) + "\n",
+        "kitty" => render_kitty(matrix, 4, 4, [0, 0, 0], [255, 255, 255]) + "\n",
+        _ => render_half_blocks(matrix, &options),
+    };
+    print!("{}", output);
+}
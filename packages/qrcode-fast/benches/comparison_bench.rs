@@ -1,5 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use qrcode_fast::{QRCode, QRErrorCorrectLevel};
+use qrcode_fast::{encode_into, EncodeOptions, QRCode, QRErrorCorrectLevel, Workspace};
 
 fn benchmark_fast_single_generation(c: &mut Criterion) {
     let text = "https://github.com/veaba/qrcodes";
@@ -29,6 +29,24 @@ fn benchmark_fast_batch_generation(c: &mut Criterion) {
     });
 }
 
+fn benchmark_fast_workspace_batch(c: &mut Criterion) {
+    let texts: Vec<String> = (0..100)
+        .map(|i| format!("https://example.com/{}", i))
+        .collect();
+    let opts = EncodeOptions::default();
+    let mut ws = Workspace::new();
+
+    // 与 fast_batch_100 相同的数据，复用工作区且不生成 SVG
+    c.bench_function("fast_workspace_batch_100", |b| {
+        b.iter(|| {
+            for text in &texts {
+                encode_into(&mut ws, text.as_bytes(), &opts).unwrap();
+                black_box(ws.is_dark(0, 0));
+            }
+        });
+    });
+}
+
 fn benchmark_fast_svg_generation(c: &mut Criterion) {
     let text = "https://github.com/veaba/qrcodes";
     let mut qr = QRCode::new();
@@ -125,6 +143,7 @@ criterion_group!(
     fast_benches,
     benchmark_fast_single_generation,
     benchmark_fast_batch_generation,
+    benchmark_fast_workspace_batch,
     benchmark_fast_svg_generation,
     benchmark_fast_error_levels,
     benchmark_fast_different_lengths
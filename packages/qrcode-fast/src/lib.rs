@@ -6,20 +6,66 @@
 
 // 本地模块：核心 QRCode 实现（特有，不共享）
 mod qr_code;
+// 复用缓冲区的编码接口
+mod workspace;
 
 // 从 qrcode-rust-shared 重新导出
 pub use qrcode_rust_shared::{
     qr_8bit_byte::QR8bitByte,
     qr_bit_buffer::BitBuffer,
-    qr_code_model::{get_min_version, QRErrorCorrectLevel, QRErrorCorrectLevel as CorrectLevel},
+    qr_builder::QrBuilder,
+    qr_code_model::{
+        get_min_version, EcLevel, QRErrorCorrectLevel, QRErrorCorrectLevel as CorrectLevel,
+    },
+    qr_damage::{BlockDamage, DamageReport, ModuleRect},
+    qr_encoder::{check_capacity, QrEncoder},
+    qr_error::{QrError, QrResult},
+    qr_explain::{BlockInfo, Explanation, SegmentInfo},
+    qr_mask::Mask,
     qr_math::QRMath,
+    qr_matrix::QrMatrix,
+    qr_module_role::{ModuleRole, RoleMap},
+    qr_pdf::{render_pdf, PdfColor, PdfOptions},
+    qr_png::{render_png, PngRenderer},
     qr_polynomial::Polynomial,
+    qr_print::{PrintPlan, PrintSpec},
+    qr_raster::{render_into, render_rgba, PixelFormat, RasterOptions, RgbaRenderer},
+    qr_render::{render, MatrixRenderer, RenderInfo, Renderer},
     qr_rs_block::get_rs_blocks,
+    qr_segment::{Segment, SegmentMode},
+    qr_static::StaticQrMatrix,
+    qr_svg::{
+        render_svg, render_svg_with, render_svg_with_options, SvgOptions, SvgPathMode,
+        SvgPathRenderer, SvgRectRenderer, SvgSize,
+    },
+    qr_symbol::QrSymbol,
+    qr_terminal::{
+        render_ascii, render_braille, render_half_blocks, render_kitty, render_sixel,
+        GraphicsProtocol, TerminalHints, TerminalOptions,
+    },
     qr_util::get_bch_digit,
+    qr_version::Version,
 };
 
+// 编译期生成（`macros` 特性）
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub use qrcode_macros as __macros;
+#[cfg(feature = "macros")]
+pub use qrcode_macros::qr_svg;
+
+/// 编译期编码为 [`StaticQrMatrix`]，参数见 `qrcode_macros::qr!`
+#[cfg(feature = "macros")]
+#[macro_export]
+macro_rules! qr {
+    ($($args:tt)*) => {
+        $crate::__macros::qr!(crate = $crate, $($args)*)
+    };
+}
+
 // 重新导出本地模块
 pub use qr_code::{QRCode, QRCodeOptions};
+pub use workspace::{encode_into, EncodeOptions, Workspace};
 
 // 重新导出，保持 API 兼容
 pub use qr_code::QRCode as QRCodeWasm;
@@ -31,6 +77,7 @@ pub use qr_code::QRCode as QRCodeWasm;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use qrcode_rust_shared::qr_conformance as conformance;
 
     #[test]
     fn test_qrcode_basic_creation() {
@@ -39,6 +86,8 @@ mod tests {
 
         assert!(qr.module_count > 0, "模块数应该大于 0");
         assert!(qr.type_number > 0, "类型号应该大于 0");
+        assert_eq!(qr.version().map(Version::get), Some(qr.type_number));
+        assert_eq!(QRCode::new().version(), None);
     }
 
     #[test]
@@ -88,6 +137,19 @@ mod tests {
         assert!(!svg.is_empty(), "SVG 不应该为空");
         assert!(svg.contains("<svg"), "SVG 应该包含 <svg 标签");
         assert!(svg.contains("</svg>"), "SVG 应该包含 </svg> 标签");
+
+        // 边长取自 options.size
+        qr.options.size = 300;
+        assert!(qr.get_svg().contains(r#"viewBox="0 0 300 300""#));
+        assert!(qr
+            .get_svg_with(SvgPathMode::Contours)
+            .contains(r#"viewBox="0 0 300 300""#));
+        let n = qr.module_count + 8;
+        assert!(qr
+            .get_svg_with_options(&qr.svg_options())
+            .contains(&format!(
+                r#"viewBox="0 0 {n} {n}" width="300" height="300""#
+            )));
     }
 
     #[test]
@@ -197,6 +259,92 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_builder_symbol() {
+        let symbol = QrBuilder::new("Hello World")
+            .ec_level(QRErrorCorrectLevel::M)
+            .version_range(Version::new(2).unwrap()..=Version::new(10).unwrap())
+            .mask(Mask::new(4).unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(symbol.version().get(), 2);
+        assert_eq!(symbol.module_count(), 25);
+        assert_eq!(symbol.mask().get(), 4);
+        assert!(symbol.is_dark(0, 0));
+        assert!(!symbol.is_dark(-1, 0));
+
+        // 渲染参数与符号分离
+        let svg = render_svg(symbol.matrix(), 300, "#112233", "#ffffff");
+        assert!(svg.contains(r##"fill="#112233""##));
+
+        let err = QrBuilder::new(&"x".repeat(3000)).build().unwrap_err();
+        assert!(matches!(err, QrError::DataTooLong { .. }));
+    }
+
+    #[test]
+    fn test_qr_encoder() {
+        conformance::check_encoder(QRCode::with_options(QRErrorCorrectLevel::M));
+    }
+
+    #[test]
+    fn test_binary_payload() {
+        let mut text = QRCode::new();
+        text.make_code("Hi");
+        let mut bytes = QRCode::new();
+        bytes.make_code_bytes(b"Hi");
+        assert_eq!(text.to_matrix(), bytes.to_matrix());
+
+        // 非 UTF-8 数据按字节计数
+        let mut binary = QRCode::new();
+        binary.make_code_bytes(&[0xFF, 0x00, 0xC3, 0x80, 0xFE]);
+        assert_eq!(binary.get_module_count(), 21);
+    }
+
+    #[test]
+    fn test_version_capacity_boundary() {
+        // 18 字节超出 1-L 的容量（17），必须选版本 2，且与共享构建器的结果一致
+        let data = [0xFF; 18];
+        let mut qr = QRCode::with_options(QRErrorCorrectLevel::L);
+        qr.make_code_bytes(&data);
+        assert_eq!(qr.type_number, 2);
+
+        let version = Version::new(2).unwrap();
+        let symbol = QrBuilder::from_bytes(&data)
+            .ec_level(QRErrorCorrectLevel::L)
+            .version_range(version..=version)
+            .mask(Mask::default())
+            .build()
+            .unwrap();
+        assert_eq!(&qr.to_matrix(), symbol.matrix());
+    }
+
+    #[test]
+    fn test_module_roles() {
+        let mut qr = QRCode::new();
+        qr.make_code("Hello World");
+        conformance::check_roles(qr.roles());
+
+        // 便捷方法直接查询角色表
+        assert_eq!(qr.module_role(0, 0), Some(ModuleRole::Finder));
+        assert_eq!(
+            qr.modules_with_role(ModuleRole::Data).count(),
+            qr.roles().count(ModuleRole::Data)
+        );
+    }
+
+    #[test]
+    fn test_to_matrix_matches_is_dark() {
+        let mut qr = QRCode::new();
+        qr.make_code("Hello World");
+        let matrix = qr.to_matrix();
+        conformance::check_matrix(&qr, &matrix);
+
+        // 共享渲染器对矩阵的输出与 get_svg 一致
+        assert_eq!(render_svg(&matrix, 256, "#000000", "#ffffff"), qr.get_svg());
+        assert_eq!(qr.get_svg_with(SvgPathMode::Modules), qr.get_svg());
+        assert!(qr.get_svg_with(SvgPathMode::Contours).len() < qr.get_svg().len());
+    }
+
     #[test]
     fn test_empty_string() {
         let mut qr = QRCode::new();
@@ -2,22 +2,38 @@
 //!
 //! 极致优化版本：
 //! 1. 一维数组存储模块数据
-//! 2. 预分配 SVG 字符串容量
-//! 3. 使用内联数字转换避免 format! 开销
-//! 4. 避免所有不必要的内存分配
+//! 2. 位压缩矩阵 + 共享 SVG 渲染器（预分配容量、内联数字转换）
+//! 3. 避免所有不必要的内存分配
+//! 4. 功能图案从按版本缓存的模板复制，格式信息查表
 
 use qrcode_rust_shared::{
     qr_8bit_byte::QR8bitByte,
     qr_bit_buffer::BitBuffer,
-    qr_code_model::{get_min_version, QRErrorCorrectLevel},
-    qr_polynomial::Polynomial,
+    qr_code_model::{get_min_version, EcLevel, QRErrorCorrectLevel},
+    qr_encoder::{check_capacity, QrEncoder},
+    qr_error::{QrError, QrResult},
+    qr_mask::Mask,
+    qr_matrix::QrMatrix,
+    qr_module_role::{ModuleRole, RoleMap},
+    qr_pdf::{render_pdf, PdfOptions},
+    qr_polynomial::{rs_generator, rs_remainder},
+    qr_raster::{render_into, RasterOptions},
+    qr_render::{render, Renderer},
     qr_rs_block::get_rs_blocks,
-    qr_util::get_bch_digit,
+    qr_svg::{
+        render_svg, render_svg_with, render_svg_with_options, SvgOptions, SvgPathMode, SvgSize,
+    },
+    qr_symbol::QrSymbol,
+    qr_template::{write_format_states, VersionTemplate},
+    qr_terminal::{render_half_blocks, TerminalOptions},
+    qr_version::Version,
 };
 
 /// QRCode 选项
 #[derive(Clone)]
 pub struct QRCodeOptions {
+    /// `get_svg` / `get_svg_with` / `svg_options` 的边长（像素），输出为正方形
+    pub size: i32,
     pub color_dark: String,
     pub color_light: String,
     pub correct_level: QRErrorCorrectLevel,
@@ -26,6 +42,7 @@ pub struct QRCodeOptions {
 impl Default for QRCodeOptions {
     fn default() -> Self {
         QRCodeOptions {
+            size: 256,
             color_dark: String::from("#000000"),
             color_light: String::from("#ffffff"),
             correct_level: QRErrorCorrectLevel::H,
@@ -42,6 +59,8 @@ pub struct QRCode {
     /// 0 = 浅色/未设置, 1 = 深色, 2 = 已设置但浅色
     modules: Vec<u8>,
     data_list: Vec<QR8bitByte>,
+    /// 每个模块的角色，由编码过程记录
+    roles: RoleMap,
 }
 
 impl QRCode {
@@ -52,6 +71,7 @@ impl QRCode {
             type_number: 0,
             modules: Vec::new(),
             data_list: Vec::new(),
+            roles: RoleMap::default(),
         }
     }
 
@@ -66,287 +86,117 @@ impl QRCode {
             type_number: 0,
             modules: Vec::new(),
             data_list: Vec::new(),
+            roles: RoleMap::default(),
         }
     }
 
     /// 生成 QRCode（完整实现）
     pub fn make_code(&mut self, text: &str) {
+        self.make_code_bytes(text.as_bytes());
+    }
+
+    /// 以字节模式编码原始二进制数据
+    pub fn make_code_bytes(&mut self, data: &[u8]) {
         self.data_list.clear();
-        self.data_list.push(QR8bitByte::new(text));
+        self.data_list.push(QR8bitByte::from_bytes(data));
 
         // 计算类型号
-        self.type_number = get_min_version(text.len(), self.options.correct_level);
+        self.type_number = get_min_version(data.len(), self.options.correct_level);
         self.module_count = self.type_number * 4 + 17;
 
-        // 分配一维数组 (0 = 未设置/浅色, 1 = 深色)
-        let count = self.module_count as usize;
-        self.modules = vec![0u8; count * count];
-
-        // 设置功能图案
-        self.setup_position_probe_pattern(0, 0);
-        self.setup_position_probe_pattern(self.module_count - 7, 0);
-        self.setup_position_probe_pattern(0, self.module_count - 7);
-        self.setup_position_adjust_pattern();
-        self.setup_timing_pattern();
-        self.setup_type_info(false);
-
-        if self.type_number >= 7 {
-            self.setup_type_number(false);
-        }
+        // 从缓存的版本模板复制功能图案，再写入格式信息（固定掩码 0）
+        let template = VersionTemplate::get(
+            Version::new(self.type_number).expect("get_min_version returns 1..=10"),
+        );
+        self.modules.clear();
+        self.modules.extend_from_slice(template.states());
+        self.roles.clone_from(template.roles());
+        write_format_states(
+            &mut self.modules,
+            self.module_count,
+            self.options.correct_level,
+            Mask::default(),
+        );
 
         // 创建数据并映射
         let data = self.create_data();
-        self.map_data(&data);
-    }
-
-    /// 绘制位置探测图案（三个角）
-    fn setup_position_probe_pattern(&mut self, row: i32, col: i32) {
-        for r in -1..=7 {
-            if row + r <= -1 || self.module_count <= row + r {
-                continue;
-            }
-            for c in -1..=7 {
-                if col + c <= -1 || self.module_count <= col + c {
-                    continue;
-                }
-                let is_dark = ((0..=6).contains(&r) && (c == 0 || c == 6))
-                    || ((0..=6).contains(&c) && (r == 0 || r == 6))
-                    || ((2..=4).contains(&r) && (2..=4).contains(&c));
-                self.set_module(row + r, col + c, is_dark);
-            }
-        }
-    }
-
-    /// 设置位置调整图案
-    fn setup_position_adjust_pattern(&mut self) {
-        let pos = get_pattern_position(self.type_number);
-        for i in 0..pos.len() {
-            for j in 0..pos.len() {
-                let row = pos[i];
-                let col = pos[j];
-
-                if row == 0 || col == 0 {
-                    continue;
-                }
-
-                // 跳过与位置探测图案重叠的位置
-                if row <= 8 && col <= 8 {
-                    continue;
-                }
-                if row <= 8 && col >= self.module_count - 8 {
-                    continue;
-                }
-                if row >= self.module_count - 8 && col <= 8 {
-                    continue;
-                }
-
-                if self.is_module_set(row, col) {
-                    continue;
-                }
-
-                for r in -2..=2 {
-                    for c in -2..=2 {
-                        let new_row = row + r;
-                        let new_col = col + c;
-                        if new_row < 0
-                            || new_row >= self.module_count
-                            || new_col < 0
-                            || new_col >= self.module_count
-                        {
-                            continue;
-                        }
-                        let is_dark = r == -2 || r == 2 || c == -2 || c == 2 || (r == 0 && c == 0);
-                        self.set_module(new_row, new_col, is_dark);
-                    }
-                }
-            }
-        }
-    }
-
-    /// 设置定时图案
-    fn setup_timing_pattern(&mut self) {
-        for r in 8..self.module_count - 8 {
-            if !self.is_module_set(r, 6) {
-                self.set_module(r, 6, r % 2 == 0);
-            }
-        }
-        for c in 8..self.module_count - 8 {
-            if !self.is_module_set(6, c) {
-                self.set_module(6, c, c % 2 == 0);
-            }
-        }
-    }
-
-    /// 设置类型信息
-    fn setup_type_info(&mut self, test: bool) {
-        let g15 = (1 << 10) | (1 << 8) | (1 << 5) | (1 << 4) | (1 << 2) | (1 << 1) | (1 << 0);
-        let g15_mask = (1 << 14) | (1 << 12) | (1 << 10) | (1 << 4) | (1 << 1);
-
-        let mask_pattern = 0;
-        let correct_level = match self.options.correct_level {
-            QRErrorCorrectLevel::L => 1,
-            QRErrorCorrectLevel::M => 0,
-            QRErrorCorrectLevel::Q => 3,
-            QRErrorCorrectLevel::H => 2,
-        };
-
-        let mut data = (correct_level << 3) | mask_pattern;
-        let mut d = data << 10;
-
-        while get_bch_digit(d) - get_bch_digit(g15) >= 0 {
-            d ^= g15 << (get_bch_digit(d) - get_bch_digit(g15));
-        }
-
-        data = ((data << 10) | d) ^ g15_mask;
-
-        // 垂直类型信息（第 8 列）- 从上到下
-        for i in 0..15 {
-            let bit = !test && ((data >> i) & 1) == 1;
-            if i < 6 {
-                self.set_module(i, 8, bit);
-            } else if i < 8 {
-                self.set_module(i + 1, 8, bit);
-            } else {
-                self.set_module(self.module_count - 15 + i, 8, bit);
-            }
-        }
-
-        // 水平类型信息（第 8 行）- 从右到左
-        for i in 0..15 {
-            let bit = !test && ((data >> i) & 1) == 1;
-            if i < 8 {
-                self.set_module(8, self.module_count - 1 - i, bit);
-            } else if i < 9 {
-                self.set_module(8, 15 - i - 1 + 1, bit);
-            } else {
-                self.set_module(8, 15 - i - 1, bit);
-            }
-        }
-
-        // 固定暗模块
-        self.set_module(self.module_count - 8, 8, !test);
-    }
-
-    /// 设置类型号（版本 7+）
-    fn setup_type_number(&mut self, _test: bool) {
-        let g18 = (1 << 12)
-            | (1 << 11)
-            | (1 << 10)
-            | (1 << 9)
-            | (1 << 8)
-            | (1 << 5)
-            | (1 << 2)
-            | (1 << 0);
-        let mut data = self.type_number << 12;
-
-        while get_bch_digit(data) - get_bch_digit(g18) >= 0 {
-            data ^= g18 << (get_bch_digit(data) - get_bch_digit(g18));
-        }
-
-        data |= self.type_number << 12;
-
-        for i in 0..18 {
-            let bit = ((data >> i) & 1) == 1;
-            let row = i / 3;
-            let col = i % 3;
-            self.set_module(self.module_count - 11 + col, row, bit);
-            self.set_module(row, self.module_count - 11 + col, bit);
-        }
+        let data_count = get_rs_blocks(self.type_number, self.options.correct_level)
+            .iter()
+            .map(|b| b.data_count as usize)
+            .sum();
+        self.map_data(&data, data_count);
     }
 
     /// 创建数据
-    fn create_data(&self) -> Vec<i32> {
+    fn create_data(&self) -> Vec<u8> {
         let rs_blocks = get_rs_blocks(self.type_number, self.options.correct_level);
+        let total_data_count: usize = rs_blocks.iter().map(|b| b.data_count as usize).sum();
+        let total_count: usize = rs_blocks.iter().map(|b| b.total_count as usize).sum();
 
-        let mut buffer = BitBuffer::new();
+        let mut buffer = BitBuffer::with_capacity(total_data_count * 8);
 
         for data in &self.data_list {
             buffer.put(4, 4); // MODE_8BIT_BYTE
             buffer.put(
-                data.get_length() as i32,
-                get_length_in_bits(4, self.type_number),
+                data.get_length() as u32,
+                get_length_in_bits(4, self.type_number) as usize,
             );
             data.write(&mut buffer);
         }
 
-        let mut total_data_count = 0;
-        for block in &rs_blocks {
-            total_data_count += block.data_count;
-        }
-
-        if buffer.length + 4 <= total_data_count as usize * 8 {
+        // 终止符（最多 4 位），into_bytes 后自然补齐到字节边界
+        if buffer.len_bits() + 4 <= total_data_count * 8 {
             buffer.put(0, 4);
         }
 
-        while !buffer.length.is_multiple_of(8) {
-            buffer.put_bit(false);
-        }
-
-        loop {
-            if buffer.length >= total_data_count as usize * 8 {
-                break;
-            }
-            buffer.put(0xEC, 8);
-            if buffer.length >= total_data_count as usize * 8 {
+        let mut data = buffer.into_bytes();
+        data.truncate(total_data_count);
+        for pad in [0xEC, 0x11].into_iter().cycle() {
+            if data.len() >= total_data_count {
                 break;
             }
-            buffer.put(0x11, 8);
+            data.push(pad);
         }
 
-        let data = buffer.buffer;
-
-        let mut offset = 0;
-        let max_dc_count = rs_blocks.iter().map(|b| b.data_count).max().unwrap_or(0);
+        // 每块计算纠错码，生成多项式在相同纠错码数的块间复用
+        let max_dc_count = rs_blocks.iter().map(|b| b.data_count).max().unwrap_or(0) as usize;
         let max_ec_count = rs_blocks
             .iter()
             .map(|b| b.total_count - b.data_count)
             .max()
-            .unwrap_or(0);
-
-        let mut dcdata: Vec<Vec<i32>> = Vec::new();
-        let mut ecdata: Vec<Vec<i32>> = Vec::new();
+            .unwrap_or(0) as usize;
 
+        let mut ecdata: Vec<Vec<u8>> = Vec::with_capacity(rs_blocks.len());
+        let mut dc_offsets = Vec::with_capacity(rs_blocks.len());
+        let mut generator = Vec::new();
+        let mut offset = 0;
         for block in &rs_blocks {
-            let dc_count = block.data_count;
-            let ec_count = block.total_count - dc_count;
-
-            dcdata.push(data[offset as usize..(offset + dc_count) as usize].to_vec());
-            offset += dc_count;
-
-            let rs_poly = Polynomial::generate_rs_poly(ec_count);
-            let dc = dcdata.last().unwrap();
-            let mut raw_coeff = dc.clone();
-            raw_coeff.extend(std::iter::repeat_n(0, ec_count as usize));
-            let raw_poly = Polynomial::new(raw_coeff, 0);
-            let mod_poly = raw_poly.r#mod(&rs_poly);
-
-            let mut ec: Vec<i32> = Vec::with_capacity(ec_count as usize);
-            for i in 0..ec_count {
-                let mod_index = i + mod_poly.len() as i32 - ec_count;
-                let val = if mod_index >= 0 {
-                    mod_poly.get(mod_index as usize)
-                } else {
-                    0
-                };
-                ec.push(val);
+            let dc_count = block.data_count as usize;
+            let ec_count = (block.total_count - block.data_count) as usize;
+            if generator.len() != ec_count + 1 {
+                generator = rs_generator(ec_count);
             }
+            let mut ec = vec![0u8; ec_count];
+            rs_remainder(&data[offset..offset + dc_count], &generator, &mut ec);
             ecdata.push(ec);
+            dc_offsets.push((offset, dc_count));
+            offset += dc_count;
         }
 
-        let mut result: Vec<i32> = Vec::new();
+        let mut result: Vec<u8> = Vec::with_capacity(total_count);
 
         for i in 0..max_dc_count {
-            for item in dcdata.iter().take(rs_blocks.len()) {
-                if i < item.len() as i32 {
-                    result.push(item[i as usize]);
+            for &(start, len) in &dc_offsets {
+                if i < len {
+                    result.push(data[start + i]);
                 }
             }
         }
 
         for i in 0..max_ec_count {
-            for item in ecdata.iter().take(rs_blocks.len()) {
-                if i < item.len() as i32 {
-                    result.push(item[i as usize]);
+            for ec in &ecdata {
+                if let Some(&cw) = ec.get(i) {
+                    result.push(cw);
                 }
             }
         }
@@ -355,7 +205,7 @@ impl QRCode {
     }
 
     /// 映射数据到模块
-    fn map_data(&mut self, data: &[i32]) {
+    fn map_data(&mut self, data: &[u8], data_count: usize) {
         let mut inc = -1;
         let mut row = self.module_count - 1;
         let mut bit_index = 7;
@@ -386,6 +236,11 @@ impl QRCode {
                         }
 
                         self.set_module(row, col_idx, dark);
+                        self.roles.set(
+                            row,
+                            col_idx,
+                            ModuleRole::for_codeword(byte_index, data_count, data.len()),
+                        );
 
                         if bit_index == 0 {
                             bit_index = 7;
@@ -440,98 +295,85 @@ impl QRCode {
         self.module_count
     }
 
-    /// 生成 SVG - 极致性能版本
-    pub fn get_svg(&self) -> String {
-        let count = self.module_count;
-        if count == 0 {
-            return String::new();
-        }
+    /// 编码后的版本，编码前为 `None`
+    pub fn version(&self) -> Option<Version> {
+        Version::new(self.type_number).ok()
+    }
 
-        let size = 256;
-        let cell_size = size / count;
-        let actual_size = cell_size * count;
-        let offset = (size - actual_size) / 2;
-        let count_usize = count as usize;
-
-        // 统计深色模块数量，精确预分配
-        let dark_count: usize = self.modules.iter().map(|&m| (m == 1) as usize).sum();
-
-        // 每个深色模块约 20-25 字节路径数据
-        let path_capacity = dark_count * 25;
-        let total_capacity = 200 + path_capacity;
-
-        let mut svg = String::with_capacity(total_capacity);
-
-        // SVG 头部
-        svg.push_str(r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 "#);
-        svg.push_str(&size.to_string());
-        svg.push(' ');
-        svg.push_str(&size.to_string());
-        svg.push_str(r#"" width=""#);
-        svg.push_str(&size.to_string());
-        svg.push_str(r#"" height=""#);
-        svg.push_str(&size.to_string());
-        svg.push_str(r#""><path d="M0 0h"#);
-        svg.push_str(&size.to_string());
-        svg.push('v');
-        svg.push_str(&size.to_string());
-        svg.push_str(r#"H0z" fill=""#);
-        svg.push_str(&self.options.color_light);
-        svg.push_str(r#""/><path fill=""#);
-        svg.push_str(&self.options.color_dark);
-        svg.push_str(r#"" d=""#);
-
-        // 生成路径数据
-        for row in 0..count_usize {
-            let row_offset = row * count_usize;
-            for col in 0..count_usize {
-                if self.modules[row_offset + col] == 1 {
-                    let x = (col as i32) * cell_size + offset;
-                    let y = (row as i32) * cell_size + offset;
-
-                    svg.push('M');
-                    Self::push_i32(&mut svg, x);
-                    svg.push(' ');
-                    Self::push_i32(&mut svg, y);
-                    svg.push('h');
-                    Self::push_i32(&mut svg, cell_size);
-                    svg.push('v');
-                    Self::push_i32(&mut svg, cell_size);
-                    svg.push('h');
-                    svg.push('-');
-                    Self::push_i32(&mut svg, cell_size);
-                    svg.push('z');
-                }
-            }
-        }
+    /// 获取指定位置模块的角色，越界返回 `None`
+    pub fn module_role(&self, row: i32, col: i32) -> Option<ModuleRole> {
+        self.roles.get(row, col)
+    }
 
-        svg.push_str(r#""/></svg>"#);
-        svg
+    /// 按行优先顺序遍历指定角色的模块，产出 `(row, col)`
+    pub fn modules_with_role(&self, role: ModuleRole) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.roles.iter_role(role)
     }
 
-    /// 快速将 i32 推入字符串
-    #[inline(always)]
-    fn push_i32(s: &mut String, mut n: i32) {
-        if n == 0 {
-            s.push('0');
-            return;
-        }
+    /// 获取完整的角色表
+    pub fn roles(&self) -> &RoleMap {
+        &self.roles
+    }
 
-        if n < 0 {
-            s.push('-');
-            n = -n;
-        }
+    /// 转换为共享的位压缩矩阵
+    pub fn to_matrix(&self) -> QrMatrix {
+        QrMatrix::from_states(&self.modules, self.module_count as usize)
+    }
 
-        let mut buf = [0u8; 10];
-        let mut i = 10;
+    /// 生成 SVG - 极致性能版本，边长为 `options.size`
+    pub fn get_svg(&self) -> String {
+        render_svg(
+            &self.to_matrix(),
+            self.options.size,
+            &self.options.color_dark,
+            &self.options.color_light,
+        )
+    }
+
+    /// 按路径写法生成 SVG：`Contours` 体积最小且没有模块间接缝，`Runs` 生成最快
+    pub fn get_svg_with(&self, mode: SvgPathMode) -> String {
+        render_svg_with(
+            &self.to_matrix(),
+            self.options.size,
+            &self.options.color_dark,
+            &self.options.color_light,
+            mode,
+        )
+    }
 
-        while n > 0 {
-            i -= 1;
-            buf[i] = (n % 10) as u8 + b'0';
-            n /= 10;
+    /// 与当前选项对应的 [`SvgOptions`]：总尺寸 `options.size`、颜色取自 `options`，其余为默认值
+    pub fn svg_options(&self) -> SvgOptions {
+        SvgOptions {
+            size: SvgSize::Total(self.options.size as f64),
+            color_dark: self.options.color_dark.clone(),
+            color_light: self.options.color_light.clone(),
+            ..Default::default()
         }
+    }
+
+    /// 按 [`SvgOptions`] 生成 SVG（模块单位的 viewBox、空白边、标题、`<g>` 片段等）
+    pub fn get_svg_with_options(&self, options: &SvgOptions) -> String {
+        render_svg_with_options(&self.to_matrix(), options)
+    }
 
-        s.push_str(unsafe { std::str::from_utf8_unchecked(&buf[i..]) });
+    /// 生成单页矢量 PDF（物理尺寸、颜色空间、出血和裁切标记见 [`PdfOptions`]）
+    pub fn get_pdf(&self, options: &PdfOptions) -> Vec<u8> {
+        render_pdf(&self.to_matrix(), options)
+    }
+
+    /// 用自定义后端渲染，四周加 `quiet_zone` 个模块的空白边，每段模块都带有角色信息
+    pub fn render<R: Renderer>(&self, quiet_zone: usize, renderer: R) -> R::Output {
+        render(&self.to_matrix(), quiet_zone, Some(&self.roles), renderer)
+    }
+
+    /// 写入调用方的像素缓冲区（视频帧、标签位图、帧缓冲），格式和布局见 [`RasterOptions`]
+    pub fn render_into(&self, buffer: &mut [u8], options: &RasterOptions) -> QrResult<()> {
+        render_into(&self.to_matrix(), buffer, options)
+    }
+
+    /// 终端输出（半块字符，一行显示两行模块），其他样式见 [`crate::render_braille`] 等
+    pub fn get_terminal(&self, options: &TerminalOptions) -> String {
+        render_half_blocks(&self.to_matrix(), options)
     }
 }
 
@@ -541,22 +383,43 @@ impl Default for QRCode {
     }
 }
 
-/// 获取位置调整图案位置
-fn get_pattern_position(type_number: i32) -> Vec<i32> {
-    let table: &[i32] = match type_number {
-        1 => &[0, 0, 0, 0, 0, 0, 0],
-        2 => &[6, 18, 0, 0, 0, 0, 0],
-        3 => &[6, 22, 0, 0, 0, 0, 0],
-        4 => &[6, 26, 0, 0, 0, 0, 0],
-        5 => &[6, 30, 0, 0, 0, 0, 0],
-        6 => &[6, 34, 0, 0, 0, 0, 0],
-        7 => &[6, 22, 38, 0, 0, 0, 0],
-        8 => &[6, 24, 42, 0, 0, 0, 0],
-        9 => &[6, 26, 46, 0, 0, 0, 0],
-        10 => &[6, 28, 50, 0, 0, 0, 0],
-        _ => &[6, 30, 54, 0, 0, 0, 0],
-    };
-    table.iter().take_while(|&&x| x != 0).copied().collect()
+impl QrEncoder for QRCode {
+    fn encode(&mut self, data: &[u8]) -> QrResult<QrSymbol> {
+        self.make_code_bytes(data);
+        let version = QRCode::version(self).ok_or(QrError::InvalidVersion(self.type_number))?;
+        check_capacity(data.len(), version, self.options.correct_level)?;
+        QrSymbol::from_matrix(
+            version,
+            self.options.correct_level,
+            Mask::default(),
+            QRCode::to_matrix(self),
+        )
+    }
+
+    fn module_count(&self) -> i32 {
+        self.module_count
+    }
+
+    fn is_dark(&self, row: i32, col: i32) -> bool {
+        QRCode::is_dark(self, row, col)
+    }
+
+    fn version(&self) -> Option<Version> {
+        QRCode::version(self)
+    }
+
+    fn ec_level(&self) -> EcLevel {
+        self.options.correct_level
+    }
+
+    fn mask(&self) -> Option<Mask> {
+        // 固定使用掩码 0
+        QRCode::version(self).map(|_| Mask::default())
+    }
+
+    fn to_matrix(&self) -> QrMatrix {
+        QRCode::to_matrix(self)
+    }
 }
 
 /// 获取长度位数
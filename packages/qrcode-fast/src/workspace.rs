@@ -0,0 +1,434 @@
+//! QR Code Workspace - 复用缓冲区的编码接口
+//!
+//! [`QRCode::make_code`](crate::QRCode::make_code) 每次都会分配模块数组、数据码字和纠错码。
+//! [`Workspace`] 持有这些缓冲区，[`encode_into`] 每次编码前完全重置状态并复用已有容量：
+//! 工作区容量达到最大的符号后，继续编码不再分配内存。
+//!
+//! ```
+//! use qrcode_fast::{encode_into, EncodeOptions, Workspace};
+//!
+//! let mut ws = Workspace::new();
+//! let opts = EncodeOptions::default();
+//! for id in 0..3 {
+//!     encode_into(&mut ws, format!("https://example.com/{id}").as_bytes(), &opts).unwrap();
+//!     assert!(ws.is_dark(0, 0));
+//! }
+//! ```
+
+use qrcode_rust_shared::{
+    qr_bit_buffer::BitBuffer,
+    qr_code_model::{EcLevel, QRMode},
+    qr_encoder::QrEncoder,
+    qr_error::{QrError, QrResult},
+    qr_mask::{mask_bit, Mask},
+    qr_matrix::QrMatrix,
+    qr_polynomial::{rs_generator_into, rs_remainder},
+    qr_rs_block::rs_block_row,
+    qr_symbol::QrSymbol,
+    qr_template::{write_format_states, VersionTemplate},
+    qr_util::get_length_in_bits,
+    qr_version::Version,
+};
+
+/// [`encode_into`] 的编码选项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncodeOptions {
+    pub ec_level: EcLevel,
+    pub mask: Mask,
+}
+
+impl Default for EncodeOptions {
+    /// 与 [`QRCode`](crate::QRCode) 一致：纠错级别 H，掩码 0
+    fn default() -> Self {
+        EncodeOptions {
+            ec_level: EcLevel::H,
+            mask: Mask::default(),
+        }
+    }
+}
+
+/// 可复用的编码工作区
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    /// 一维数组：modules[row * module_count + col]
+    /// 0 = 未设置, 1 = 深色, 2 = 已设置但浅色
+    modules: Vec<u8>,
+    module_count: i32,
+    version: Option<Version>,
+    ec_level: EcLevel,
+    mask: Mask,
+    /// 模式、长度与数据位
+    bits: BitBuffer,
+    /// 补齐后的数据码字
+    data: Vec<u8>,
+    /// 所有块的纠错码字，按块依次存放
+    ec: Vec<u8>,
+    /// 交织后的最终码字
+    codewords: Vec<u8>,
+    /// 生成多项式，纠错码字数不变时直接复用
+    generator: Vec<u8>,
+}
+
+impl Workspace {
+    pub fn new() -> Self {
+        let defaults = EncodeOptions::default();
+        Workspace {
+            modules: Vec::new(),
+            module_count: 0,
+            version: None,
+            ec_level: defaults.ec_level,
+            mask: defaults.mask,
+            bits: BitBuffer::new(),
+            data: Vec::new(),
+            ec: Vec::new(),
+            codewords: Vec::new(),
+            generator: Vec::new(),
+        }
+    }
+
+    /// 预分配可容纳 `version` 及以下任意符号的容量
+    ///
+    /// 版本模板在全局缓存中，已经构建过的版本首次编码也不分配内存。
+    pub fn with_capacity(version: Version) -> Self {
+        let count = version.module_count() as usize;
+        // 版本 40 最多 3706 个码字，按最大值预留即可
+        let codewords = 3706;
+        Workspace {
+            modules: Vec::with_capacity(count * count),
+            bits: BitBuffer::with_capacity(codewords * 8),
+            data: Vec::with_capacity(codewords),
+            ec: Vec::with_capacity(codewords),
+            codewords: Vec::with_capacity(codewords),
+            generator: Vec::with_capacity(31),
+            ..Self::new()
+        }
+    }
+
+    /// 每边模块数，编码前或编码失败后为 0
+    pub fn module_count(&self) -> i32 {
+        self.module_count
+    }
+
+    /// 读取 (row, col) 处的模块，越界返回 `false`
+    #[inline(always)]
+    pub fn is_dark(&self, row: i32, col: i32) -> bool {
+        if row < 0 || row >= self.module_count || col < 0 || col >= self.module_count {
+            return false;
+        }
+        let idx = (row as usize) * (self.module_count as usize) + (col as usize);
+        self.modules[idx] == 1
+    }
+
+    /// 当前版本，编码前或编码失败后为 `None`
+    pub fn version(&self) -> Option<Version> {
+        self.version
+    }
+
+    /// 最近一次编码使用的纠错级别
+    pub fn ec_level(&self) -> EcLevel {
+        self.ec_level
+    }
+
+    /// 最近一次编码使用的掩码，编码前或编码失败后为 `None`
+    pub fn mask(&self) -> Option<Mask> {
+        self.version.map(|_| self.mask)
+    }
+
+    /// 交织后的码字（数据码字在前，纠错码字在后）
+    pub fn codewords(&self) -> &[u8] {
+        &self.codewords
+    }
+
+    /// 转换为共享的位压缩矩阵
+    pub fn to_matrix(&self) -> QrMatrix {
+        QrMatrix::from_states(&self.modules, self.module_count as usize)
+    }
+
+    #[inline(always)]
+    fn set_module(&mut self, row: i32, col: i32, is_dark: bool) {
+        let idx = (row as usize) * (self.module_count as usize) + (col as usize);
+        self.modules[idx] = if is_dark { 1 } else { 2 };
+    }
+
+    #[inline(always)]
+    fn is_module_set(&self, row: i32, col: i32) -> bool {
+        let idx = (row as usize) * (self.module_count as usize) + (col as usize);
+        self.modules[idx] != 0
+    }
+
+    /// 清空上一次的全部结果（保留各缓冲区的容量）
+    fn reset(&mut self, opts: &EncodeOptions) {
+        self.modules.clear();
+        self.module_count = 0;
+        self.version = None;
+        self.ec_level = opts.ec_level;
+        self.mask = opts.mask;
+        self.bits.clear();
+        self.data.clear();
+        self.ec.clear();
+        self.codewords.clear();
+    }
+
+    /// 生成补齐、纠错并交织后的码字（调用前已 [`reset`](Self::reset)）
+    fn create_data(&mut self, data: &[u8], version: Version) {
+        let row = rs_block_row(version.get(), self.ec_level);
+        let (count1, dc1, count2, dc2) = (row[0], row[2], row[3], row[5]);
+        let ec_count = (row[1] - row[2]) as usize;
+        let total_data_count = (count1 * dc1 + count2 * dc2) as usize;
+        let block_count = (count1 + count2) as usize;
+
+        self.bits.put(QRMode::MODE_8BIT_BYTE as u32, 4);
+        self.bits.put(
+            data.len() as u32,
+            get_length_in_bits(QRMode::MODE_8BIT_BYTE, version.get()) as usize,
+        );
+        self.bits.put_bytes(data);
+        // 终止符（最多 4 位），末字节未写满的位为 0
+        if self.bits.len_bits() + 4 <= total_data_count * 8 {
+            self.bits.put(0, 4);
+        }
+
+        self.data.extend_from_slice(self.bits.as_bytes());
+        self.data.truncate(total_data_count);
+        for pad in [0xEC, 0x11].into_iter().cycle() {
+            if self.data.len() >= total_data_count {
+                break;
+            }
+            self.data.push(pad);
+        }
+
+        // 同一行内两组块的纠错码字数相同，生成多项式只需一份
+        if self.generator.len() != ec_count + 1 {
+            rs_generator_into(ec_count, &mut self.generator);
+        }
+        self.ec.resize(block_count * ec_count, 0);
+        let block_range = |b: usize| {
+            let b = b as i32;
+            let start = if b < count1 {
+                b * dc1
+            } else {
+                count1 * dc1 + (b - count1) * dc2
+            };
+            let len = if b < count1 { dc1 } else { dc2 };
+            start as usize..(start + len) as usize
+        };
+        for (b, ec) in self.ec.chunks_exact_mut(ec_count.max(1)).enumerate() {
+            rs_remainder(&self.data[block_range(b)], &self.generator, ec);
+        }
+
+        for i in 0..dc1.max(dc2) as usize {
+            for b in 0..block_count {
+                let range = block_range(b);
+                if i < range.len() {
+                    self.codewords.push(self.data[range.start + i]);
+                }
+            }
+        }
+        for i in 0..ec_count {
+            for b in 0..block_count {
+                self.codewords.push(self.ec[b * ec_count + i]);
+            }
+        }
+    }
+
+    /// 按之字形顺序放置码字并应用掩码
+    fn map_data(&mut self) {
+        let count = self.module_count;
+        let mut inc = -1;
+        let mut row = count - 1;
+        let mut bit_index = 7;
+        let mut byte_index = 0;
+
+        let mut col = count - 1;
+        while col > 0 {
+            if col == 6 {
+                col -= 1;
+            }
+            loop {
+                for c in 0..2 {
+                    let col_idx = col - c;
+                    if self.is_module_set(row, col_idx) {
+                        continue;
+                    }
+                    let mut dark = self
+                        .codewords
+                        .get(byte_index)
+                        .is_some_and(|&byte| (byte >> bit_index) & 1 == 1);
+                    if mask_bit(self.mask, row as usize, col_idx as usize) {
+                        dark = !dark;
+                    }
+                    self.set_module(row, col_idx, dark);
+
+                    if bit_index == 0 {
+                        bit_index = 7;
+                        byte_index += 1;
+                    } else {
+                        bit_index -= 1;
+                    }
+                }
+
+                row += inc;
+                if row < 0 || count <= row {
+                    row -= inc;
+                    inc = -inc;
+                    break;
+                }
+            }
+            col -= 2;
+        }
+    }
+}
+
+impl Default for Workspace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 以字节模式将 `data` 编码到 `ws` 中
+///
+/// 自动选择能容纳数据的最小版本（1-40）。每次调用都会完全重置工作区，
+/// 与之前的编码结果无关；数据放不下时返回 [`QrError::DataTooLong`]，工作区保持为空。
+pub fn encode_into(ws: &mut Workspace, data: &[u8], opts: &EncodeOptions) -> QrResult<()> {
+    ws.reset(opts);
+
+    let version = min_version(data.len(), opts.ec_level)?;
+    let count = version.module_count();
+    ws.module_count = count;
+    ws.modules
+        .extend_from_slice(VersionTemplate::get(version).states());
+    write_format_states(&mut ws.modules, count, opts.ec_level, opts.mask);
+
+    ws.create_data(data, version);
+    ws.map_data();
+    ws.version = Some(version);
+    Ok(())
+}
+
+impl QrEncoder for Workspace {
+    /// 沿用最近一次的纠错级别与掩码（初始为 H 与掩码 0）
+    fn encode(&mut self, data: &[u8]) -> QrResult<QrSymbol> {
+        let opts = EncodeOptions {
+            ec_level: self.ec_level,
+            mask: self.mask,
+        };
+        encode_into(self, data, &opts)?;
+        let version = self.version.ok_or(QrError::InvalidVersion(0))?;
+        QrSymbol::from_matrix(
+            version,
+            self.ec_level,
+            self.mask,
+            Workspace::to_matrix(self),
+        )
+    }
+
+    fn module_count(&self) -> i32 {
+        self.module_count
+    }
+
+    fn is_dark(&self, row: i32, col: i32) -> bool {
+        Workspace::is_dark(self, row, col)
+    }
+
+    fn version(&self) -> Option<Version> {
+        self.version
+    }
+
+    fn ec_level(&self) -> EcLevel {
+        self.ec_level
+    }
+
+    fn mask(&self) -> Option<Mask> {
+        Workspace::mask(self)
+    }
+
+    fn to_matrix(&self) -> QrMatrix {
+        Workspace::to_matrix(self)
+    }
+}
+
+/// 能以字节模式容纳 `len` 字节的最小版本，不分配内存
+fn min_version(len: usize, ec_level: EcLevel) -> QrResult<Version> {
+    let mut last = (0, 0);
+    for version in Version::all() {
+        let row = rs_block_row(version.get(), ec_level);
+        let capacity = ((row[0] * row[2] + row[3] * row[5]) * 8) as usize;
+        let bits = 4 + get_length_in_bits(QRMode::MODE_8BIT_BYTE, version.get()) as usize + len * 8;
+        if bits <= capacity {
+            return Ok(version);
+        }
+        last = (bits, capacity);
+    }
+    Err(QrError::DataTooLong {
+        bits: last.0,
+        capacity: last.1,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qrcode_rust_shared::qr_builder::QrBuilder;
+
+    fn built(data: &[u8], opts: &EncodeOptions, version: Version) -> QrSymbol {
+        QrBuilder::from_bytes(data)
+            .ec_level(opts.ec_level)
+            .version_range(version..=version)
+            .mask(opts.mask)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_encode_into_matches_builder() {
+        let mut ws = Workspace::new();
+        let cases: [(&[u8], EcLevel, u8); 4] = [
+            (b"Hello World", EcLevel::M, 0),
+            (b"https://github.com/veaba/qrcodes", EcLevel::H, 3),
+            (&[0xAB; 300], EcLevel::Q, 5),
+            (&[0x5A; 1200], EcLevel::L, 7),
+        ];
+        for (data, ec_level, mask) in cases {
+            let opts = EncodeOptions {
+                ec_level,
+                mask: Mask::new(mask).unwrap(),
+            };
+            encode_into(&mut ws, data, &opts).unwrap();
+            let version = ws.version().unwrap();
+            assert_eq!(ws.to_matrix(), *built(data, &opts, version).matrix());
+        }
+        // 覆盖版本信息与多组块
+        assert!(ws.version().unwrap().get() >= 7);
+    }
+
+    #[test]
+    fn test_encode_into_resets_state() {
+        let opts = EncodeOptions::default();
+        let mut reused = Workspace::new();
+        encode_into(&mut reused, &[b'x'; 200], &opts).unwrap();
+        encode_into(&mut reused, b"Hi", &opts).unwrap();
+
+        let mut fresh = Workspace::new();
+        encode_into(&mut fresh, b"Hi", &opts).unwrap();
+        assert_eq!(reused.version(), Some(Version::MIN));
+        assert_eq!(reused.module_count(), 21);
+        assert_eq!(reused.to_matrix(), fresh.to_matrix());
+        assert_eq!(reused.codewords(), fresh.codewords());
+
+        let err = encode_into(&mut reused, &[0; 3000], &opts).unwrap_err();
+        assert!(matches!(err, QrError::DataTooLong { .. }));
+        assert_eq!(reused.version(), None);
+        assert_eq!(reused.mask(), None);
+        assert_eq!(reused.module_count(), 0);
+        assert!(!reused.is_dark(0, 0));
+        assert!(reused.codewords().is_empty());
+    }
+
+    #[test]
+    fn test_workspace_encoder() {
+        let mut ws = Workspace::new();
+        let symbol = ws.encode(b"Hello").unwrap();
+        assert_eq!(symbol.ec_level(), EcLevel::H);
+        assert_eq!(Some(symbol), QrEncoder::symbol(&ws));
+    }
+}
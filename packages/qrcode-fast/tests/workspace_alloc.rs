@@ -0,0 +1,95 @@
+//! 验证 `encode_into` 在稳定状态下不分配内存
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use qrcode_fast::{encode_into, EcLevel, EncodeOptions, Mask, Workspace};
+
+/// 按线程统计分配次数的分配器，避免并行测试互相干扰
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+#[test]
+fn test_encode_into_steady_state_allocations() {
+    let payloads: Vec<Vec<u8>> = (0..200)
+        .map(|i| format!("https://example.com/item/{}", i * 7919).into_bytes())
+        .chain([vec![0x42; 1200], b"Hi".to_vec()])
+        .collect();
+    let options: Vec<EncodeOptions> = EcLevel::ALL
+        .iter()
+        .zip(Mask::ALL)
+        .map(|(&ec_level, mask)| EncodeOptions { ec_level, mask })
+        .collect();
+
+    let mut ws = Workspace::new();
+    // 预热：工作区容量增长到最大的符号
+    for opts in &options {
+        for data in &payloads {
+            encode_into(&mut ws, data, opts).unwrap();
+        }
+    }
+
+    let before = allocations();
+    for opts in &options {
+        for data in &payloads {
+            encode_into(&mut ws, data, opts).unwrap();
+        }
+    }
+    assert_eq!(allocations() - before, 0);
+}
+
+#[test]
+fn test_with_capacity_first_encode() {
+    let data = vec![0x42; 2000];
+    let sizes = [100, 1000];
+    let options: Vec<EncodeOptions> = EcLevel::ALL
+        .iter()
+        .map(|&ec_level| EncodeOptions {
+            ec_level,
+            ..EncodeOptions::default()
+        })
+        .collect();
+
+    // 版本模板全局缓存，第一次用到某个版本时构建
+    let mut warm_up = Workspace::new();
+    for opts in &options {
+        for len in sizes {
+            encode_into(&mut warm_up, &data[..len], opts).unwrap();
+        }
+    }
+
+    let mut ws = Workspace::with_capacity(qrcode_fast::Version::MAX);
+    let before = allocations();
+    for opts in &options {
+        for len in sizes {
+            encode_into(&mut ws, &data[..len], opts).unwrap();
+        }
+    }
+    assert_eq!(allocations() - before, 0);
+}
@@ -0,0 +1,286 @@
+//! QR Code Macros - 编译期生成二维码
+//!
+//! 固件和静态站点里嵌入的固定网址可以在编译期完成编码，二进制中不包含编码器代码：
+//!
+//! ```
+//! use qrcode_macros::{qr, qr_svg};
+//! use qrcode_rust_shared::StaticQrMatrix;
+//!
+//! // 位压缩矩阵，运行时只读取常量数据
+//! static HOME: StaticQrMatrix = qr!("https://example.com", ec = H);
+//! assert!(HOME.is_dark(0, 0));
+//!
+//! // SVG 字符串字面量
+//! const LOGO: &str = qr_svg!("https://example.com", ec = M, size = 128);
+//! assert!(LOGO.starts_with("<svg"));
+//! ```
+//!
+//! 数据可以是字符串或字节串字面量（`b"..."`），之后是可选的 `key = value` 参数：
+//!
+//! | 参数 | 适用 | 说明 |
+//! |------|------|------|
+//! | `ec = L/M/Q/H` | 全部 | 纠错级别，默认 H |
+//! | `version = 1..=40` | 全部 | 固定版本，默认取能容纳数据的最小版本 |
+//! | `mask = 0..=7` | 全部 | 固定掩码，默认按惩罚分选择 |
+//! | `size = 256` | `qr_svg!` | SVG 边长 |
+//! | `dark = "#000000"` / `light = "#ffffff"` | `qr_svg!` | 颜色 |
+//! | `quiet_zone = 0` | `qr_svg!` | 空白边宽度（模块数） |
+//! | `crate = path` | `qr!` | `StaticQrMatrix` 所在的 crate，默认 `::qrcode_rust_shared` |
+//!
+//! 数据放不下或参数不合法时在编译期报错。
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, Lit, LitStr, Token};
+
+use qrcode_rust_shared::{render_svg, EcLevel, Mask, QrBuilder, QrSymbol, Version};
+
+/// 编译期编码，展开为 `StaticQrMatrix` 常量表达式
+#[proc_macro]
+pub fn qr(input: TokenStream) -> TokenStream {
+    expand(input, expand_qr)
+}
+
+/// 编译期编码并渲染，展开为 SVG 字符串字面量（`&'static str`）
+#[proc_macro]
+pub fn qr_svg(input: TokenStream) -> TokenStream {
+    expand(input, expand_qr_svg)
+}
+
+fn expand(input: TokenStream, f: fn(Args) -> syn::Result<TokenStream2>) -> TokenStream {
+    syn::parse::<Args>(input)
+        .and_then(f)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_qr(mut args: Args) -> syn::Result<TokenStream2> {
+    let krate = args
+        .krate
+        .take()
+        .unwrap_or_else(|| quote!(::qrcode_rust_shared));
+    args.reject(&["size", "dark", "light", "quiet_zone"], "qr_svg!")?;
+    let symbol = args.encode()?;
+
+    let version = symbol.version().get() as u8;
+    let ec_level = Ident::new(&symbol.ec_level().to_string(), Span::call_site());
+    let mask = symbol.mask().get();
+    let bits = symbol.matrix().to_bytes();
+    Ok(quote! {
+        #krate::StaticQrMatrix::from_raw(
+            #version,
+            #krate::EcLevel::#ec_level,
+            #mask,
+            &[#(#bits),*],
+        )
+    })
+}
+
+fn expand_qr_svg(args: Args) -> syn::Result<TokenStream2> {
+    if let Some(krate) = &args.krate {
+        return Err(syn::Error::new_spanned(
+            krate,
+            "`crate` only applies to qr!",
+        ));
+    }
+    let symbol = args.encode()?;
+
+    let size = args.int::<i32>("size")?.unwrap_or(256);
+    let dark = args
+        .string("dark")?
+        .unwrap_or_else(|| "#000000".to_string());
+    let light = args
+        .string("light")?
+        .unwrap_or_else(|| "#ffffff".to_string());
+    let quiet_zone = args.int::<usize>("quiet_zone")?.unwrap_or(0);
+
+    let matrix = symbol.matrix().with_quiet_zone(quiet_zone);
+    let svg = LitStr::new(&render_svg(&matrix, size, &dark, &light), args.span);
+    Ok(quote!(#svg))
+}
+
+/// 宏参数：`[crate = path,] "data" [, key = value]*`
+struct Args {
+    krate: Option<TokenStream2>,
+    data: Vec<u8>,
+    span: Span,
+    options: Vec<(Ident, OptionValue)>,
+}
+
+enum OptionValue {
+    Lit(Lit),
+    Ident(Ident),
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // 转发宏以 `crate = $crate` 开头，路径原样保留
+        let mut krate = None;
+        if input.peek(Token![crate]) && input.peek2(Token![=]) {
+            input.parse::<Token![crate]>()?;
+            input.parse::<Token![=]>()?;
+            let mut path = TokenStream2::new();
+            while !input.is_empty() && !input.peek(Token![,]) {
+                path.extend([input.parse::<TokenTree>()?]);
+            }
+            input.parse::<Token![,]>()?;
+            krate = Some(path);
+        }
+
+        let lit: Lit = input.parse()?;
+        let data = match &lit {
+            Lit::Str(s) => s.value().into_bytes(),
+            Lit::ByteStr(b) => b.value(),
+            _ => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "expected a string or byte string",
+                ))
+            }
+        };
+
+        let mut options: Vec<(Ident, OptionValue)> = Vec::new();
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let value = if input.peek(Ident) {
+                OptionValue::Ident(input.parse()?)
+            } else {
+                OptionValue::Lit(input.parse()?)
+            };
+            if options.iter().any(|(k, _)| *k == key) {
+                return Err(syn::Error::new(key.span(), format!("duplicate `{key}`")));
+            }
+            options.push((key, value));
+        }
+
+        let args = Args {
+            krate,
+            data,
+            span: lit.span(),
+            options,
+        };
+        args.reject_unknown()?;
+        Ok(args)
+    }
+}
+
+impl Args {
+    const KEYS: [&'static str; 7] = [
+        "ec",
+        "version",
+        "mask",
+        "size",
+        "dark",
+        "light",
+        "quiet_zone",
+    ];
+
+    fn get(&self, key: &str) -> Option<&OptionValue> {
+        self.options
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value)
+    }
+
+    fn reject_unknown(&self) -> syn::Result<()> {
+        match self
+            .options
+            .iter()
+            .find(|(k, _)| !Self::KEYS.iter().any(|known| k == known))
+        {
+            Some((key, _)) => Err(syn::Error::new(
+                key.span(),
+                format!(
+                    "unknown option `{key}`, expected one of: {}",
+                    Self::KEYS.join(", ")
+                ),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// 拒绝只属于另一个宏的参数
+    fn reject(&self, keys: &[&str], owner: &str) -> syn::Result<()> {
+        match self
+            .options
+            .iter()
+            .find(|(k, _)| keys.iter().any(|key| k == key))
+        {
+            Some((key, _)) => Err(syn::Error::new(
+                key.span(),
+                format!("`{key}` only applies to {owner}"),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn int<N>(&self, key: &str) -> syn::Result<Option<N>>
+    where
+        N: std::str::FromStr,
+        N::Err: std::fmt::Display,
+    {
+        match self.get(key) {
+            None => Ok(None),
+            Some(OptionValue::Lit(Lit::Int(n))) => n.base10_parse().map(Some),
+            Some(value) => Err(value.error(format!("`{key}` expects an integer"))),
+        }
+    }
+
+    fn string(&self, key: &str) -> syn::Result<Option<String>> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(OptionValue::Lit(Lit::Str(s))) => Ok(Some(s.value())),
+            Some(value) => Err(value.error(format!("`{key}` expects a string"))),
+        }
+    }
+
+    /// 按参数调用共享构建器编码
+    fn encode(&self) -> syn::Result<QrSymbol> {
+        let mut builder = QrBuilder::from_bytes(&self.data);
+        if let Some(value) = self.get("ec") {
+            let name = match value {
+                OptionValue::Ident(ident) => ident.to_string(),
+                OptionValue::Lit(Lit::Str(s)) => s.value(),
+                OptionValue::Lit(_) => String::new(),
+            };
+            let level: EcLevel = name
+                .parse()
+                .map_err(|_| value.error("`ec` expects one of L, M, Q, H"))?;
+            builder = builder.ec_level(level);
+        }
+        if let Some(version) = self.int::<i32>("version")? {
+            let version = Version::new(version).map_err(|err| self.error("version", err))?;
+            builder = builder.version_range(version..=version);
+        }
+        if let Some(mask) = self.int::<u8>("mask")? {
+            let mask = Mask::new(mask).map_err(|err| self.error("mask", err))?;
+            builder = builder.mask(mask);
+        }
+        builder
+            .build()
+            .map_err(|err| syn::Error::new(self.span, err))
+    }
+
+    fn error(&self, key: &str, message: impl std::fmt::Display) -> syn::Error {
+        match self.get(key) {
+            Some(value) => value.error(message),
+            None => syn::Error::new(self.span, message),
+        }
+    }
+}
+
+impl OptionValue {
+    fn error(&self, message: impl std::fmt::Display) -> syn::Error {
+        match self {
+            OptionValue::Lit(lit) => syn::Error::new(lit.span(), message),
+            OptionValue::Ident(ident) => syn::Error::new(ident.span(), message),
+        }
+    }
+}
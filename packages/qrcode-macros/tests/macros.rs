@@ -0,0 +1,58 @@
+use qrcode_macros::{qr, qr_svg};
+use qrcode_rust_shared::{render_svg, EcLevel, Mask, QrBuilder, StaticQrMatrix, Version};
+
+static HOME: StaticQrMatrix = qr!("https://example.com", ec = H);
+
+#[test]
+fn test_qr_matches_builder() {
+    let symbol = QrBuilder::new("https://example.com")
+        .ec_level(EcLevel::H)
+        .build()
+        .unwrap();
+    assert_eq!(HOME.version(), symbol.version());
+    assert_eq!(HOME.ec_level(), EcLevel::H);
+    assert_eq!(HOME.mask(), symbol.mask());
+    assert_eq!(HOME.to_matrix(), *symbol.matrix());
+}
+
+#[test]
+fn test_qr_options() {
+    const FIXED: StaticQrMatrix = qr!(b"\x00\xFFbinary", ec = "l", version = 7, mask = 3,);
+    assert_eq!(FIXED.version(), Version::new(7).unwrap());
+    assert_eq!(FIXED.ec_level(), EcLevel::L);
+    assert_eq!(FIXED.mask(), Mask::new(3).unwrap());
+    assert_eq!(FIXED.module_count(), 45);
+
+    let symbol = QrBuilder::from_bytes(b"\x00\xFFbinary")
+        .ec_level(EcLevel::L)
+        .version_range(Version::new(7).unwrap()..=Version::new(7).unwrap())
+        .mask(Mask::new(3).unwrap())
+        .build()
+        .unwrap();
+    assert_eq!(FIXED.to_matrix(), *symbol.matrix());
+
+    // 也可以在函数体内直接使用
+    let local = qr!("Hi", ec = M);
+    assert_eq!(local.module_count(), 21);
+}
+
+#[test]
+fn test_qr_svg() {
+    const SVG: &str = qr_svg!("Hello", ec = M, size = 128, dark = "#112233");
+    let symbol = QrBuilder::new("Hello")
+        .ec_level(EcLevel::M)
+        .build()
+        .unwrap();
+    assert_eq!(SVG, render_svg(symbol.matrix(), 128, "#112233", "#ffffff"));
+
+    let padded: &'static str = qr_svg!("Hello", ec = M, quiet_zone = 4);
+    assert_eq!(
+        padded,
+        render_svg(
+            &symbol.matrix().with_quiet_zone(4),
+            256,
+            "#000000",
+            "#ffffff"
+        )
+    );
+}
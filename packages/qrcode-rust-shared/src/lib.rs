@@ -5,20 +5,75 @@
 
 // Core modules shared between implementations
 pub mod qr_8bit_byte;
+pub mod qr_base64;
 pub mod qr_bit_buffer;
+pub mod qr_builder;
 pub mod qr_code_model;
+pub mod qr_color;
+#[cfg(any(test, feature = "test-support"))]
+pub mod qr_conformance;
+pub mod qr_damage;
+pub mod qr_encoder;
+pub mod qr_error;
+pub mod qr_explain;
+pub mod qr_mask;
 pub mod qr_math;
+pub mod qr_matrix;
+pub mod qr_module_role;
+pub mod qr_pdf;
+pub mod qr_png;
 pub mod qr_polynomial;
+pub mod qr_print;
+pub mod qr_raster;
+pub mod qr_render;
 pub mod qr_rs_block;
+pub mod qr_segment;
+#[cfg(feature = "serde")]
+pub mod qr_serde;
+pub mod qr_static;
+pub mod qr_svg;
+pub mod qr_symbol;
+pub mod qr_template;
+pub mod qr_terminal;
 pub mod qr_util;
+pub mod qr_version;
 
 // Re-export commonly used types
 pub use qr_8bit_byte::QR8bitByte;
 pub use qr_bit_buffer::BitBuffer;
-pub use qr_code_model::{QRErrorCorrectLevel, QRMode};
+pub use qr_builder::QrBuilder;
+pub use qr_code_model::{EcLevel, QRErrorCorrectLevel, QRMode};
+pub use qr_color::parse_color;
+pub use qr_damage::{BlockDamage, DamageReport, ModuleRect};
+pub use qr_encoder::{check_capacity, QrEncoder};
+pub use qr_error::{QrError, QrResult};
+pub use qr_explain::{BlockInfo, Explanation, SegmentInfo};
+pub use qr_mask::Mask;
 pub use qr_math::QRMath;
-pub use qr_polynomial::Polynomial;
-pub use qr_rs_block::{get_rs_blocks, QRRSBlock};
+pub use qr_matrix::QrMatrix;
+pub use qr_module_role::{ModuleRole, RoleMap};
+pub use qr_pdf::{render_pdf, PdfColor, PdfOptions};
+pub use qr_png::{encode_png, png_data_url, render_png, PngFormat, PngRenderer};
+pub use qr_polynomial::{rs_generator, rs_generator_into, rs_remainder, Polynomial};
+pub use qr_print::{PrintPlan, PrintSpec};
+pub use qr_raster::{
+    render_into, render_rgba, render_rgba_fit, PixelFormat, RasterOptions, RgbaRenderer, Scaling,
+};
+pub use qr_render::{render, MatrixRenderer, RenderInfo, Renderer};
+pub use qr_rs_block::{get_rs_blocks, rs_block_row, QRRSBlock};
+pub use qr_segment::{Segment, SegmentMode};
+pub use qr_static::StaticQrMatrix;
+pub use qr_svg::{
+    render_svg, render_svg_rects, render_svg_with, render_svg_with_options, SvgOptions,
+    SvgPathMode, SvgPathRenderer, SvgRectRenderer, SvgSize,
+};
+pub use qr_symbol::QrSymbol;
+pub use qr_template::VersionTemplate;
+pub use qr_terminal::{
+    render_ascii, render_braille, render_half_blocks, render_kitty, render_sixel, GraphicsProtocol,
+    TerminalHints, TerminalOptions,
+};
+pub use qr_version::Version;
 
 /// Version information
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
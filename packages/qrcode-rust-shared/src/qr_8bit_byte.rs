@@ -1,13 +1,18 @@
 //! QR Code 8-bit Byte Mode
 
 pub struct QR8bitByte {
-    pub data: String,
+    pub data: Vec<u8>,
 }
 
 impl QR8bitByte {
     pub fn new(data: &str) -> Self {
+        Self::from_bytes(data.as_bytes())
+    }
+
+    /// 直接使用原始字节（不要求是合法的 UTF-8）
+    pub fn from_bytes(data: &[u8]) -> Self {
         QR8bitByte {
-            data: data.to_string(),
+            data: data.to_vec(),
         }
     }
 
@@ -16,9 +21,7 @@ impl QR8bitByte {
     }
 
     pub fn write(&self, buffer: &mut crate::qr_bit_buffer::BitBuffer) {
-        for byte in self.data.bytes() {
-            buffer.put(byte as i32, 8);
-        }
+        buffer.put_bytes(&self.data);
     }
 }
 
@@ -34,6 +37,16 @@ mod tests {
 
         let mut buf = BitBuffer::new();
         data.write(&mut buf);
-        assert_eq!(buf.length, 16); // 2 bytes * 8 bits
+        assert_eq!(buf.len_bits(), 16); // 2 bytes * 8 bits
+    }
+
+    #[test]
+    fn test_qr8bit_byte_binary() {
+        let data = QR8bitByte::from_bytes(&[0xFF, 0x00, 0xC3]);
+        assert_eq!(data.get_length(), 3);
+
+        let mut buf = BitBuffer::new();
+        data.write(&mut buf);
+        assert_eq!(buf.as_bytes(), &[0xFF, 0x00, 0xC3]);
     }
 }
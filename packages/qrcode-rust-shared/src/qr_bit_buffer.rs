@@ -1,39 +1,109 @@
 //! QR Code Bit Buffer
+//!
+//! 以 `u8` 存储的位缓冲区，高位在前。`put` 一次写入整个取值，
+//! 按字节边界分段拷贝，而不是逐位追加。
 
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct BitBuffer {
-    pub buffer: Vec<i32>,
-    pub length: usize,
+    bytes: Vec<u8>,
+    len_bits: usize,
 }
 
 impl BitBuffer {
     pub fn new() -> Self {
         BitBuffer {
-            buffer: Vec::new(),
-            length: 0,
+            bytes: Vec::new(),
+            len_bits: 0,
         }
     }
 
-    pub fn put(&mut self, num: i32, length: i32) {
-        for i in (0..length).rev() {
-            self.put_bit(((num >> i) & 1) == 1);
+    /// 预分配至少 `bits` 位的容量
+    pub fn with_capacity(bits: usize) -> Self {
+        BitBuffer {
+            bytes: Vec::with_capacity(bits.div_ceil(8)),
+            len_bits: 0,
+        }
+    }
+
+    /// 额外预留 `bits` 位的容量
+    pub fn reserve(&mut self, bits: usize) {
+        let needed = (self.len_bits + bits).div_ceil(8);
+        self.bytes.reserve(needed.saturating_sub(self.bytes.len()));
+    }
+
+    /// 写入 `value` 的低 `bits` 位（`bits` <= 32），高位在前
+    pub fn put(&mut self, value: u32, bits: usize) {
+        debug_assert!(bits <= 32, "put: at most 32 bits at a time");
+        let mut remaining = bits;
+        while remaining > 0 {
+            let used = self.len_bits % 8;
+            if used == 0 {
+                self.bytes.push(0);
+            }
+            let free = 8 - used;
+            let take = free.min(remaining);
+            let chunk = (value >> (remaining - take)) & ((1u32 << take) - 1);
+            let last = self.bytes.len() - 1;
+            self.bytes[last] |= (chunk << (free - take)) as u8;
+            self.len_bits += take;
+            remaining -= take;
         }
     }
 
     pub fn put_bit(&mut self, bit: bool) {
-        let buf_index = self.length / 8;
-        if self.buffer.len() <= buf_index {
-            self.buffer.push(0);
+        self.put(bit as u32, 1);
+    }
+
+    /// 写入整字节序列，字节对齐时直接拷贝
+    pub fn put_bytes(&mut self, data: &[u8]) {
+        if self.len_bits.is_multiple_of(8) {
+            self.bytes.extend_from_slice(data);
+            self.len_bits += data.len() * 8;
+        } else {
+            self.reserve(data.len() * 8);
+            for &byte in data {
+                self.put(byte as u32, 8);
+            }
         }
-        if bit {
-            self.buffer[buf_index] |= 0x80 >> (self.length % 8);
+    }
+
+    /// 追加另一个缓冲区的全部位
+    pub fn append(&mut self, other: &BitBuffer) {
+        let full = other.len_bits / 8;
+        self.put_bytes(&other.bytes[..full]);
+        let rest = other.len_bits % 8;
+        if rest > 0 {
+            self.put((other.bytes[full] >> (8 - rest)) as u32, rest);
         }
-        self.length += 1;
     }
-}
 
-impl Default for BitBuffer {
-    fn default() -> Self {
-        Self::new()
+    /// 清空内容，保留已分配的容量
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+        self.len_bits = 0;
+    }
+
+    /// 已写入的位数
+    pub fn len_bits(&self) -> usize {
+        self.len_bits
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len_bits == 0
+    }
+
+    /// 读取第 `index` 位
+    pub fn get(&self, index: usize) -> bool {
+        index < self.len_bits && (self.bytes[index / 8] >> (7 - index % 8)) & 1 == 1
+    }
+
+    /// 底层字节（最后一个字节未写满的位为 0）
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
     }
 }
 
@@ -45,8 +115,8 @@ mod tests {
     fn test_bit_buffer_basic() {
         let mut buf = BitBuffer::new();
         buf.put(0x0F, 4); // 1111
-        assert_eq!(buf.length, 4);
-        assert_eq!(buf.buffer[0], 0xF0); // 11110000
+        assert_eq!(buf.len_bits(), 4);
+        assert_eq!(buf.as_bytes()[0], 0xF0); // 11110000
     }
 
     #[test]
@@ -54,8 +124,60 @@ mod tests {
         let mut buf = BitBuffer::new();
         buf.put(0xFF, 8);
         buf.put(0x01, 1);
-        assert_eq!(buf.length, 9);
-        assert_eq!(buf.buffer[0], 0xFF);
-        assert_eq!(buf.buffer[1], 0x80);
+        assert_eq!(buf.len_bits(), 9);
+        assert_eq!(buf.as_bytes()[0], 0xFF);
+        assert_eq!(buf.as_bytes()[1], 0x80);
+
+        buf.clear();
+        assert!(buf.is_empty());
+        buf.put(0x01, 1);
+        assert_eq!(buf.as_bytes(), &[0x80]);
+    }
+
+    #[test]
+    fn test_put_spanning_bytes() {
+        let mut buf = BitBuffer::new();
+        buf.put(0b101, 3);
+        buf.put(0x1234_5678, 32);
+        buf.put(0b1, 1);
+        assert_eq!(buf.len_bits(), 36);
+        assert_eq!(buf.as_bytes(), &[0xA2, 0x46, 0x8A, 0xCF, 0x10]);
+        assert!(buf.get(0) && !buf.get(1) && buf.get(2));
+        assert!(!buf.get(36));
+    }
+
+    #[test]
+    fn test_put_matches_bitwise() {
+        let values = [(0x3FFu32, 10), (0, 3), (0xABCD, 16), (1, 1), (0x7F, 7)];
+        let mut fast = BitBuffer::new();
+        let mut slow = BitBuffer::new();
+        for (value, bits) in values {
+            fast.put(value, bits);
+            for i in (0..bits).rev() {
+                slow.put_bit((value >> i) & 1 == 1);
+            }
+        }
+        assert_eq!(fast, slow);
+    }
+
+    #[test]
+    fn test_put_bytes_and_append() {
+        let mut a = BitBuffer::new();
+        a.put(0b1, 1);
+        a.put_bytes(&[0xFF, 0x00]);
+        assert_eq!(a.len_bits(), 17);
+        assert_eq!(a.as_bytes(), &[0xFF, 0x80, 0x00]);
+
+        let mut b = BitBuffer::with_capacity(16);
+        b.put(0b0110, 4);
+        b.append(&a);
+        assert_eq!(b.len_bits(), 21);
+        assert_eq!(b.as_bytes(), &[0x6F, 0xF8, 0x00]);
+
+        let mut aligned = BitBuffer::new();
+        aligned.put_bytes(&[0x12]);
+        aligned.append(&b);
+        assert_eq!(aligned.as_bytes(), &[0x12, 0x6F, 0xF8, 0x00]);
+        assert_eq!(aligned.len_bits(), 29);
     }
 }
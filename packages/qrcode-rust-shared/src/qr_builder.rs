@@ -0,0 +1,539 @@
+//! QR Code Builder - 链式编码接口
+//!
+//! ```
+//! use qrcode_rust_shared::{EcLevel, QrBuilder, Version};
+//!
+//! let level: EcLevel = "M".parse().unwrap();
+//! let symbol = QrBuilder::new("Hello World")
+//!     .ec_level(level)
+//!     .version_range(Version::MIN..=Version::new(10).unwrap())
+//!     .boost_ecl(true)
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(symbol.module_count(), 21);
+//! ```
+//!
+//! 编码流程：选择最小可用版本 → （可选）提升纠错级别 → 生成数据码字并填充
+//! → 分块计算 RS 纠错码并交织 → 绘制功能图案 → 放置数据 → 选择掩码。
+
+use core::ops::{Range, RangeInclusive};
+
+use crate::qr_bit_buffer::BitBuffer;
+use crate::qr_code_model::QRErrorCorrectLevel;
+use crate::qr_error::{QrError, QrResult};
+use crate::qr_mask::{best_mask, score_masks, Mask, MASK_COUNT};
+use crate::qr_matrix::QrMatrix;
+use crate::qr_module_role::{ModuleRole, RoleMap};
+use crate::qr_polynomial::{rs_generator, rs_remainder};
+use crate::qr_rs_block::get_rs_blocks;
+use crate::qr_segment::Segment;
+use crate::qr_symbol::QrSymbol;
+use crate::qr_template::{format_info_bits, format_info_positions, put, VersionTemplate};
+use crate::qr_version::Version;
+
+/// ECI 指派号上限
+const MAX_ECI: u32 = 999_999;
+
+/// 二维码编码构建器
+#[derive(Debug, Clone)]
+pub struct QrBuilder {
+    segments: Vec<Segment>,
+    ec_level: QRErrorCorrectLevel,
+    version_range: RangeInclusive<Version>,
+    mask: Option<Mask>,
+    boost_ecl: bool,
+}
+
+/// 一个 RS 块：数据码字在 [`Encoded::data_codewords`] 中的范围及其纠错码字
+#[derive(Debug, Clone)]
+pub(crate) struct EncodedBlock {
+    pub(crate) data: Range<usize>,
+    pub(crate) ec: Vec<u8>,
+}
+
+/// 码字阶段的中间结果，`build` 与 `explain` 共用
+#[derive(Debug, Clone)]
+pub(crate) struct Encoded {
+    /// 各段写入后、填充前的位数
+    pub(crate) segment_bits: usize,
+    /// 填充后的数据码字
+    pub(crate) data_codewords: Vec<u8>,
+    pub(crate) blocks: Vec<EncodedBlock>,
+    /// 交织后的最终码字序列
+    pub(crate) codewords: Vec<u8>,
+}
+
+/// 编码各阶段的完整结果
+pub(crate) struct Parts {
+    pub(crate) symbol: QrSymbol,
+    pub(crate) encoded: Encoded,
+    /// 8 个掩码的惩罚分（只在需要时计算）
+    pub(crate) penalties: Option<[u32; MASK_COUNT as usize]>,
+}
+
+impl QrBuilder {
+    /// 以字节模式编码 `data`（UTF-8）
+    ///
+    /// 默认：纠错级别 H，版本 1-40，自动选择掩码，不带 ECI，不提升纠错级别。
+    pub fn new(data: &str) -> Self {
+        Self::from_bytes(data.as_bytes())
+    }
+
+    /// 以字节模式编码原始二进制数据（如 CBOR、压缩数据、签名令牌）
+    ///
+    /// 默认值同 [`new`](Self::new)。
+    pub fn from_bytes(data: &[u8]) -> Self {
+        QrBuilder {
+            segments: vec![Segment::Byte(data.to_vec())],
+            ec_level: QRErrorCorrectLevel::H,
+            version_range: Version::MIN..=Version::MAX,
+            mask: None,
+            boost_ecl: false,
+        }
+    }
+
+    /// 设置纠错级别
+    pub fn ec_level(mut self, level: QRErrorCorrectLevel) -> Self {
+        self.ec_level = level;
+        self
+    }
+
+    /// 限制可选版本范围，取其中能容纳数据的最小版本
+    pub fn version_range(mut self, range: RangeInclusive<Version>) -> Self {
+        self.version_range = range;
+        self
+    }
+
+    /// 固定掩码，不设置时按惩罚分自动选择
+    pub fn mask(mut self, mask: Mask) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// 在数据前插入 ECI 头（如 26 = UTF-8），重复调用时替换之前的值
+    pub fn eci(mut self, assignment: u32) -> Self {
+        self.segments.retain(|seg| !matches!(seg, Segment::Eci(_)));
+        self.segments.insert(0, Segment::Eci(assignment));
+        self
+    }
+
+    /// 在不增大版本的前提下，尽量使用更高的纠错级别
+    pub fn boost_ecl(mut self, boost: bool) -> Self {
+        self.boost_ecl = boost;
+        self
+    }
+
+    /// 要编码的数据段
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// 执行编码
+    pub fn build(&self) -> QrResult<QrSymbol> {
+        self.build_parts(false).map(|parts| parts.symbol)
+    }
+
+    /// 执行编码并保留中间结果，`score_all_masks` 为 true 时总是计算 8 个掩码的惩罚分
+    pub(crate) fn build_parts(&self, score_all_masks: bool) -> QrResult<Parts> {
+        let (version, ec_level) = self.resolve()?;
+        let encoded = self.encode(version.get(), ec_level);
+
+        let template = VersionTemplate::get(version);
+        let (mut matrix, mut roles) = (template.function().clone(), template.roles().clone());
+        place_data(
+            &mut matrix,
+            &mut roles,
+            &encoded.codewords,
+            encoded.data_codewords.len(),
+        );
+
+        let penalties = (score_all_masks || self.mask.is_none()).then(|| {
+            score_masks(&matrix, template, |candidate, mask| {
+                draw_format_info(candidate, ec_level, mask)
+            })
+        });
+        let mask = match (self.mask, &penalties) {
+            (Some(mask), _) => mask,
+            (None, Some(scores)) => best_mask(scores),
+            (None, None) => unreachable!(),
+        };
+        matrix.xor_with(template.mask_pattern(mask));
+        draw_format_info(&mut matrix, ec_level, mask);
+
+        Ok(Parts {
+            symbol: QrSymbol {
+                version,
+                ec_level,
+                mask,
+                matrix,
+                roles,
+            },
+            encoded,
+            penalties,
+        })
+    }
+
+    /// 校验参数，并确定版本与（提升后的）纠错级别
+    pub(crate) fn resolve(&self) -> QrResult<(Version, QRErrorCorrectLevel)> {
+        let (min, max) = (*self.version_range.start(), *self.version_range.end());
+        if min > max {
+            return Err(QrError::InvalidVersionRange {
+                min: min.get(),
+                max: max.get(),
+            });
+        }
+        for seg in &self.segments {
+            if let Segment::Eci(assignment) = *seg {
+                if assignment > MAX_ECI {
+                    return Err(QrError::InvalidEci(assignment));
+                }
+            }
+        }
+
+        let version = Version::all()
+            .filter(|v| (min..=max).contains(v))
+            .find(|v| self.data_bits(v.get()) <= capacity_bits(v.get(), self.ec_level))
+            .ok_or(QrError::DataTooLong {
+                bits: self.data_bits(max.get()),
+                capacity: capacity_bits(max.get(), self.ec_level),
+            })?;
+
+        let mut ec_level = self.ec_level;
+        if self.boost_ecl {
+            let bits = self.data_bits(version.get());
+            for level in QRErrorCorrectLevel::ALL {
+                if level > self.ec_level && bits <= capacity_bits(version.get(), level) {
+                    ec_level = level;
+                }
+            }
+        }
+        Ok((version, ec_level))
+    }
+
+    /// 指定版本下数据段所需的位数
+    fn data_bits(&self, version: i32) -> usize {
+        self.segments.iter().map(|seg| seg.bit_len(version)).sum()
+    }
+
+    /// 生成数据码字、各块纠错码字及交织后的最终序列
+    fn encode(&self, version: i32, ec_level: QRErrorCorrectLevel) -> Encoded {
+        let rs_blocks = get_rs_blocks(version, ec_level);
+        let data_count: usize = rs_blocks.iter().map(|b| b.data_count as usize).sum();
+        let capacity = data_count * 8;
+
+        let mut buffer = BitBuffer::with_capacity(capacity);
+        for seg in &self.segments {
+            seg.write(&mut buffer, version);
+        }
+        let segment_bits = buffer.len_bits();
+
+        // 终止符（最多 4 位），随后补齐到字节边界
+        buffer.put(0, (capacity - segment_bits).min(4));
+        let mut data = buffer.into_bytes();
+
+        // 填充字节 0xEC / 0x11 交替
+        for pad in [0xEC, 0x11].into_iter().cycle() {
+            if data.len() >= data_count {
+                break;
+            }
+            data.push(pad);
+        }
+
+        let mut blocks = Vec::with_capacity(rs_blocks.len());
+        let mut generator = Vec::new();
+        let mut offset = 0;
+        for block in &rs_blocks {
+            let range = offset..offset + block.data_count as usize;
+            offset = range.end;
+            let mut ec = vec![0u8; (block.total_count - block.data_count) as usize];
+            if generator.len() != ec.len() + 1 {
+                generator = rs_generator(ec.len());
+            }
+            rs_remainder(&data[range.clone()], &generator, &mut ec);
+            blocks.push(EncodedBlock { data: range, ec });
+        }
+
+        let total_count: usize = rs_blocks.iter().map(|b| b.total_count as usize).sum();
+        let mut codewords = Vec::with_capacity(total_count);
+        let data_blocks: Vec<&[u8]> = blocks.iter().map(|b| &data[b.data.clone()]).collect();
+        interleave(&data_blocks, &mut codewords);
+        let ec_blocks: Vec<&[u8]> = blocks.iter().map(|b| b.ec.as_slice()).collect();
+        interleave(&ec_blocks, &mut codewords);
+
+        Encoded {
+            segment_bits,
+            data_codewords: data,
+            blocks,
+            codewords,
+        }
+    }
+}
+
+/// 重新计算指定版本和纠错级别下每个模块的角色（与编码内容无关）
+pub(crate) fn symbol_roles(version: Version, ec_level: QRErrorCorrectLevel) -> RoleMap {
+    let rs_blocks = get_rs_blocks(version.get(), ec_level);
+    let data_count = rs_blocks.iter().map(|b| b.data_count as usize).sum();
+    let total_count = rs_blocks.iter().map(|b| b.total_count as usize).sum();
+    let template = VersionTemplate::get(version);
+    let (mut matrix, mut roles) = (template.function().clone(), template.roles().clone());
+    place_data(&mut matrix, &mut roles, &vec![0u8; total_count], data_count);
+    roles
+}
+
+/// 指定版本和纠错级别下可用的数据位数
+pub(crate) fn capacity_bits(version: i32, ec_level: QRErrorCorrectLevel) -> usize {
+    get_rs_blocks(version, ec_level)
+        .iter()
+        .map(|b| b.data_count as usize * 8)
+        .sum()
+}
+
+/// 按列依次取各块的第 i 个码字
+fn interleave(blocks: &[&[u8]], out: &mut Vec<u8>) {
+    let max = blocks.iter().map(|b| b.len()).max().unwrap_or(0);
+    for i in 0..max {
+        for block in blocks {
+            if let Some(&cw) = block.get(i) {
+                out.push(cw);
+            }
+        }
+    }
+}
+
+/// 写入格式信息
+fn draw_format_info(matrix: &mut QrMatrix, ec_level: QRErrorCorrectLevel, mask: Mask) {
+    let count = matrix.width() as i32;
+    let bits = format_info_bits(ec_level, mask);
+    for i in 0..15 {
+        let dark = (bits >> i) & 1 == 1;
+        let ((r1, c1), (r2, c2)) = format_info_positions(count, i);
+        matrix.set(c1 as usize, r1 as usize, dark);
+        matrix.set(c2 as usize, r2 as usize, dark);
+    }
+}
+
+/// 数据区模块的放置顺序：从右下角起每两列一组之字形扫描，跳过功能图案。
+/// 第 `i` 个位置存放交织后码字序列的第 `i` 位。
+pub(crate) fn data_positions(roles: &RoleMap) -> Vec<(i32, i32)> {
+    let count = roles.size() as i32;
+    let mut positions = Vec::with_capacity(roles.size() * roles.size());
+    let mut upward = true;
+
+    let mut col = count - 1;
+    while col > 0 {
+        if col == 6 {
+            col -= 1;
+        }
+        for step in 0..count {
+            let row = if upward { count - 1 - step } else { step };
+            for c in [col, col - 1] {
+                if !roles.get(row, c).is_some_and(ModuleRole::is_function) {
+                    positions.push((row, c));
+                }
+            }
+        }
+        upward = !upward;
+        col -= 2;
+    }
+    positions
+}
+
+/// 按之字形顺序把码字放入非功能模块（未掩码），并记录数据区角色
+fn place_data(matrix: &mut QrMatrix, roles: &mut RoleMap, codewords: &[u8], data_count: usize) {
+    for (bit_index, (row, col)) in data_positions(roles).into_iter().enumerate() {
+        let byte_index = bit_index / 8;
+        let dark = codewords
+            .get(byte_index)
+            .is_some_and(|&cw| (cw >> (7 - bit_index % 8)) & 1 == 1);
+        let role = ModuleRole::for_codeword(byte_index, data_count, codewords.len());
+        put(matrix, roles, row, col, dark, role);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qr_mask::penalty_score;
+    use crate::qr_template::version_info_bits;
+
+    fn version(n: i32) -> Version {
+        Version::new(n).unwrap()
+    }
+
+    fn mask(n: u8) -> Mask {
+        Mask::new(n).unwrap()
+    }
+
+    #[test]
+    fn test_format_info_bits() {
+        // ISO/IEC 18004 附录 C：M 级 + 掩码 5 → 100000011001110
+        assert_eq!(
+            format_info_bits(QRErrorCorrectLevel::M, mask(5)),
+            0b100_0000_1100_1110
+        );
+        // L 级 + 掩码 4 → 110011000101111
+        assert_eq!(
+            format_info_bits(QRErrorCorrectLevel::L, mask(4)),
+            0b110_0110_0010_1111
+        );
+    }
+
+    #[test]
+    fn test_version_info_bits() {
+        // 版本 7 → 000111110010010100
+        assert_eq!(version_info_bits(version(7)), 0b00_0111_1100_1001_0100);
+    }
+
+    #[test]
+    fn test_smallest_version_selected() {
+        let symbol = QrBuilder::new("Hello World")
+            .ec_level(QRErrorCorrectLevel::M)
+            .build()
+            .unwrap();
+        assert_eq!(symbol.version().get(), 1);
+        assert_eq!(symbol.module_count(), 21);
+
+        // 版本 1-H 最多 7 字节
+        let symbol = QrBuilder::new("12345678").build().unwrap();
+        assert_eq!(symbol.version().get(), 2);
+    }
+
+    #[test]
+    fn test_version_range_and_errors() {
+        let symbol = QrBuilder::new("a")
+            .version_range(version(5)..=version(6))
+            .build()
+            .unwrap();
+        assert_eq!(symbol.version().get(), 5);
+
+        assert_eq!(
+            QrBuilder::new("a")
+                .version_range(version(3)..=version(2))
+                .build(),
+            Err(QrError::InvalidVersionRange { min: 3, max: 2 })
+        );
+        assert_eq!(
+            QrBuilder::new("a").eci(1_000_000).build(),
+            Err(QrError::InvalidEci(1_000_000))
+        );
+        assert!(matches!(
+            QrBuilder::new(&"x".repeat(20))
+                .version_range(version(1)..=version(1))
+                .build(),
+            Err(QrError::DataTooLong { capacity: 72, .. })
+        ));
+    }
+
+    #[test]
+    fn test_boost_ecl() {
+        // "Hi" 在版本 1 下 H 级也放得下
+        let symbol = QrBuilder::new("Hi")
+            .ec_level(QRErrorCorrectLevel::L)
+            .boost_ecl(true)
+            .build()
+            .unwrap();
+        assert_eq!(symbol.version().get(), 1);
+        assert_eq!(symbol.ec_level(), QRErrorCorrectLevel::H);
+
+        let symbol = QrBuilder::new("Hi")
+            .ec_level(QRErrorCorrectLevel::L)
+            .build()
+            .unwrap();
+        assert_eq!(symbol.ec_level(), QRErrorCorrectLevel::L);
+    }
+
+    #[test]
+    fn test_fixed_mask_and_format_info() {
+        let symbol = QrBuilder::new("test")
+            .ec_level(QRErrorCorrectLevel::Q)
+            .mask(mask(3))
+            .build()
+            .unwrap();
+        assert_eq!(symbol.mask().get(), 3);
+
+        let bits = format_info_bits(QRErrorCorrectLevel::Q, mask(3));
+        let count = symbol.module_count();
+        for i in 0..15 {
+            let ((r1, c1), (r2, c2)) = format_info_positions(count, i);
+            let expected = (bits >> i) & 1 == 1;
+            assert_eq!(symbol.is_dark(r1, c1), expected);
+            assert_eq!(symbol.is_dark(r2, c2), expected);
+        }
+        assert!(symbol.is_dark(count - 8, 8));
+    }
+
+    #[test]
+    fn test_auto_mask_has_lowest_penalty() {
+        let auto = QrBuilder::new("https://example.com").build().unwrap();
+        let best = penalty_score(auto.matrix());
+        for mask in Mask::ALL {
+            let fixed = QrBuilder::new("https://example.com")
+                .mask(mask)
+                .build()
+                .unwrap();
+            assert!(penalty_score(fixed.matrix()) >= best);
+        }
+    }
+
+    #[test]
+    fn test_version_info_placed() {
+        let symbol = QrBuilder::new(&"x".repeat(100)).build().unwrap();
+        assert!(symbol.version() >= version(7));
+        assert_eq!(symbol.roles().count(ModuleRole::VersionInfo), 36);
+
+        let bits = version_info_bits(symbol.version());
+        let count = symbol.module_count();
+        for i in 0..18 {
+            let expected = (bits >> i) & 1 == 1;
+            assert_eq!(symbol.is_dark(i / 3, i % 3 + count - 11), expected);
+            assert_eq!(symbol.is_dark(i % 3 + count - 11, i / 3), expected);
+        }
+    }
+
+    #[test]
+    fn test_codewords_with_eci() {
+        // ECI 26: 0111 00011010，随后是字节模式头
+        let builder = QrBuilder::new("A").eci(26).ec_level(QRErrorCorrectLevel::L);
+        let encoded = builder.encode(1, QRErrorCorrectLevel::L);
+        assert_eq!(encoded.segment_bits, 12 + 4 + 8 + 8);
+        assert_eq!(encoded.data_codewords.len(), 19);
+        let codewords = encoded.codewords;
+        assert_eq!(codewords.len(), 26);
+        assert_eq!(&codewords[..4], &[0x71, 0xA4, 0x01, 0x41]);
+        // 终止符与填充
+        assert_eq!(&codewords[4..7], &[0x00, 0xEC, 0x11]);
+    }
+
+    #[test]
+    fn test_binary_payload() {
+        // 非 UTF-8 字节按原样写入
+        let builder = QrBuilder::from_bytes(&[0xFF, 0x00, 0xC3]).ec_level(QRErrorCorrectLevel::L);
+        assert_eq!(builder.segments(), &[Segment::Byte(vec![0xFF, 0x00, 0xC3])]);
+        let encoded = builder.encode(1, QRErrorCorrectLevel::L);
+        assert_eq!(&encoded.codewords[..5], &[0x40, 0x3F, 0xF0, 0x0C, 0x30]);
+
+        let text = QrBuilder::new("Hi").mask(mask(3)).build().unwrap();
+        let bytes = QrBuilder::from_bytes(b"Hi").mask(mask(3)).build().unwrap();
+        assert_eq!(text, bytes);
+    }
+
+    #[test]
+    fn test_module_roles_cover_symbol() {
+        let symbol = QrBuilder::new("roles").build().unwrap();
+        let count = symbol.module_count() as usize;
+        assert_eq!(symbol.roles().count(ModuleRole::Finder), 3 * 49);
+        assert_eq!(symbol.roles().count(ModuleRole::FormatInfo), 30);
+        assert_eq!(symbol.roles().count(ModuleRole::DarkModule), 1);
+        // 版本 1：26 个码字 × 8 位 + 0 个剩余位
+        let data_region = symbol.roles().count(ModuleRole::Data)
+            + symbol.roles().count(ModuleRole::ErrorCorrection)
+            + symbol.roles().count(ModuleRole::Remainder);
+        assert_eq!(data_region, 26 * 8);
+        let function: usize = ModuleRole::ALL
+            .iter()
+            .filter(|r| r.is_function())
+            .map(|&r| symbol.roles().count(r))
+            .sum();
+        assert_eq!(function + data_region, count * count);
+    }
+}
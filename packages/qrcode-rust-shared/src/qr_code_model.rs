@@ -1,5 +1,11 @@
 //! QR Code Model - Shared constants and types
 
+use core::cmp::Ordering;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::qr_error::{QrError, QrResult};
+
 /// QR Code 模式
 pub struct QRMode;
 
@@ -8,11 +14,17 @@ impl QRMode {
     pub const MODE_ALPHA_NUM: i32 = 2;
     pub const MODE_8BIT_BYTE: i32 = 4;
     pub const MODE_KANJI: i32 = 8;
+    pub const MODE_ECI: i32 = 7;
 }
 
 /// 错误纠正级别
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub enum QRErrorCorrectLevel {
+///
+/// 判别值是格式信息中的 2 位编码（L=01, M=00, Q=11, H=10），不代表强弱顺序；
+/// 比较、查表请使用 [`Ord`] 与 [`ordinal`](Self::ordinal)，编码格式信息请使用
+/// [`format_bits`](Self::format_bits)，不要直接 `as` 转换。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EcLevel {
     L = 1, // 低 (~7%)
     M = 0, // 中 (~15%)
     Q = 3, // 高 (~25%)
@@ -20,6 +32,67 @@ pub enum QRErrorCorrectLevel {
     H = 2, // 最高 (~30%)
 }
 
+/// 旧名称，等同于 [`EcLevel`]
+pub type QRErrorCorrectLevel = EcLevel;
+
+impl EcLevel {
+    /// 全部纠错级别，从低到高
+    pub const ALL: [EcLevel; 4] = [EcLevel::L, EcLevel::M, EcLevel::Q, EcLevel::H];
+
+    /// 从低到高的序号：L=0, M=1, Q=2, H=3（纠错表的列号）
+    pub fn ordinal(self) -> usize {
+        match self {
+            EcLevel::L => 0,
+            EcLevel::M => 1,
+            EcLevel::Q => 2,
+            EcLevel::H => 3,
+        }
+    }
+
+    /// 格式信息中的 2 位编码
+    pub fn format_bits(self) -> u8 {
+        self as u8
+    }
+}
+
+impl PartialOrd for EcLevel {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EcLevel {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.ordinal().cmp(&other.ordinal())
+    }
+}
+
+impl fmt::Display for EcLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            EcLevel::L => "L",
+            EcLevel::M => "M",
+            EcLevel::Q => "Q",
+            EcLevel::H => "H",
+        })
+    }
+}
+
+impl FromStr for EcLevel {
+    type Err = QrError;
+
+    /// 解析 "L" / "M" / "Q" / "H"（不区分大小写）
+    fn from_str(s: &str) -> QrResult<Self> {
+        match s.trim() {
+            "L" | "l" => Ok(EcLevel::L),
+            "M" | "m" => Ok(EcLevel::M),
+            "Q" | "q" => Ok(EcLevel::Q),
+            "H" | "h" => Ok(EcLevel::H),
+            other => Err(QrError::InvalidEcLevel(other.to_string())),
+        }
+    }
+}
+
 /// 位置调整图案位置表 (完整 40 版本)
 pub const PATTERN_POSITION_TABLE: &[[i32; 7]] = &[
     [0, 0, 0, 0, 0, 0, 0],
@@ -110,13 +183,17 @@ pub const QR_CODE_LIMIT_LENGTH: &[[i32; 4]] = &[
 
 /// 获取类型号 (完整版本，支持 40 个版本)
 pub fn get_type_number(text: &str, correct_level: QRErrorCorrectLevel) -> i32 {
-    let length = text.len();
-    let level_map = [1, 0, 3, 2];
-    let level_index = level_map[correct_level as usize];
+    get_type_number_bytes(text.as_bytes(), correct_level)
+}
+
+/// 获取类型号（原始字节数据）
+pub fn get_type_number_bytes(data: &[u8], correct_level: QRErrorCorrectLevel) -> i32 {
+    let length = data.len();
+    let level_index = correct_level.ordinal();
     let data_length = length + 2; // 考虑模式指示符和长度字段
 
     for (i, limits) in QR_CODE_LIMIT_LENGTH.iter().enumerate() {
-        let limit = limits[level_index as usize];
+        let limit = limits[level_index];
         if data_length <= limit as usize {
             return (i + 1) as i32;
         }
@@ -125,17 +202,15 @@ pub fn get_type_number(text: &str, correct_level: QRErrorCorrectLevel) -> i32 {
 }
 
 /// 快速版本：获取最小版本号 (支持 1-10 版本，用于 qrcode-fast)
+///
+/// `QR_CODE_LIMIT_LENGTH[i]` 是版本 `i + 1` 的容量。早期实现把第 `i` 行当成版本 `i`，
+/// 容量介于相邻两个版本之间的数据会选出放不下的小版本。
 pub fn get_min_version(text_len: usize, level: QRErrorCorrectLevel) -> i32 {
-    let level_idx = match level {
-        QRErrorCorrectLevel::L => 0,
-        QRErrorCorrectLevel::M => 1,
-        QRErrorCorrectLevel::Q => 2,
-        QRErrorCorrectLevel::H => 3,
-    };
-
-    for (version, limits) in QR_CODE_LIMIT_LENGTH.iter().enumerate().skip(1).take(10) {
+    let level_idx = level.ordinal();
+
+    for (i, limits) in QR_CODE_LIMIT_LENGTH.iter().take(10).enumerate() {
         if limits[level_idx] >= text_len as i32 {
-            return version as i32;
+            return i as i32 + 1;
         }
     }
     10 // 最大支持 Version 10 (fast mode)
@@ -159,11 +234,35 @@ mod tests {
         assert_eq!(QRErrorCorrectLevel::H as i32, 2);
     }
 
+    #[test]
+    fn test_ec_level_order_and_bits() {
+        assert!(EcLevel::L < EcLevel::M && EcLevel::M < EcLevel::Q && EcLevel::Q < EcLevel::H);
+        assert_eq!(EcLevel::ALL.iter().max(), Some(&EcLevel::H));
+        let bits: Vec<u8> = EcLevel::ALL.iter().map(|l| l.format_bits()).collect();
+        assert_eq!(bits, vec![0b01, 0b00, 0b11, 0b10]);
+    }
+
+    #[test]
+    fn test_ec_level_parse_display() {
+        for level in EcLevel::ALL {
+            assert_eq!(level.to_string().parse::<EcLevel>(), Ok(level));
+        }
+        assert_eq!(" q ".parse::<EcLevel>(), Ok(EcLevel::Q));
+        assert_eq!(
+            "X".parse::<EcLevel>(),
+            Err(QrError::InvalidEcLevel("X".to_string()))
+        );
+    }
+
     #[test]
     fn test_get_type_number_basic() {
         // "Hello" (5 chars) with H level should fit in version 1
         let version = get_type_number("Hello", QRErrorCorrectLevel::H);
         assert!((1..=40).contains(&version));
+        assert_eq!(
+            get_type_number_bytes(b"Hello", QRErrorCorrectLevel::H),
+            version
+        );
     }
 
     #[test]
@@ -171,6 +270,30 @@ mod tests {
         // Short text should fit in version 1-2
         let v = get_min_version(5, QRErrorCorrectLevel::H);
         assert!((1..=10).contains(&v));
+
+        // 1-L 最多 17 字节，18 字节需要版本 2
+        assert_eq!(get_min_version(17, QRErrorCorrectLevel::L), 1);
+        assert_eq!(get_min_version(18, QRErrorCorrectLevel::L), 2);
+    }
+
+    #[test]
+    fn test_get_min_version_capacity_boundaries() {
+        // 恰好等于版本 v 的容量时选 v，多 1 字节时选 v + 1
+        for level in EcLevel::ALL {
+            for version in 1..10 {
+                let capacity = QR_CODE_LIMIT_LENGTH[version - 1][level.ordinal()] as usize;
+                assert_eq!(
+                    get_min_version(capacity, level),
+                    version as i32,
+                    "{level:?}"
+                );
+                assert_eq!(
+                    get_min_version(capacity + 1, level),
+                    version as i32 + 1,
+                    "{level:?}"
+                );
+            }
+        }
     }
 
     #[test]
@@ -0,0 +1,64 @@
+//! QR Code Color - 颜色字符串解析
+//!
+//! 渲染选项中的颜色使用 CSS 十六进制写法：`#rgb`、`#rgba`、`#rrggbb`、`#rrggbbaa`，
+//! 另外接受 `transparent`。位图输出（PNG、RGBA 像素）统一先解析为 RGBA8888。
+
+use crate::qr_error::{QrError, QrResult};
+
+/// 解析颜色字符串为 `[r, g, b, a]`
+pub fn parse_color(color: &str) -> QrResult<[u8; 4]> {
+    let invalid = || QrError::InvalidColor(color.to_string());
+    let trimmed = color.trim();
+    if trimmed.eq_ignore_ascii_case("transparent") {
+        return Ok([0, 0, 0, 0]);
+    }
+    let hex = trimmed.strip_prefix('#').ok_or_else(invalid)?;
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).unwrap();
+    let pair = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+    match hex.len() {
+        3 | 4 => {
+            // 简写形式每位重复一次：#f80 = #ff8800
+            let mut rgba = [0xff; 4];
+            for (i, channel) in rgba.iter_mut().enumerate().take(hex.len()) {
+                *channel = digit(i) * 0x11;
+            }
+            Ok(rgba)
+        }
+        6 | 8 => {
+            let mut rgba = [0xff; 4];
+            for (i, channel) in rgba.iter_mut().enumerate().take(hex.len() / 2) {
+                *channel = pair(i * 2);
+            }
+            Ok(rgba)
+        }
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("#000000"), Ok([0, 0, 0, 255]));
+        assert_eq!(parse_color("#FFFFFF"), Ok([255, 255, 255, 255]));
+        assert_eq!(parse_color("#f80"), Ok([0xff, 0x88, 0x00, 255]));
+        assert_eq!(parse_color("#f808"), Ok([0xff, 0x88, 0x00, 0x88]));
+        assert_eq!(parse_color(" #11223380 "), Ok([0x11, 0x22, 0x33, 0x80]));
+        assert_eq!(parse_color("Transparent"), Ok([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn test_parse_color_invalid() {
+        for color in ["", "000000", "#12345", "#gg0000", "red", "#+1234"] {
+            assert_eq!(
+                parse_color(color),
+                Err(QrError::InvalidColor(color.to_string()))
+            );
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! QR Code Conformance - 各实现共用的一致性检查
+//!
+//! 仅在 `test-support` 特性（以及本 crate 自身的测试）下编译。qrcode-rust、qrcode-fast
+//! 与 qrcode-wasm 的测试调用这里的函数，同一套检查只写一次；检查失败时直接 panic。
+
+use crate::qr_builder::QrBuilder;
+use crate::qr_code_model::QRErrorCorrectLevel;
+use crate::qr_encoder::QrEncoder;
+use crate::qr_error::QrError;
+use crate::qr_mask::Mask;
+use crate::qr_matrix::QrMatrix;
+use crate::qr_module_role::{ModuleRole, RoleMap};
+
+/// 检查 [`QrEncoder`] 的约定，`encoder` 须是尚未编码、纠错级别为 M 的新实例
+pub fn check_encoder<E: QrEncoder>(mut encoder: E) {
+    assert_eq!(encoder.version(), None);
+    assert!(!encoder.is_dark(0, 0));
+
+    // 由短到长编码，每次都重新选择版本
+    let inputs: [&[u8]; 3] = [b"Hi", &[b'x'; 40], &[0xFF, 0x00, 0xC3]];
+    let symbols: Vec<_> = inputs
+        .iter()
+        .map(|data| encoder.encode(data).unwrap())
+        .collect();
+    let versions: Vec<i32> = symbols.iter().map(|s| s.version().get()).collect();
+    assert_eq!(versions, [1, 3, 1]);
+    for (symbol, data) in symbols.iter().zip(inputs) {
+        // 与共享构建器在相同版本、掩码下的结果一致
+        let expected = QrBuilder::from_bytes(data)
+            .ec_level(QRErrorCorrectLevel::M)
+            .version_range(symbol.version()..=symbol.version())
+            .mask(Mask::default())
+            .build()
+            .unwrap();
+        assert_eq!(*symbol, expected);
+    }
+
+    assert_eq!(encoder.symbol().as_ref(), symbols.last());
+    assert_eq!(encoder.mask(), Some(Mask::default()));
+    assert!(!encoder.is_dark(-1, 0));
+    assert!(!encoder.is_dark(0, encoder.module_count()));
+
+    let err = encoder.encode(&[0; 3000]).unwrap_err();
+    assert!(matches!(err, QrError::DataTooLong { .. }));
+}
+
+/// 检查版本 1–6 符号（没有版本信息，如 "Hello World"）的角色表
+pub fn check_roles(roles: &RoleMap) {
+    let count = roles.size() as i32;
+
+    assert_eq!(roles.get(0, 0), Some(ModuleRole::Finder));
+    assert_eq!(roles.get(7, 7), Some(ModuleRole::Separator));
+    assert_eq!(roles.get(6, 8), Some(ModuleRole::Timing));
+    assert_eq!(roles.get(8, 0), Some(ModuleRole::FormatInfo));
+    assert_eq!(roles.get(count - 8, 8), Some(ModuleRole::DarkModule));
+    assert_eq!(roles.get(count, 0), None);
+
+    assert_eq!(roles.iter_role(ModuleRole::Finder).count(), 3 * 49);
+    assert_eq!(roles.count(ModuleRole::FormatInfo), 30);
+    assert_eq!(roles.count(ModuleRole::DarkModule), 1);
+    assert_eq!(roles.count(ModuleRole::VersionInfo), 0);
+
+    // 数据区每个码字恰好占 8 个模块
+    let data = roles.count(ModuleRole::Data);
+    let ec = roles.count(ModuleRole::ErrorCorrection);
+    assert!(data > 0 && data.is_multiple_of(8));
+    assert!(ec > 0 && ec.is_multiple_of(8));
+    let function: usize = ModuleRole::ALL
+        .iter()
+        .filter(|r| r.is_function())
+        .map(|&r| roles.count(r))
+        .sum();
+    assert_eq!(
+        function + data + ec + roles.count(ModuleRole::Remainder),
+        (count * count) as usize
+    );
+}
+
+/// 检查实现导出的矩阵与逐个读取的 `is_dark` 一致
+pub fn check_matrix<E: QrEncoder>(encoder: &E, matrix: &QrMatrix) {
+    let count = encoder.module_count();
+    assert_eq!(matrix.width(), count as usize);
+    assert_eq!(matrix.height(), count as usize);
+    for row in 0..count {
+        for col in 0..count {
+            assert_eq!(
+                matrix.get(col as usize, row as usize),
+                encoder.is_dark(row, col)
+            );
+        }
+    }
+}
+
+/// 解析只含 `M x y`、`h`、`v`、`z` 的路径数据，按 even-odd 规则判断
+/// `count × count` 网格中每个模块中心是否被填充
+pub fn rasterize_path(d: &str, count: usize) -> QrMatrix {
+    #[derive(Debug)]
+    enum Token {
+        Command(char),
+        Number(i32),
+    }
+    let mut tokens = Vec::new();
+    let mut number = String::new();
+    for c in d.chars() {
+        if c.is_ascii_digit() || (c == '-' && number.is_empty()) {
+            number.push(c);
+            continue;
+        }
+        if !number.is_empty() {
+            tokens.push(Token::Number(number.parse().unwrap()));
+            number.clear();
+        }
+        if c != ' ' {
+            tokens.push(Token::Command(c));
+        }
+    }
+
+    // 竖直边 (x, y0, y1)
+    let mut edges = Vec::new();
+    let (mut x, mut y, mut sx, mut sy) = (0, 0, 0, 0);
+    let mut iter = tokens.into_iter();
+    while let Some(token) = iter.next() {
+        let mut arg = || match iter.next() {
+            Some(Token::Number(n)) => n,
+            other => panic!("expected number, got {other:?}"),
+        };
+        match token {
+            Token::Command('M') => {
+                (x, y) = (arg(), arg());
+                (sx, sy) = (x, y);
+            }
+            Token::Command('h') => x += arg(),
+            Token::Command('v') => {
+                let dy = arg();
+                edges.push((x, y, y + dy));
+                y += dy;
+            }
+            Token::Command('z') => {
+                assert!(x == sx || y == sy, "closing segment must be axis-aligned");
+                if y != sy {
+                    edges.push((x, y, sy));
+                }
+                (x, y) = (sx, sy);
+            }
+            other => panic!("unexpected token {other:?}"),
+        }
+    }
+
+    let mut filled = QrMatrix::square(count);
+    for cy in 0..count {
+        for cx in 0..count {
+            let (px, py) = (cx as f64 + 0.5, cy as f64 + 0.5);
+            let crossings = edges
+                .iter()
+                .filter(|&&(ex, y0, y1)| {
+                    ex as f64 > px && (y0.min(y1) as f64) < py && py < y0.max(y1) as f64
+                })
+                .count();
+            filled.set(cx, cy, crossings % 2 == 1);
+        }
+    }
+    filled
+}
@@ -0,0 +1,254 @@
+//! QR Code Damage - 遮挡容错分析
+//!
+//! 在贴纸、Logo 或打孔覆盖二维码之前，估算它是否仍能被识读：
+//! 按 `map_data` 的放置顺序把每个被遮挡的模块映射到码字和 RS 块，
+//! 统计每块受损的码字数，并与该块的纠错能力比较。
+//!
+//! 被遮挡模块的取值未知，按错误（而非已知位置的擦除）计算，结果偏保守。
+//! 功能图案（定位、定时、格式信息等）被遮挡的情况只计数，不计入判定。
+
+use crate::qr_builder::data_positions;
+use crate::qr_code_model::QRErrorCorrectLevel;
+use crate::qr_matrix::QrMatrix;
+use crate::qr_module_role::ModuleRole;
+use crate::qr_rs_block::get_rs_blocks;
+use crate::qr_symbol::QrSymbol;
+
+/// 以模块为单位的矩形区域，允许超出符号边界（超出部分忽略）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModuleRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl ModuleRect {
+    pub fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        ModuleRect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// 是否覆盖模块 `(row, col)`
+    pub fn contains(&self, row: i32, col: i32) -> bool {
+        (self.x..self.x + self.width).contains(&col)
+            && (self.y..self.y + self.height).contains(&row)
+    }
+}
+
+/// 单个 RS 块的受损情况
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockDamage {
+    pub data_count: usize,
+    pub ec_count: usize,
+    /// 至少有一位被遮挡的码字数
+    pub damaged_codewords: usize,
+    /// 可纠正的码字数
+    pub capacity: usize,
+}
+
+impl BlockDamage {
+    /// 剩余纠错余量，负数表示超出纠错能力
+    pub fn margin(&self) -> i32 {
+        self.capacity as i32 - self.damaged_codewords as i32
+    }
+
+    pub fn passes(&self) -> bool {
+        self.damaged_codewords <= self.capacity
+    }
+}
+
+/// 遮挡分析结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DamageReport {
+    /// 按 RS 块顺序排列
+    pub blocks: Vec<BlockDamage>,
+    /// 被遮挡的数据区模块数（含剩余位）
+    pub data_modules: usize,
+    /// 被遮挡的功能图案模块数
+    pub function_modules: usize,
+}
+
+impl DamageReport {
+    /// 所有块都在纠错能力之内
+    pub fn passes(&self) -> bool {
+        self.blocks.iter().all(BlockDamage::passes)
+    }
+
+    /// 所有块中最小的余量
+    pub fn min_margin(&self) -> i32 {
+        self.blocks
+            .iter()
+            .map(BlockDamage::margin)
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+impl QrSymbol {
+    /// 分析被矩形区域遮挡后的容错情况
+    pub fn analyze_damage(&self, rects: &[ModuleRect]) -> DamageReport {
+        self.analyze_damage_with(|row, col| rects.iter().any(|rect| rect.contains(row, col)))
+    }
+
+    /// 分析被位图遮挡后的容错情况，`obscured` 中深色模块表示被遮挡
+    pub fn analyze_damage_mask(&self, obscured: &QrMatrix) -> DamageReport {
+        self.analyze_damage_with(|row, col| {
+            let (x, y) = (col as usize, row as usize);
+            x < obscured.width() && y < obscured.height() && obscured.get(x, y)
+        })
+    }
+
+    fn analyze_damage_with(&self, is_obscured: impl Fn(i32, i32) -> bool) -> DamageReport {
+        let rs_blocks = get_rs_blocks(self.version.get(), self.ec_level);
+        let misdecode = misdecode_codewords(self.version.get(), self.ec_level);
+        let mut blocks: Vec<BlockDamage> = rs_blocks
+            .iter()
+            .map(|b| {
+                let ec_count = (b.total_count - b.data_count) as usize;
+                BlockDamage {
+                    data_count: b.data_count as usize,
+                    ec_count,
+                    damaged_codewords: 0,
+                    capacity: ec_count.saturating_sub(misdecode) / 2,
+                }
+            })
+            .collect();
+
+        // 交织序列中第 i 个码字所属的块：先按列取数据码字，再按列取纠错码字
+        let max_data = blocks.iter().map(|b| b.data_count).max().unwrap_or(0);
+        let max_ec = blocks.iter().map(|b| b.ec_count).max().unwrap_or(0);
+        let mut owner = Vec::new();
+        for i in 0..max_data {
+            owner.extend((0..blocks.len()).filter(|&b| i < blocks[b].data_count));
+        }
+        for i in 0..max_ec {
+            owner.extend((0..blocks.len()).filter(|&b| i < blocks[b].ec_count));
+        }
+
+        let mut damaged = vec![false; owner.len()];
+        let mut data_modules = 0;
+        for (bit_index, (row, col)) in data_positions(&self.roles).into_iter().enumerate() {
+            if !is_obscured(row, col) {
+                continue;
+            }
+            data_modules += 1;
+            if let Some(flag) = damaged.get_mut(bit_index / 8) {
+                *flag = true;
+            }
+        }
+        for (&block, _) in owner.iter().zip(&damaged).filter(|(_, &hit)| hit) {
+            blocks[block].damaged_codewords += 1;
+        }
+
+        let count = self.module_count();
+        let function_modules = (0..count)
+            .flat_map(|row| (0..count).map(move |col| (row, col)))
+            .filter(|&(row, col)| {
+                self.roles
+                    .get(row, col)
+                    .is_some_and(ModuleRole::is_function)
+                    && is_obscured(row, col)
+            })
+            .count();
+
+        DamageReport {
+            blocks,
+            data_modules,
+            function_modules,
+        }
+    }
+}
+
+/// 为防止误译保留、不用于纠错的码字数（ISO/IEC 18004 表 9 中的 p）
+fn misdecode_codewords(version: i32, ec_level: QRErrorCorrectLevel) -> usize {
+    match (version, ec_level) {
+        (1, QRErrorCorrectLevel::L) => 3,
+        (1, QRErrorCorrectLevel::M) | (2, QRErrorCorrectLevel::L) => 2,
+        (1, _) | (3, QRErrorCorrectLevel::L) => 1,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qr_builder::QrBuilder;
+
+    #[test]
+    fn test_no_damage() {
+        let symbol = QrBuilder::new("Hi").build().unwrap();
+        let report = symbol.analyze_damage(&[]);
+        // 1-H：单块 9 个数据码字 + 17 个纠错码字，p = 1
+        assert_eq!(report.blocks.len(), 1);
+        assert_eq!(report.blocks[0].ec_count, 17);
+        assert_eq!(report.blocks[0].capacity, 8);
+        assert_eq!(report.min_margin(), 8);
+        assert!(report.passes());
+        assert_eq!((report.data_modules, report.function_modules), (0, 0));
+    }
+
+    #[test]
+    fn test_first_codeword_region() {
+        // 第一个码字占据右下角 2 列 × 4 行
+        let symbol = QrBuilder::new("Hi").build().unwrap();
+        let count = symbol.module_count();
+        let report = symbol.analyze_damage(&[ModuleRect::new(count - 2, count - 4, 2, 4)]);
+        assert_eq!(report.data_modules, 8);
+        assert_eq!(report.blocks[0].damaged_codewords, 1);
+        assert_eq!(report.blocks[0].margin(), 7);
+
+        // 超出边界的部分被忽略
+        let overhang = symbol.analyze_damage(&[ModuleRect::new(count - 2, count - 4, 10, 10)]);
+        assert_eq!(overhang, report);
+    }
+
+    #[test]
+    fn test_mask_matches_rects() {
+        let symbol = QrBuilder::new(&"x".repeat(80))
+            .ec_level(QRErrorCorrectLevel::Q)
+            .build()
+            .unwrap();
+        let count = symbol.module_count();
+        let rect = ModuleRect::new(count / 2 - 3, count / 2 - 3, 7, 7);
+
+        let mut mask = QrMatrix::square(count as usize);
+        for row in 0..count {
+            for col in 0..count {
+                if rect.contains(row, col) {
+                    mask.set(col as usize, row as usize, true);
+                }
+            }
+        }
+        let report = symbol.analyze_damage_mask(&mask);
+        assert_eq!(report, symbol.analyze_damage(&[rect]));
+        assert!(report.blocks.len() > 1);
+        assert_eq!(report.data_modules + report.function_modules, 49);
+        // 中心区域分散到多个块中
+        assert!(
+            report
+                .blocks
+                .iter()
+                .filter(|b| b.damaged_codewords > 0)
+                .count()
+                > 1
+        );
+    }
+
+    #[test]
+    fn test_fails_beyond_capacity() {
+        let symbol = QrBuilder::new("Hello World")
+            .ec_level(QRErrorCorrectLevel::L)
+            .build()
+            .unwrap();
+        let count = symbol.module_count();
+        let report = symbol.analyze_damage(&[ModuleRect::new(8, 8, count - 8, count - 8)]);
+        assert!(!report.passes());
+        assert!(report.min_margin() < 0);
+        assert!(report.function_modules > 0);
+    }
+}
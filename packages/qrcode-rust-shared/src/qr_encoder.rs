@@ -0,0 +1,173 @@
+//! QR Code Encoder - 各实现共用的编码接口
+//!
+//! qrcode-rust、qrcode-fast 与 wasm 核心都实现了 [`QrEncoder`]，
+//! 应用代码和对比工具可以用泛型在不同实现之间切换：
+//!
+//! ```
+//! use qrcode_rust_shared::{render_svg, QrEncoder, QrResult};
+//!
+//! fn to_svg(encoder: &mut impl QrEncoder, data: &[u8]) -> QrResult<String> {
+//!     let symbol = encoder.encode(data)?;
+//!     Ok(render_svg(symbol.matrix(), 256, "#000000", "#ffffff"))
+//! }
+//! ```
+//!
+//! 各实现原有的方法保持不变；通过本接口访问时行为统一：
+//! 越界的 `is_dark` 返回 `false`，数据放不下时返回 [`QrError::DataTooLong`]。
+
+use crate::qr_builder::capacity_bits;
+use crate::qr_code_model::{EcLevel, QRMode};
+use crate::qr_error::{QrError, QrResult};
+use crate::qr_mask::Mask;
+use crate::qr_matrix::QrMatrix;
+use crate::qr_symbol::QrSymbol;
+use crate::qr_util::get_length_in_bits;
+use crate::qr_version::Version;
+
+/// 二维码编码实现的公共接口
+pub trait QrEncoder {
+    /// 以字节模式编码 `data`（替换之前的内容）并返回编码结果
+    fn encode(&mut self, data: &[u8]) -> QrResult<QrSymbol>;
+
+    /// 每边模块数，编码前为 0
+    fn module_count(&self) -> i32;
+
+    /// 读取 (row, col) 处的模块，越界或编码前返回 `false`
+    fn is_dark(&self, row: i32, col: i32) -> bool;
+
+    /// 当前版本，编码前为 `None`
+    fn version(&self) -> Option<Version>;
+
+    /// 使用的纠错级别
+    fn ec_level(&self) -> EcLevel;
+
+    /// 使用的掩码，编码前为 `None`
+    fn mask(&self) -> Option<Mask>;
+
+    /// 当前的模块矩阵，编码前为空矩阵
+    fn to_matrix(&self) -> QrMatrix {
+        let count = self.module_count().max(0);
+        let mut matrix = QrMatrix::square(count as usize);
+        for row in 0..count {
+            for col in 0..count {
+                if self.is_dark(row, col) {
+                    matrix.set(col as usize, row as usize, true);
+                }
+            }
+        }
+        matrix
+    }
+
+    /// 当前的编码结果，编码前为 `None`
+    fn symbol(&self) -> Option<QrSymbol> {
+        QrSymbol::from_matrix(
+            self.version()?,
+            self.ec_level(),
+            self.mask()?,
+            self.to_matrix(),
+        )
+        .ok()
+    }
+}
+
+/// 检查 `len` 字节的数据能否以字节模式放入指定版本
+pub fn check_capacity(len: usize, version: Version, ec_level: EcLevel) -> QrResult<()> {
+    let bits = 4 + get_length_in_bits(QRMode::MODE_8BIT_BYTE, version.get()) as usize + len * 8;
+    let capacity = capacity_bits(version.get(), ec_level);
+    if bits <= capacity {
+        Ok(())
+    } else {
+        Err(QrError::DataTooLong { bits, capacity })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qr_builder::QrBuilder;
+
+    /// 以共享构建器为后端的最小实现
+    struct BuilderEncoder {
+        ec_level: EcLevel,
+        symbol: Option<QrSymbol>,
+    }
+
+    impl QrEncoder for BuilderEncoder {
+        fn encode(&mut self, data: &[u8]) -> QrResult<QrSymbol> {
+            let symbol = QrBuilder::from_bytes(data)
+                .ec_level(self.ec_level)
+                .build()?;
+            self.symbol = Some(symbol.clone());
+            Ok(symbol)
+        }
+
+        fn module_count(&self) -> i32 {
+            self.symbol.as_ref().map_or(0, QrSymbol::module_count)
+        }
+
+        fn is_dark(&self, row: i32, col: i32) -> bool {
+            self.symbol.as_ref().is_some_and(|s| s.is_dark(row, col))
+        }
+
+        fn version(&self) -> Option<Version> {
+            self.symbol.as_ref().map(QrSymbol::version)
+        }
+
+        fn ec_level(&self) -> EcLevel {
+            self.ec_level
+        }
+
+        fn mask(&self) -> Option<Mask> {
+            self.symbol.as_ref().map(QrSymbol::mask)
+        }
+    }
+
+    #[test]
+    fn test_default_methods() {
+        let mut encoder = BuilderEncoder {
+            ec_level: EcLevel::M,
+            symbol: None,
+        };
+        assert_eq!(encoder.symbol(), None);
+        assert_eq!(encoder.to_matrix().width(), 0);
+        assert!(!encoder.is_dark(0, 0));
+
+        let symbol = encoder.encode(b"Hello World").unwrap();
+        assert_eq!(encoder.to_matrix(), *symbol.matrix());
+        // 由矩阵重建的符号与构建器的结果完全一致（含模块角色）
+        assert_eq!(encoder.symbol(), Some(symbol));
+    }
+
+    #[test]
+    fn test_check_capacity() {
+        let v1 = Version::MIN;
+        // 1-L：19 个数据码字 = 152 位，字节模式最多 17 字节
+        assert_eq!(check_capacity(17, v1, EcLevel::L), Ok(()));
+        assert_eq!(
+            check_capacity(18, v1, EcLevel::L),
+            Err(QrError::DataTooLong {
+                bits: 156,
+                capacity: 152,
+            })
+        );
+        assert!(check_capacity(2953, Version::MAX, EcLevel::L).is_ok());
+        assert!(check_capacity(2954, Version::MAX, EcLevel::L).is_err());
+    }
+
+    #[test]
+    fn test_symbol_from_matrix_size() {
+        let err = QrSymbol::from_matrix(
+            Version::MIN,
+            EcLevel::L,
+            Mask::default(),
+            QrMatrix::square(25),
+        );
+        assert_eq!(
+            err,
+            Err(QrError::MatrixSizeMismatch {
+                expected: 21,
+                actual: 25,
+            })
+        );
+    }
+}
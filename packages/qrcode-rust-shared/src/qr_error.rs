@@ -0,0 +1,121 @@
+//! QR Code Error - 编码错误类型
+
+use core::fmt;
+
+/// 编码过程中可能出现的错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QrError {
+    /// 数据在允许的版本范围内放不下
+    DataTooLong {
+        /// 需要的数据位数
+        bits: usize,
+        /// 最大版本下可用的数据位数
+        capacity: usize,
+    },
+    /// 版本号不在 1..=40 内
+    InvalidVersion(i32),
+    /// 版本范围为空（最小值大于最大值）
+    InvalidVersionRange { min: i32, max: i32 },
+    /// 掩码编号不在 0..=7 内
+    InvalidMask(u8),
+    /// ECI 指派号超出 0..=999999
+    InvalidEci(u32),
+    /// 无法解析的纠错级别（应为 L / M / Q / H）
+    InvalidEcLevel(String),
+    /// 给定的物理尺寸放不下满足最小模块尺寸的二维码
+    PrintSizeTooSmall {
+        /// 可用的像素边长
+        available_px: u32,
+        /// 最低纠错级别下需要的像素边长
+        required_px: u32,
+    },
+    /// 矩阵尺寸与版本不符
+    MatrixSizeMismatch { expected: usize, actual: usize },
+    /// 无法解析的颜色（应为 #rgb / #rgba / #rrggbb / #rrggbbaa）
+    InvalidColor(String),
+    /// 像素缓冲区的行跨度放不下一行像素
+    StrideTooSmall { stride: usize, required: usize },
+    /// 像素缓冲区太小
+    BufferTooSmall { len: usize, required: usize },
+}
+
+impl fmt::Display for QrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QrError::DataTooLong { bits, capacity } => write!(
+                f,
+                "data too long: needs {} bits but only {} are available",
+                bits, capacity
+            ),
+            QrError::InvalidVersion(v) => write!(f, "invalid version {} (expected 1..=40)", v),
+            QrError::InvalidVersionRange { min, max } => {
+                write!(f, "invalid version range {}..={}", min, max)
+            }
+            QrError::InvalidMask(m) => write!(f, "invalid mask {} (expected 0..=7)", m),
+            QrError::InvalidEci(n) => {
+                write!(f, "invalid ECI assignment {} (expected 0..=999999)", n)
+            }
+            QrError::InvalidEcLevel(s) => {
+                write!(
+                    f,
+                    "invalid error correction level {:?} (expected L/M/Q/H)",
+                    s
+                )
+            }
+            QrError::PrintSizeTooSmall {
+                available_px,
+                required_px,
+            } => write!(
+                f,
+                "print size too small: needs {} px but only {} are available",
+                required_px, available_px
+            ),
+            QrError::MatrixSizeMismatch { expected, actual } => write!(
+                f,
+                "matrix is {}x{} modules but the version needs {}x{}",
+                actual, actual, expected, expected
+            ),
+            QrError::InvalidColor(s) => write!(
+                f,
+                "invalid color {:?} (expected #rgb, #rgba, #rrggbb or #rrggbbaa)",
+                s
+            ),
+            QrError::StrideTooSmall { stride, required } => write!(
+                f,
+                "stride too small: rows need {} bytes but the stride is {}",
+                required, stride
+            ),
+            QrError::BufferTooSmall { len, required } => write!(
+                f,
+                "buffer too small: needs {} bytes but only {} are available",
+                required, len
+            ),
+        }
+    }
+}
+
+impl std::error::Error for QrError {}
+
+/// 编码结果
+pub type QrResult<T> = Result<T, QrError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let err = QrError::DataTooLong {
+            bits: 100,
+            capacity: 72,
+        };
+        assert_eq!(
+            err.to_string(),
+            "data too long: needs 100 bits but only 72 are available"
+        );
+        assert_eq!(
+            QrError::InvalidMask(9).to_string(),
+            "invalid mask 9 (expected 0..=7)"
+        );
+    }
+}
@@ -0,0 +1,217 @@
+//! QR Code Explain - 编码过程报告
+//!
+//! [`QrBuilder::explain`] 返回编码各阶段的结构化结果，
+//! 用于对比不同实现的中间数据（开启 `serde` 特性后可序列化为 JSON 再做 diff）。
+
+use crate::qr_builder::QrBuilder;
+use crate::qr_code_model::QRErrorCorrectLevel;
+use crate::qr_error::QrResult;
+use crate::qr_mask::{Mask, MASK_COUNT};
+use crate::qr_module_role::ModuleRole;
+use crate::qr_segment::SegmentMode;
+use crate::qr_symbol::QrSymbol;
+use crate::qr_template::{format_info_bits, version_info_bits};
+use crate::qr_version::Version;
+
+/// 编码过程报告
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Explanation {
+    pub version: Version,
+    pub ec_level: QRErrorCorrectLevel,
+    /// 实际使用的掩码
+    pub mask: Mask,
+    pub module_count: i32,
+    /// 各数据段及其在位流中的位置
+    pub segments: Vec<SegmentInfo>,
+    /// 数据段总位数（终止符和填充之前）
+    pub segment_bits: usize,
+    /// 填充后的数据码字
+    pub data_codewords: Vec<u8>,
+    /// 各 RS 块的数据码字与纠错码字
+    pub blocks: Vec<BlockInfo>,
+    /// 交织后的最终码字序列（按放置顺序）
+    pub interleaved: Vec<u8>,
+    /// 码字之后的剩余位数
+    pub remainder_bits: usize,
+    /// 15 位格式信息（已异或掩码）
+    pub format_info: u16,
+    /// 18 位版本信息（版本 7+）
+    pub version_info: Option<u32>,
+    /// 掩码 0-7 的惩罚分
+    pub mask_penalties: [u32; MASK_COUNT as usize],
+}
+
+/// 数据段信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SegmentInfo {
+    pub mode: SegmentMode,
+    /// 在数据位流中的起始位
+    pub bit_offset: usize,
+    /// 占用位数（含模式指示符与计数字段）
+    pub bit_length: usize,
+    /// 字符计数字段的值
+    pub char_count: usize,
+}
+
+/// RS 块信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockInfo {
+    pub data: Vec<u8>,
+    pub ec: Vec<u8>,
+}
+
+impl QrBuilder {
+    /// 编码并返回各阶段的结构化报告
+    pub fn explain(&self) -> QrResult<Explanation> {
+        self.explain_with_symbol()
+            .map(|(explanation, _)| explanation)
+    }
+
+    /// 同 [`explain`](Self::explain)，同时返回编码结果
+    pub fn explain_with_symbol(&self) -> QrResult<(Explanation, QrSymbol)> {
+        let parts = self.build_parts(true)?;
+        let symbol = parts.symbol;
+        let encoded = parts.encoded;
+
+        let mut bit_offset = 0;
+        let segments = self
+            .segments()
+            .iter()
+            .map(|seg| {
+                let info = SegmentInfo {
+                    mode: seg.mode(),
+                    bit_offset,
+                    bit_length: seg.bit_len(symbol.version().get()),
+                    char_count: seg.char_count(),
+                };
+                bit_offset += info.bit_length;
+                info
+            })
+            .collect();
+
+        let explanation = Explanation {
+            version: symbol.version(),
+            ec_level: symbol.ec_level(),
+            mask: symbol.mask(),
+            module_count: symbol.module_count(),
+            segments,
+            segment_bits: encoded.segment_bits,
+            blocks: encoded
+                .blocks
+                .iter()
+                .map(|block| BlockInfo {
+                    data: encoded.data_codewords[block.data.clone()].to_vec(),
+                    ec: block.ec.clone(),
+                })
+                .collect(),
+            data_codewords: encoded.data_codewords,
+            interleaved: encoded.codewords,
+            remainder_bits: symbol.roles().count(ModuleRole::Remainder),
+            format_info: format_info_bits(symbol.ec_level(), symbol.mask()) as u16,
+            version_info: (symbol.version().get() >= 7)
+                .then(|| version_info_bits(symbol.version()) as u32),
+            mask_penalties: parts.penalties.unwrap_or_default(),
+        };
+        Ok((explanation, symbol))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Explanation {
+    /// 序列化为带缩进的 JSON，便于 diff
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qr_mask::penalty_score;
+
+    #[test]
+    fn test_explain_hello_world() {
+        // 版本 1-M，字节模式：0100 00001011 01001000 ...
+        let (report, symbol) = QrBuilder::new("Hello World")
+            .ec_level(QRErrorCorrectLevel::M)
+            .explain_with_symbol()
+            .unwrap();
+
+        assert_eq!(report.version.get(), 1);
+        assert_eq!(report.module_count, 21);
+        assert_eq!(
+            report.segments,
+            vec![SegmentInfo {
+                mode: SegmentMode::Byte,
+                bit_offset: 0,
+                bit_length: 4 + 8 + 88,
+                char_count: 11,
+            }]
+        );
+        assert_eq!(report.segment_bits, 100);
+        assert_eq!(report.data_codewords.len(), 16);
+        assert_eq!(&report.data_codewords[..2], &[0x40, 0xB4]);
+        assert_eq!(&report.data_codewords[13..], &[0xEC, 0x11, 0xEC]);
+
+        // 单块：交织序列 = 数据码字 + 纠错码字
+        assert_eq!(report.blocks.len(), 1);
+        assert_eq!(report.blocks[0].data, report.data_codewords);
+        assert_eq!(report.blocks[0].ec.len(), 10);
+        let mut expected = report.blocks[0].data.clone();
+        expected.extend(&report.blocks[0].ec);
+        assert_eq!(report.interleaved, expected);
+
+        assert_eq!(report.remainder_bits, 0);
+        assert_eq!(report.version_info, None);
+        assert_eq!(
+            report.format_info as i32,
+            format_info_bits(QRErrorCorrectLevel::M, report.mask)
+        );
+
+        // 报告中的惩罚分与实际选择的掩码一致
+        let best = *report.mask_penalties.iter().min().unwrap();
+        assert_eq!(report.mask_penalties[report.mask.get() as usize], best);
+        assert_eq!(penalty_score(symbol.matrix()), best);
+    }
+
+    #[test]
+    fn test_explain_multi_block_interleave() {
+        let report = QrBuilder::new(&"x".repeat(60))
+            .ec_level(QRErrorCorrectLevel::Q)
+            .eci(26)
+            .mask(Mask::new(1).unwrap())
+            .explain()
+            .unwrap();
+
+        assert_eq!(report.mask.get(), 1);
+        assert_eq!(report.segments.len(), 2);
+        assert_eq!(report.segments[0].mode, SegmentMode::Eci);
+        assert_eq!(report.segments[1].bit_offset, report.segments[0].bit_length);
+        assert!(report.blocks.len() > 1);
+
+        // 交织序列依次取各块的第 i 个码字
+        let max_data = report.blocks.iter().map(|b| b.data.len()).max().unwrap();
+        let mut expected = Vec::new();
+        for i in 0..max_data {
+            expected.extend(report.blocks.iter().filter_map(|b| b.data.get(i)));
+        }
+        for i in 0..report.blocks[0].ec.len() {
+            expected.extend(report.blocks.iter().map(|b| b.ec[i]));
+        }
+        assert_eq!(report.interleaved, expected);
+        assert!(report.mask_penalties.iter().all(|&p| p > 0));
+    }
+
+    #[test]
+    fn test_explain_version_info() {
+        let report = QrBuilder::new(&"x".repeat(150)).explain().unwrap();
+        assert!(report.version.get() >= 7);
+        assert_eq!(
+            report.version_info,
+            Some(version_info_bits(report.version) as u32)
+        );
+    }
+}
@@ -0,0 +1,368 @@
+//! QR Code Mask - 掩码图案与惩罚分计算
+//!
+//! 掩码条件与 ISO/IEC 18004 表 10 一致（`i` = 行，`j` = 列），
+//! 惩罚分按 N1-N4 四条规则累加，分数越低越好，在 64 位压缩行上按位计算。
+
+use core::fmt;
+
+use crate::qr_error::{QrError, QrResult};
+use crate::qr_matrix::QrMatrix;
+use crate::qr_template::VersionTemplate;
+
+/// 掩码图案数量
+pub const MASK_COUNT: u8 = 8;
+
+/// 掩码编号，保证在 0..=7 内
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "u8", into = "u8")
+)]
+pub struct Mask(pub(crate) u8);
+
+impl Mask {
+    /// 全部 8 个掩码
+    pub const ALL: [Mask; MASK_COUNT as usize] = [
+        Mask(0),
+        Mask(1),
+        Mask(2),
+        Mask(3),
+        Mask(4),
+        Mask(5),
+        Mask(6),
+        Mask(7),
+    ];
+
+    /// 校验并创建掩码编号
+    pub fn new(mask: u8) -> QrResult<Self> {
+        if mask < MASK_COUNT {
+            Ok(Mask(mask))
+        } else {
+            Err(QrError::InvalidMask(mask))
+        }
+    }
+
+    /// 掩码编号 (0-7)
+    pub fn get(self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<u8> for Mask {
+    type Error = QrError;
+
+    fn try_from(mask: u8) -> QrResult<Self> {
+        Mask::new(mask)
+    }
+}
+
+impl From<Mask> for u8 {
+    fn from(mask: Mask) -> u8 {
+        mask.get()
+    }
+}
+
+impl fmt::Display for Mask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// 判断 (row, col) 处的模块是否需要被 `mask` 翻转
+#[inline]
+pub fn mask_bit(mask: Mask, row: usize, col: usize) -> bool {
+    let (i, j) = (row, col);
+    match mask.get() {
+        0 => (i + j) % 2 == 0,
+        1 => i % 2 == 0,
+        2 => j % 3 == 0,
+        3 => (i + j) % 3 == 0,
+        4 => (i / 2 + j / 3) % 2 == 0,
+        5 => (i * j) % 2 + (i * j) % 3 == 0,
+        6 => ((i * j) % 2 + (i * j) % 3) % 2 == 0,
+        7 => ((i * j) % 3 + (i + j) % 2) % 2 == 0,
+        _ => unreachable!(),
+    }
+}
+
+/// 计算矩阵的总惩罚分（N1 + N2 + N3 + N4）
+///
+/// 按 64 位压缩行计算：连续段由相邻位异或后的跳变位定位，
+/// 2x2 块与定位样式一次比较 64 个起点，列方向在转置后复用同样的逻辑。
+pub fn penalty_score(matrix: &QrMatrix) -> u32 {
+    let size = matrix.width();
+    if size == 0 {
+        return 0;
+    }
+    let columns = matrix.transpose();
+
+    let mut score = 0u32;
+    for i in 0..size {
+        // N1 + N3，行与列
+        score += line_penalty(matrix.row(i), size) + line_penalty(columns.row(i), size);
+    }
+
+    // N2: 2x2 同色块，每个 3 分
+    score += 3 * same_color_blocks(matrix);
+
+    // N4: 深色比例每偏离 50% 5 个百分点得 10 分
+    let total = size * size;
+    let k = (matrix.count_dark() * 20).abs_diff(total * 10) / total;
+    score += 10 * k as u32;
+
+    score
+}
+
+/// 计算 8 个掩码的惩罚分
+///
+/// `base` 为尚未加掩码的符号，每个候选与 [`VersionTemplate::mask_pattern`] 异或得到；
+/// `finish` 在评分前写入随掩码变化的内容（如格式信息）。
+/// 开启 `rayon` 特性时 8 个掩码并行计算。
+pub fn score_masks<F>(
+    base: &QrMatrix,
+    template: &VersionTemplate,
+    finish: F,
+) -> [u32; MASK_COUNT as usize]
+where
+    F: Fn(&mut QrMatrix, Mask) + Sync,
+{
+    let score = |mask: Mask| {
+        let mut candidate = base.clone();
+        candidate.xor_with(template.mask_pattern(mask));
+        finish(&mut candidate, mask);
+        penalty_score(&candidate)
+    };
+
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        let mut scores = [0; MASK_COUNT as usize];
+        scores
+            .par_iter_mut()
+            .zip(Mask::ALL.par_iter())
+            .for_each(|(slot, &mask)| *slot = score(mask));
+        scores
+    }
+    #[cfg(not(feature = "rayon"))]
+    Mask::ALL.map(score)
+}
+
+/// 惩罚分最低的掩码，同分时取编号小的
+pub fn best_mask(scores: &[u32; MASK_COUNT as usize]) -> Mask {
+    Mask::ALL
+        .into_iter()
+        .min_by_key(|&mask| scores[mask.get() as usize])
+        .unwrap_or_default()
+}
+
+/// 2x2 同色块的个数（块可以重叠），一次比较 64 个起点
+pub fn same_color_blocks(matrix: &QrMatrix) -> u32 {
+    let size = matrix.width();
+    let mut count = 0;
+    for y in 0..size.saturating_sub(1) {
+        let (upper, lower) = (matrix.row(y), matrix.row(y + 1));
+        for start in (0..size - 1).step_by(64) {
+            let a = window(upper, start);
+            let same = !(a ^ window(upper, start + 1))
+                & !(a ^ window(lower, start))
+                & !(a ^ window(lower, start + 1))
+                & high_bits(size - 1 - start);
+            count += same.count_ones();
+        }
+    }
+    count
+}
+
+/// 1:1:3:1:1 定位样式本身，共 7 个模块（高位在前）
+pub const FINDER_CORE: u64 = 0b101_1101 << 57;
+
+/// 1:1:3:1:1 定位样式，一侧带 4 个浅色模块，共 11 个模块（高位在前）
+const FINDER_LIKE: [u64; 2] = [FINDER_CORE, FINDER_CORE >> 4];
+
+/// 一行（或转置后的一列）的 N1 + N3 惩罚分
+fn line_penalty(line: &[u64], len: usize) -> u32 {
+    let mut score = 0;
+
+    // N1: 连续 5 个及以上同色模块，得 3 + (长度 - 5) 分；
+    // 相邻位异或后的 1 即为一段的结尾
+    let mut run_start = 0;
+    for start in (0..len - 1).step_by(64) {
+        let mut ends = (window(line, start) ^ window(line, start + 1)) & high_bits(len - 1 - start);
+        while ends != 0 {
+            let offset = ends.leading_zeros() as usize;
+            ends ^= 1 << (63 - offset);
+            let end = start + offset + 1;
+            score += run_penalty(end - run_start);
+            run_start = end;
+        }
+    }
+    score += run_penalty(len - run_start);
+
+    // N3: 每处定位样式 40 分，一次检查 64 个起点
+    if len >= 11 {
+        for start in (0..=len - 11).step_by(64) {
+            let mut found = [high_bits(len - 10 - start); 2];
+            for k in 0..11 {
+                let w = window(line, start + k);
+                for (found, pattern) in found.iter_mut().zip(FINDER_LIKE) {
+                    *found &= if pattern >> (63 - k) & 1 == 1 { w } else { !w };
+                }
+            }
+            score += 40 * (found[0].count_ones() + found[1].count_ones());
+        }
+    }
+
+    score
+}
+
+fn run_penalty(run: usize) -> u32 {
+    if run >= 5 {
+        (run - 2) as u32
+    } else {
+        0
+    }
+}
+
+/// 从第 `start` 个模块开始的 64 位（高位在前），超出行尾的部分为 0
+#[inline]
+pub fn window(line: &[u64], start: usize) -> u64 {
+    let (word, offset) = (start / 64, start % 64);
+    let high = line.get(word).map_or(0, |w| w << offset);
+    let low = match offset {
+        0 => 0,
+        _ => line.get(word + 1).map_or(0, |w| w >> (64 - offset)),
+    };
+    high | low
+}
+
+/// 最高的 `n` 位为 1（`n` 超过 64 时取全部）
+#[inline]
+pub fn high_bits(n: usize) -> u64 {
+    u64::MAX.checked_shr(n as u32).map_or(u64::MAX, |low| !low)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_bit() {
+        let [m0, m1, m2, _, _, m5, _, _] = Mask::ALL;
+        assert!(mask_bit(m0, 0, 0));
+        assert!(!mask_bit(m0, 0, 1));
+        assert!(mask_bit(m1, 2, 5));
+        assert!(mask_bit(m2, 1, 3));
+        assert!(mask_bit(m5, 0, 7));
+    }
+
+    #[test]
+    fn test_mask_invalid() {
+        // 越界的掩码编号无法构造
+        assert_eq!(Mask::new(8), Err(QrError::InvalidMask(8)));
+        assert_eq!(Mask::try_from(7).map(u8::from), Ok(7));
+        assert_eq!(Mask::ALL[3].to_string(), "3");
+    }
+
+    #[test]
+    fn test_penalty_uniform() {
+        // 全浅色 21x21：N1 = 42 * (3 + 16)，N2 = 400 * 3，N4 = 100
+        let m = QrMatrix::square(21);
+        assert_eq!(penalty_score(&m), 42 * 19 + 400 * 3 + 100);
+    }
+
+    /// 逐模块的朴素实现，用于校验按位计算的结果
+    fn reference_penalty(matrix: &QrMatrix) -> u32 {
+        let size = matrix.width();
+        let dark = |x: usize, y: usize| matrix.get(x, y);
+        let mut score = 0;
+        for a in 0..size {
+            for horizontal in [true, false] {
+                let at = |b: usize| if horizontal { dark(b, a) } else { dark(a, b) };
+                let mut run = 1;
+                for b in 1..=size {
+                    if b < size && at(b) == at(b - 1) {
+                        run += 1;
+                        continue;
+                    }
+                    if run >= 5 {
+                        score += 3 + (run - 5);
+                    }
+                    run = 1;
+                }
+                if size >= 11 {
+                    for b in 0..=size - 11 {
+                        for pattern in FINDER_LIKE {
+                            if (0..11).all(|k| at(b + k) == (pattern >> (63 - k) & 1 == 1)) {
+                                score += 40;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let c = dark(x, y);
+                if dark(x + 1, y) == c && dark(x, y + 1) == c && dark(x + 1, y + 1) == c {
+                    score += 3;
+                }
+            }
+        }
+        let total = size * size;
+        score + 10 * ((matrix.count_dark() * 20).abs_diff(total * 10) / total) as u32
+    }
+
+    #[test]
+    fn test_penalty_matches_reference() {
+        // 覆盖单字、跨字边界（>64）和三个字（177）的行宽
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        for size in [21, 45, 63, 64, 65, 97, 128, 177] {
+            let mut m = QrMatrix::square(size);
+            for y in 0..size {
+                for x in 0..size {
+                    seed ^= seed << 13;
+                    seed ^= seed >> 7;
+                    seed ^= seed << 17;
+                    // 偏向长连续段，让 N1-N3 都有命中
+                    m.set(x, y, seed % 5 < 2 || (x / 7 + y / 3) % 4 == 0);
+                }
+            }
+            assert_eq!(penalty_score(&m), reference_penalty(&m), "size {size}");
+        }
+    }
+
+    #[test]
+    fn test_score_masks() {
+        let symbol = crate::qr_builder::QrBuilder::new("mask scoring")
+            .mask(Mask::ALL[0])
+            .build()
+            .unwrap();
+        let template = VersionTemplate::get(symbol.version());
+        let mut base = symbol.matrix().clone();
+        base.xor_with(template.mask_pattern(Mask::ALL[0]));
+
+        let scores = score_masks(&base, template, |_, _| {});
+        for mask in Mask::ALL {
+            let mut candidate = base.clone();
+            candidate.xor_with(template.mask_pattern(mask));
+            assert_eq!(scores[mask.get() as usize], penalty_score(&candidate));
+        }
+        assert_eq!(
+            scores[best_mask(&scores).get() as usize],
+            *scores.iter().min().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_penalty_checkerboard() {
+        // 棋盘格没有连续、2x2 块和定位样式，深浅比例接近 50%
+        let mut m = QrMatrix::square(21);
+        for y in 0..21 {
+            for x in 0..21 {
+                m.set(x, y, (x + y) % 2 == 0);
+            }
+        }
+        assert_eq!(penalty_score(&m), 0);
+    }
+}
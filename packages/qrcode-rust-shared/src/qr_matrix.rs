@@ -0,0 +1,350 @@
+//! QR Code Matrix - 位压缩的模块矩阵
+//!
+//! 所有实现共用的模块存储格式：每行按 64 位字压缩，
+//! 第 `x` 列位于 `row(y)[x / 64]` 的第 `63 - x % 64` 位（高位在前）。
+
+/// 位压缩的二维码模块矩阵（`true` = 深色）
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct QrMatrix {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    bits: Vec<u64>,
+}
+
+impl QrMatrix {
+    /// 创建全部为浅色的矩阵
+    pub fn new(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(64);
+        QrMatrix {
+            width,
+            height,
+            words_per_row,
+            bits: vec![0; words_per_row * height],
+        }
+    }
+
+    /// 创建正方形矩阵（二维码的常见情况）
+    pub fn square(size: usize) -> Self {
+        Self::new(size, size)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// 每行占用的 64 位字数
+    pub fn words_per_row(&self) -> usize {
+        self.words_per_row
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// 读取 (x, y) 处的模块，越界返回 false
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        if x >= self.width || y >= self.height {
+            return false;
+        }
+        let word = self.bits[y * self.words_per_row + x / 64];
+        (word >> (63 - x % 64)) & 1 == 1
+    }
+
+    /// 设置 (x, y) 处的模块，越界时忽略
+    #[inline]
+    pub fn set(&mut self, x: usize, y: usize, dark: bool) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let bit = 1u64 << (63 - x % 64);
+        let word = &mut self.bits[y * self.words_per_row + x / 64];
+        if dark {
+            *word |= bit;
+        } else {
+            *word &= !bit;
+        }
+    }
+
+    /// 翻转 (x, y) 处的模块，越界时忽略
+    #[inline]
+    pub fn toggle(&mut self, x: usize, y: usize) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        self.bits[y * self.words_per_row + x / 64] ^= 1u64 << (63 - x % 64);
+    }
+
+    /// 第 `y` 行的压缩数据（末尾多余的位始终为 0）
+    pub fn row(&self, y: usize) -> &[u64] {
+        let start = y * self.words_per_row;
+        &self.bits[start..start + self.words_per_row]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [u64] {
+        let start = y * self.words_per_row;
+        &mut self.bits[start..start + self.words_per_row]
+    }
+
+    /// 全部压缩数据（按行连续存放）
+    pub fn as_words(&self) -> &[u64] {
+        &self.bits
+    }
+
+    /// 深色模块总数
+    pub fn count_dark(&self) -> usize {
+        self.bits.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// 与同尺寸矩阵逐位异或（整行 64 位一次，用于应用掩码位图）
+    pub fn xor_with(&mut self, other: &QrMatrix) {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "matrix size mismatch"
+        );
+        for (word, other) in self.bits.iter_mut().zip(&other.bits) {
+            *word ^= other;
+        }
+    }
+
+    /// 转置矩阵：列变为行，按列统计时可以复用按行的位运算
+    pub fn transpose(&self) -> QrMatrix {
+        let mut transposed = QrMatrix::new(self.height, self.width);
+        for (x, y) in self.dark_modules() {
+            transposed.set(y, x, true);
+        }
+        transposed
+    }
+
+    /// 按行优先顺序把所有模块连续打包为字节（高位在前，末字节不足补 0）
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0u8; (self.width * self.height).div_ceil(8)];
+        for (x, y) in self.dark_modules() {
+            let i = y * self.width + x;
+            bytes[i / 8] |= 0x80 >> (i % 8);
+        }
+        bytes
+    }
+
+    /// 从 [`to_bytes`](Self::to_bytes) 的输出还原，长度不符返回 `None`
+    pub fn from_bytes(width: usize, height: usize, bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != (width * height).div_ceil(8) {
+            return None;
+        }
+        let mut matrix = QrMatrix::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let i = y * width + x;
+                if bytes[i / 8] & (0x80 >> (i % 8)) != 0 {
+                    matrix.set(x, y, true);
+                }
+            }
+        }
+        Some(matrix)
+    }
+
+    /// 按行优先顺序遍历所有深色模块，产出 `(x, y)`
+    pub fn dark_modules(&self) -> DarkModules<'_> {
+        DarkModules {
+            matrix: self,
+            index: 0,
+            current: self.bits.first().copied().unwrap_or(0),
+        }
+    }
+
+    /// 从 `Vec<Vec<Option<bool>>>`（qrcode-rust / wasm 模型）转换，`None` 视为浅色
+    pub fn from_option_rows(rows: &[Vec<Option<bool>>]) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |r| r.len());
+        let mut matrix = QrMatrix::new(width, height);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if cell.unwrap_or(false) {
+                    matrix.set(x, y, true);
+                }
+            }
+        }
+        matrix
+    }
+
+    /// 转换为 `Vec<Vec<Option<bool>>>`，每个模块均为 `Some`
+    pub fn to_option_rows(&self) -> Vec<Vec<Option<bool>>> {
+        (0..self.height)
+            .map(|y| (0..self.width).map(|x| Some(self.get(x, y))).collect())
+            .collect()
+    }
+
+    /// 从 qrcode-fast 的一维状态数组转换
+    /// (0 = 未设置, 1 = 深色, 2 = 已设置但浅色)
+    pub fn from_states(states: &[u8], size: usize) -> Self {
+        let mut matrix = QrMatrix::square(size);
+        for (y, row) in states.chunks_exact(size.max(1)).take(size).enumerate() {
+            for (x, &state) in row.iter().enumerate() {
+                if state == 1 {
+                    matrix.set(x, y, true);
+                }
+            }
+        }
+        matrix
+    }
+
+    /// 转换为 qrcode-fast 的一维状态数组（1 = 深色, 2 = 浅色）
+    pub fn to_states(&self) -> Vec<u8> {
+        let mut states = Vec::with_capacity(self.width * self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                states.push(if self.get(x, y) { 1 } else { 2 });
+            }
+        }
+        states
+    }
+
+    /// 四周加上 `quiet_zone` 个模块宽的浅色边距
+    pub fn with_quiet_zone(&self, quiet_zone: usize) -> QrMatrix {
+        let mut padded = QrMatrix::new(self.width + quiet_zone * 2, self.height + quiet_zone * 2);
+        for (x, y) in self.dark_modules() {
+            padded.set(x + quiet_zone, y + quiet_zone, true);
+        }
+        padded
+    }
+}
+
+/// 深色模块迭代器，见 [`QrMatrix::dark_modules`]
+pub struct DarkModules<'a> {
+    matrix: &'a QrMatrix,
+    index: usize,
+    current: u64,
+}
+
+impl Iterator for DarkModules<'_> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let wpr = self.matrix.words_per_row;
+        loop {
+            if self.current != 0 {
+                let bit = self.current.leading_zeros() as usize;
+                self.current &= !(1u64 << (63 - bit));
+                let y = self.index / wpr;
+                let x = (self.index % wpr) * 64 + bit;
+                return Some((x, y));
+            }
+            self.index += 1;
+            if self.index >= self.matrix.bits.len() {
+                return None;
+            }
+            self.current = self.matrix.bits[self.index];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_set() {
+        let mut m = QrMatrix::square(70);
+        m.set(0, 0, true);
+        m.set(69, 3, true);
+        m.set(64, 69, true);
+        assert!(m.get(0, 0));
+        assert!(m.get(69, 3));
+        assert!(m.get(64, 69));
+        assert!(!m.get(1, 0));
+        assert!(!m.get(70, 0), "越界应返回 false");
+        assert_eq!(m.count_dark(), 3);
+
+        m.set(0, 0, false);
+        m.toggle(1, 1);
+        assert!(!m.get(0, 0));
+        assert!(m.get(1, 1));
+        assert_eq!(m.words_per_row(), 2);
+    }
+
+    #[test]
+    fn test_row_layout_msb_first() {
+        let mut m = QrMatrix::square(21);
+        m.set(0, 2, true);
+        m.set(20, 2, true);
+        assert_eq!(m.row(2), &[(1u64 << 63) | (1u64 << 43)]);
+    }
+
+    #[test]
+    fn test_xor_and_transpose() {
+        let mut m = QrMatrix::new(100, 3);
+        m.set(70, 1, true);
+        m.set(2, 2, true);
+        let t = m.transpose();
+        assert_eq!((t.width(), t.height()), (3, 100));
+        assert!(t.get(1, 70) && t.get(2, 2));
+        assert_eq!(t.transpose(), m);
+
+        let mut flipped = m.clone();
+        flipped.xor_with(&m);
+        assert_eq!(flipped.count_dark(), 0);
+    }
+
+    #[test]
+    fn test_dark_modules_order() {
+        let mut m = QrMatrix::new(100, 3);
+        for &(x, y) in &[(5, 0), (99, 0), (0, 2), (64, 2)] {
+            m.set(x, y, true);
+        }
+        let dark: Vec<_> = m.dark_modules().collect();
+        assert_eq!(dark, vec![(5, 0), (99, 0), (0, 2), (64, 2)]);
+    }
+
+    #[test]
+    fn test_option_rows_round_trip() {
+        let rows = vec![
+            vec![Some(true), None, Some(false)],
+            vec![None, Some(true), Some(true)],
+            vec![Some(false), Some(false), Some(true)],
+        ];
+        let m = QrMatrix::from_option_rows(&rows);
+        assert!(m.get(0, 0) && m.get(1, 1) && m.get(2, 1) && m.get(2, 2));
+        assert_eq!(m.count_dark(), 4);
+        assert_eq!(QrMatrix::from_option_rows(&m.to_option_rows()), m);
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let mut m = QrMatrix::square(3);
+        m.set(0, 0, true);
+        m.set(2, 2, true);
+        assert_eq!(m.to_bytes(), vec![0b1000_0000, 0b1000_0000]);
+        assert_eq!(QrMatrix::from_bytes(3, 3, &m.to_bytes()), Some(m));
+        assert_eq!(QrMatrix::from_bytes(3, 3, &[0]), None);
+    }
+
+    #[test]
+    fn test_states_round_trip() {
+        let states = [1, 0, 2, 2, 1, 0, 0, 0, 1];
+        let m = QrMatrix::from_states(&states, 3);
+        assert_eq!(
+            m.dark_modules().collect::<Vec<_>>(),
+            vec![(0, 0), (1, 1), (2, 2)]
+        );
+        assert_eq!(m.to_states(), vec![1, 2, 2, 2, 1, 2, 2, 2, 1]);
+    }
+
+    #[test]
+    fn test_with_quiet_zone() {
+        let mut m = QrMatrix::new(2, 3);
+        m.set(0, 0, true);
+        m.set(1, 2, true);
+        let padded = m.with_quiet_zone(2);
+        assert_eq!((padded.width(), padded.height()), (6, 7));
+        assert_eq!(
+            padded.dark_modules().collect::<Vec<_>>(),
+            vec![(2, 2), (3, 4)]
+        );
+        assert_eq!(m.with_quiet_zone(0), m);
+    }
+}
@@ -0,0 +1,161 @@
+//! QR Code Module Role - 模块角色分类
+//!
+//! 编码器在绘制每个模块时记录它属于哪一类图案，
+//! 方便渲染端为定位图案、校正图案、数据区等设置不同样式。
+
+/// 模块角色
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ModuleRole {
+    /// 位置探测图案（7x7）
+    Finder,
+    /// 位置探测图案外围的分隔符
+    Separator,
+    /// 位置校正图案（5x5）
+    Alignment,
+    /// 定时图案
+    Timing,
+    /// 格式信息
+    FormatInfo,
+    /// 版本信息（版本 7+）
+    VersionInfo,
+    /// 固定暗模块
+    DarkModule,
+    /// 数据码字
+    #[default]
+    Data,
+    /// 纠错码字
+    ErrorCorrection,
+    /// 剩余位
+    Remainder,
+}
+
+impl ModuleRole {
+    /// 所有角色，按上面的声明顺序
+    pub const ALL: [ModuleRole; 10] = [
+        ModuleRole::Finder,
+        ModuleRole::Separator,
+        ModuleRole::Alignment,
+        ModuleRole::Timing,
+        ModuleRole::FormatInfo,
+        ModuleRole::VersionInfo,
+        ModuleRole::DarkModule,
+        ModuleRole::Data,
+        ModuleRole::ErrorCorrection,
+        ModuleRole::Remainder,
+    ];
+
+    /// 是否为功能图案（不承载数据）
+    pub fn is_function(self) -> bool {
+        !matches!(
+            self,
+            ModuleRole::Data | ModuleRole::ErrorCorrection | ModuleRole::Remainder
+        )
+    }
+
+    /// 根据放置时的码字下标判断数据区模块的角色
+    ///
+    /// `data_count` 为数据码字数，`total_count` 为数据 + 纠错码字数。
+    pub fn for_codeword(index: usize, data_count: usize, total_count: usize) -> ModuleRole {
+        if index < data_count {
+            ModuleRole::Data
+        } else if index < total_count {
+            ModuleRole::ErrorCorrection
+        } else {
+            ModuleRole::Remainder
+        }
+    }
+}
+
+/// 每个模块的角色表，按 `row * size + col` 存放
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RoleMap {
+    size: usize,
+    roles: Vec<ModuleRole>,
+}
+
+impl RoleMap {
+    /// 创建 `size x size` 的角色表，初始全部为 [`ModuleRole::Data`]
+    pub fn new(size: usize) -> Self {
+        RoleMap {
+            size,
+            roles: vec![ModuleRole::Data; size * size],
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// 读取角色，越界返回 `None`
+    #[inline]
+    pub fn get(&self, row: i32, col: i32) -> Option<ModuleRole> {
+        if row < 0 || col < 0 || row as usize >= self.size || col as usize >= self.size {
+            return None;
+        }
+        Some(self.roles[row as usize * self.size + col as usize])
+    }
+
+    /// 设置角色，越界时忽略
+    #[inline]
+    pub fn set(&mut self, row: i32, col: i32, role: ModuleRole) {
+        if row < 0 || col < 0 || row as usize >= self.size || col as usize >= self.size {
+            return;
+        }
+        self.roles[row as usize * self.size + col as usize] = role;
+    }
+
+    /// 按行优先顺序遍历指定角色的模块，产出 `(row, col)`
+    pub fn iter_role(&self, role: ModuleRole) -> impl Iterator<Item = (i32, i32)> + '_ {
+        let size = self.size;
+        self.roles
+            .iter()
+            .enumerate()
+            .filter(move |&(_, &r)| r == role)
+            .map(move |(i, _)| ((i / size) as i32, (i % size) as i32))
+    }
+
+    /// 指定角色的模块数量
+    pub fn count(&self, role: ModuleRole) -> usize {
+        self.roles.iter().filter(|&&r| r == role).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_map_get_set() {
+        let mut map = RoleMap::new(21);
+        assert_eq!(map.get(0, 0), Some(ModuleRole::Data));
+        map.set(0, 0, ModuleRole::Finder);
+        map.set(20, 20, ModuleRole::Remainder);
+        map.set(21, 0, ModuleRole::Finder); // 越界忽略
+        assert_eq!(map.get(0, 0), Some(ModuleRole::Finder));
+        assert_eq!(map.get(20, 20), Some(ModuleRole::Remainder));
+        assert_eq!(map.get(21, 0), None);
+        assert_eq!(map.get(-1, 0), None);
+    }
+
+    #[test]
+    fn test_iter_role() {
+        let mut map = RoleMap::new(5);
+        map.set(1, 3, ModuleRole::Timing);
+        map.set(4, 0, ModuleRole::Timing);
+        let timing: Vec<_> = map.iter_role(ModuleRole::Timing).collect();
+        assert_eq!(timing, vec![(1, 3), (4, 0)]);
+        assert_eq!(map.count(ModuleRole::Data), 23);
+    }
+
+    #[test]
+    fn test_for_codeword() {
+        assert_eq!(ModuleRole::for_codeword(0, 9, 26), ModuleRole::Data);
+        assert_eq!(
+            ModuleRole::for_codeword(9, 9, 26),
+            ModuleRole::ErrorCorrection
+        );
+        assert_eq!(ModuleRole::for_codeword(26, 9, 26), ModuleRole::Remainder);
+        assert!(ModuleRole::Finder.is_function());
+        assert!(!ModuleRole::ErrorCorrection.is_function());
+    }
+}
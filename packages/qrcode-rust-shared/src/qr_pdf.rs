@@ -0,0 +1,448 @@
+//! QR Code PDF - 无依赖的 PDF 1.4 矢量输出
+//!
+//! 单页 PDF，二维码是按精确物理尺寸缩放的矢量路径。深色区域复用 SVG `Contours`
+//! 模式的合并轮廓（[`contour_polygons`]），一个 `f*` 填充全部模块，文件通常只有一两 KB。
+//! 支持 DeviceGray / DeviceRGB / DeviceCMYK、出血和裁切标记，供印刷厂直接使用。
+
+use std::fmt::Write;
+
+use crate::qr_matrix::QrMatrix;
+use crate::qr_png::zlib_compress;
+use crate::qr_svg::contour_polygons;
+
+const MM_PER_INCH: f64 = 25.4;
+const PT_PER_INCH: f64 = 72.0;
+/// 裁切标记距成品边的最小距离（毫米），出血更大时取出血宽度
+const CROP_MARK_OFFSET_MM: f64 = 3.0;
+/// 裁切标记长度（毫米）
+const CROP_MARK_LENGTH_MM: f64 = 5.0;
+/// 裁切标记线宽（pt）
+const CROP_MARK_WIDTH_PT: f64 = 0.25;
+
+/// 颜色及其颜色空间，各分量取值 0.0–1.0
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PdfColor {
+    /// DeviceGray：0 = 黑，1 = 白
+    Gray(f64),
+    /// DeviceRGB
+    Rgb(f64, f64, f64),
+    /// DeviceCMYK
+    Cmyk(f64, f64, f64, f64),
+}
+
+impl PdfColor {
+    /// 由 8 位 RGB 构造 DeviceRGB 颜色
+    pub fn from_rgb8([r, g, b]: [u8; 3]) -> Self {
+        PdfColor::Rgb(r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0)
+    }
+
+    /// 写入设置颜色的操作符，`stroke` 为 true 时设置描边色
+    fn push_operator(self, out: &mut String, stroke: bool) {
+        let (components, operator): (&[f64], _) = match &self {
+            PdfColor::Gray(g) => (&[*g][..], "g"),
+            PdfColor::Rgb(r, g, b) => (&[*r, *g, *b][..], "rg"),
+            PdfColor::Cmyk(c, m, y, k) => (&[*c, *m, *y, *k][..], "k"),
+        };
+        for &component in components {
+            push_number(out, component.clamp(0.0, 1.0));
+            out.push(' ');
+        }
+        if stroke {
+            out.push_str(&operator.to_uppercase());
+        } else {
+            out.push_str(operator);
+        }
+        out.push('\n');
+    }
+
+    /// 同一颜色空间中的套准色（所有色版都印），用于裁切标记
+    fn registration(self) -> Self {
+        match self {
+            PdfColor::Gray(_) => PdfColor::Gray(0.0),
+            PdfColor::Rgb(..) => PdfColor::Rgb(0.0, 0.0, 0.0),
+            PdfColor::Cmyk(..) => PdfColor::Cmyk(1.0, 1.0, 1.0, 1.0),
+        }
+    }
+}
+
+/// PDF 输出选项
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdfOptions {
+    /// 二维码边长（毫米，含空白边），即成品尺寸
+    pub size_mm: f64,
+    /// 空白边宽度（模块数）
+    pub quiet_zone: u32,
+    pub dark: PdfColor,
+    /// 浅色模块、空白边与出血的颜色；`None` 时不绘制（露出纸色）
+    pub light: Option<PdfColor>,
+    /// 页面尺寸（宽, 高，毫米）；`None` 时页面恰好容纳二维码、出血和裁切标记
+    pub page_mm: Option<(f64, f64)>,
+    /// 二维码左上角相对页面左上角的位置（毫米）；`None` 时居中
+    pub position_mm: Option<(f64, f64)>,
+    /// 出血宽度（毫米），浅色背景向外延伸这么多并写入 `/BleedBox`
+    pub bleed_mm: f64,
+    /// 在四角绘制裁切标记
+    pub crop_marks: bool,
+    /// 用 FlateDecode 压缩内容流
+    pub compress: bool,
+}
+
+impl Default for PdfOptions {
+    fn default() -> Self {
+        PdfOptions {
+            size_mm: 30.0,
+            quiet_zone: 4,
+            dark: PdfColor::Gray(0.0),
+            light: Some(PdfColor::Gray(1.0)),
+            page_mm: None,
+            position_mm: None,
+            bleed_mm: 0.0,
+            crop_marks: false,
+            compress: true,
+        }
+    }
+}
+
+impl PdfOptions {
+    /// 成品边到页面边的最小距离（毫米）：出血，或出血加裁切标记
+    fn margin_mm(&self) -> f64 {
+        let bleed = self.bleed_mm.max(0.0);
+        if self.crop_marks {
+            bleed.max(CROP_MARK_OFFSET_MM) + CROP_MARK_LENGTH_MM
+        } else {
+            bleed
+        }
+    }
+}
+
+/// 生成单页 PDF
+pub fn render_pdf(matrix: &QrMatrix, options: &PdfOptions) -> Vec<u8> {
+    let margin = options.margin_mm();
+    let size = options.size_mm.max(0.0);
+    let (page_w, page_h) = options
+        .page_mm
+        .unwrap_or((size + margin * 2.0, size + margin * 2.0));
+    let (left, top) = options
+        .position_mm
+        .unwrap_or(((page_w - size) / 2.0, (page_h - size) / 2.0));
+
+    // PDF 坐标以 pt 为单位，原点在左下角
+    let media = [0.0, 0.0, pt(page_w), pt(page_h)];
+    let trim = [
+        pt(left),
+        pt(page_h - top - size),
+        pt(left + size),
+        pt(page_h - top),
+    ];
+    let bleed = pt(options.bleed_mm.max(0.0));
+    let bleed_box = [
+        (trim[0] - bleed).max(media[0]),
+        (trim[1] - bleed).max(media[1]),
+        (trim[2] + bleed).min(media[2]),
+        (trim[3] + bleed).min(media[3]),
+    ];
+
+    let content = page_content(matrix, options, trim, bleed);
+    let (content, filter) = if options.compress {
+        (zlib_compress(content.as_bytes()), " /Filter /FlateDecode")
+    } else {
+        (content.into_bytes(), "")
+    };
+
+    let mut page = String::from("<< /Type /Page /Parent 2 0 R");
+    for (name, rect) in [
+        ("MediaBox", media),
+        ("BleedBox", bleed_box),
+        ("TrimBox", trim),
+    ] {
+        write!(page, " /{} [", name).unwrap();
+        for (i, &v) in rect.iter().enumerate() {
+            if i > 0 {
+                page.push(' ');
+            }
+            push_number(&mut page, v);
+        }
+        page.push(']');
+    }
+    page.push_str(" /Resources << >> /Contents 4 0 R >>");
+
+    let mut stream = format!("<< /Length {}{} >>\nstream\n", content.len(), filter).into_bytes();
+    stream.extend_from_slice(&content);
+    stream.extend_from_slice(b"\nendstream");
+
+    write_document(&[
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        page.into_bytes(),
+        stream,
+    ])
+}
+
+/// 页面内容流：背景、二维码轮廓和裁切标记
+fn page_content(matrix: &QrMatrix, options: &PdfOptions, trim: [f64; 4], bleed: f64) -> String {
+    let mut out = String::with_capacity(matrix.width() * matrix.height());
+
+    if let Some(light) = options.light {
+        light.push_operator(&mut out, false);
+        push_rect(
+            &mut out,
+            trim[0] - bleed,
+            trim[1] - bleed,
+            trim[2] - trim[0] + bleed * 2.0,
+            trim[3] - trim[1] + bleed * 2.0,
+        );
+        out.push_str("f\n");
+    }
+
+    let total = matrix.width() + options.quiet_zone as usize * 2;
+    let polygons = contour_polygons(matrix);
+    if total > 0 && !polygons.is_empty() {
+        // 路径以模块为单位，y 轴向下：变换到成品区域左上角并按模块边长缩放
+        let module = (trim[2] - trim[0]) / total as f64;
+        let quiet = options.quiet_zone as f64 * module;
+        out.push_str("q\n");
+        for v in [module, 0.0, 0.0, -module, trim[0] + quiet, trim[3] - quiet] {
+            push_number(&mut out, v);
+            out.push(' ');
+        }
+        out.push_str("cm\n");
+        options.dark.push_operator(&mut out, false);
+        for polygon in polygons {
+            for (i, &(x, y)) in polygon.iter().enumerate() {
+                writeln!(out, "{} {} {}", x, y, if i == 0 { 'm' } else { 'l' }).unwrap();
+            }
+            out.push_str("h\n");
+        }
+        out.push_str("f*\nQ\n");
+    }
+
+    if options.crop_marks {
+        let offset = pt(options.bleed_mm.max(0.0).max(CROP_MARK_OFFSET_MM));
+        let length = pt(CROP_MARK_LENGTH_MM);
+        options.dark.registration().push_operator(&mut out, true);
+        push_number(&mut out, CROP_MARK_WIDTH_PT);
+        out.push_str(" w\n");
+        for &x in &[trim[0], trim[2]] {
+            for &y in &[trim[1], trim[3]] {
+                // 从角点向外：水平标记在左右两侧，竖直标记在上下两侧
+                let sx = if x == trim[0] { -1.0 } else { 1.0 };
+                let sy = if y == trim[1] { -1.0 } else { 1.0 };
+                push_line(&mut out, x + sx * offset, y, x + sx * (offset + length), y);
+                push_line(&mut out, x, y + sy * offset, x, y + sy * (offset + length));
+            }
+        }
+        out.push_str("S\n");
+    }
+    out
+}
+
+/// 组装文件：头部、对象、交叉引用表和尾部
+fn write_document(objects: &[Vec<u8>]) -> Vec<u8> {
+    // 第二行的高位字节提示传输工具这是二进制文件
+    let mut pdf = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+        pdf.extend_from_slice(object);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+
+    let xref = pdf.len();
+    // 每个交叉引用条目正好 20 字节
+    let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        writeln!(table, "{:010} 00000 n ", offset).unwrap();
+    }
+    write!(
+        table,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref
+    )
+    .unwrap();
+    pdf.extend_from_slice(table.as_bytes());
+    pdf
+}
+
+/// 毫米转 pt
+fn pt(mm: f64) -> f64 {
+    mm / MM_PER_INCH * PT_PER_INCH
+}
+
+fn push_rect(out: &mut String, x: f64, y: f64, w: f64, h: f64) {
+    for v in [x, y, w, h] {
+        push_number(out, v);
+        out.push(' ');
+    }
+    out.push_str("re\n");
+}
+
+fn push_line(out: &mut String, x0: f64, y0: f64, x1: f64, y1: f64) {
+    push_number(out, x0);
+    out.push(' ');
+    push_number(out, y0);
+    out.push_str(" m ");
+    push_number(out, x1);
+    out.push(' ');
+    push_number(out, y1);
+    out.push_str(" l\n");
+}
+
+/// 最多 4 位小数，去掉末尾的 0（PDF 不接受指数形式）
+fn push_number(out: &mut String, v: f64) {
+    let text = format!("{:.4}", v);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    out.push_str(if text == "-0" { "0" } else { text });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qr_builder::QrBuilder;
+
+    fn text(pdf: &[u8]) -> String {
+        String::from_utf8_lossy(pdf).into_owned()
+    }
+
+    #[test]
+    fn test_numbers() {
+        let mut out = String::new();
+        for v in [1.0, 0.5, -0.00001, 85.03937, 1e-9] {
+            push_number(&mut out, v);
+            out.push(' ');
+        }
+        assert_eq!(out, "1 0.5 0 85.0394 0 ");
+        assert_eq!(pt(25.4), 72.0);
+    }
+
+    #[test]
+    fn test_structure_and_xref() {
+        let symbol = QrBuilder::new("Hello").build().unwrap();
+        let compressed = render_pdf(symbol.matrix(), &PdfOptions::default());
+        assert!(text(&compressed).contains("/Filter /FlateDecode"));
+
+        let options = PdfOptions {
+            compress: false,
+            ..Default::default()
+        };
+        let pdf = render_pdf(symbol.matrix(), &options);
+        let s = text(&pdf);
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        assert!(s.ends_with("%%EOF\n"));
+        assert!(pdf.len() > compressed.len());
+
+        // 交叉引用表中的字节偏移指向各对象（头部第二行不是 UTF-8，按字节比较）
+        let xref: usize = s
+            .rsplit("startxref\n")
+            .next()
+            .unwrap()
+            .lines()
+            .next()
+            .unwrap()
+            .parse()
+            .unwrap();
+        let table = std::str::from_utf8(&pdf[xref..]).unwrap();
+        assert!(table.starts_with("xref\n0 5\n"));
+        for (i, entry) in table.lines().skip(3).take(4).enumerate() {
+            assert_eq!(entry.len(), 19); // 加上换行共 20 字节
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj\n", i + 1).as_bytes()));
+        }
+
+        // 30 mm = 85.0394 pt，无出血时三个框相同
+        assert!(s.contains("/MediaBox [0 0 85.0394 85.0394] /BleedBox [0 0 85.0394 85.0394]"));
+    }
+
+    #[test]
+    fn test_content_paths() {
+        // 3×3 环：一个外轮廓加一个内轮廓，even-odd 填充
+        let mut m = QrMatrix::square(3);
+        for (x, y) in [
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (0, 1),
+            (2, 1),
+            (0, 2),
+            (1, 2),
+            (2, 2),
+        ] {
+            m.set(x, y, true);
+        }
+        let options = PdfOptions {
+            size_mm: 127.0,
+            quiet_zone: 1,
+            compress: false,
+            ..Default::default()
+        };
+        let s = text(&render_pdf(&m, &options));
+        // 5 个模块共 360 pt，每模块 72 pt，空白边 1 个模块
+        assert!(s.contains("1 g\n0 0 360 360 re\nf\n"));
+        assert!(s.contains(
+            "q\n72 0 0 -72 72 288 cm\n0 g\n0 0 m\n3 0 l\n3 3 l\n0 3 l\nh\n1 1 m\n1 2 l\n2 2 l\n2 1 l\nh\nf*\nQ\n"
+        ));
+        assert!(!s.contains(" S\n"));
+    }
+
+    #[test]
+    fn test_colour_spaces() {
+        let symbol = QrBuilder::new("Hello").build().unwrap();
+        for (dark, operator) in [
+            (PdfColor::Gray(0.2), "0.2 g\n"),
+            (PdfColor::from_rgb8([255, 0, 51]), "1 0 0.2 rg\n"),
+            (PdfColor::Cmyk(0.0, 0.5, 1.0, 0.1), "0 0.5 1 0.1 k\n"),
+        ] {
+            let options = PdfOptions {
+                dark,
+                light: None,
+                compress: false,
+                ..Default::default()
+            };
+            let s = text(&render_pdf(symbol.matrix(), &options));
+            assert!(s.contains(operator), "{operator}");
+            assert!(!s.contains(" re\n"));
+        }
+    }
+
+    #[test]
+    fn test_bleed_crop_marks_and_placement() {
+        let symbol = QrBuilder::new("Hello").build().unwrap();
+        let options = PdfOptions {
+            size_mm: 25.4,
+            dark: PdfColor::Cmyk(0.0, 0.0, 0.0, 1.0),
+            light: Some(PdfColor::Cmyk(0.0, 0.0, 0.0, 0.0)),
+            bleed_mm: 3.0,
+            crop_marks: true,
+            compress: false,
+            ..Default::default()
+        };
+        let s = text(&render_pdf(symbol.matrix(), &options));
+        // 成品 72 pt，四周 3 mm 出血 + 5 mm 标记 = 8 mm
+        let margin = pt(8.0);
+        let page = 72.0 + margin * 2.0;
+        let mut expected = String::new();
+        push_number(&mut expected, page);
+        assert!(s.contains(&format!("/MediaBox [0 0 {0} {0}]", expected)));
+        assert!(s.contains("/TrimBox [22.6772 22.6772 94.6772 94.6772]"));
+        assert!(s.contains("/BleedBox [14.1732 14.1732 103.1811 103.1811]"));
+        // 背景延伸到出血，标记用套准色
+        assert!(s.contains("14.1732 14.1732 89.0079 89.0079 re\n"));
+        assert!(s.contains("1 1 1 1 K\n0.25 w\n"));
+        assert_eq!(s.lines().filter(|line| line.contains(" m ")).count(), 8);
+        // 左上角的水平标记：距成品边 3 mm，长 5 mm
+        assert!(s.contains("14.1732 94.6772 m 0 94.6772 l\n"));
+
+        // 放在 A4 页面的指定位置
+        let options = PdfOptions {
+            size_mm: 20.0,
+            page_mm: Some((210.0, 297.0)),
+            position_mm: Some((10.0, 10.0)),
+            compress: false,
+            ..Default::default()
+        };
+        let s = text(&render_pdf(symbol.matrix(), &options));
+        assert!(s.contains("/MediaBox [0 0 595.2756 841.8898]"));
+        assert!(s.contains("/TrimBox [28.3465 756.8504 85.0394 813.5433]"));
+    }
+}
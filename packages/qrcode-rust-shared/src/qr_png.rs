@@ -0,0 +1,509 @@
+//! QR Code PNG - 无依赖的 PNG 编码
+//!
+//! 二维码只有两种颜色，1 位灰度或 1 位调色板就足够，文件通常只有几百字节。
+//! 压缩使用 zlib 固定霍夫曼块（LZ77 匹配重复的像素行），
+//! 压缩后反而更大时退回存储块。wasm 与不依赖 `image` 的构建都可以直接输出 PNG。
+
+use crate::qr_base64;
+use crate::qr_matrix::QrMatrix;
+use crate::qr_module_role::ModuleRole;
+use crate::qr_render::{render, RenderInfo, Renderer};
+
+/// PNG 像素格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PngFormat {
+    /// 1 位灰度：0 = 黑，1 = 白
+    Gray1,
+    /// 8 位灰度
+    Gray8,
+    /// 1 位调色板，下标 0 / 1 对应的 RGBA 颜色；透明度写入 tRNS 块
+    Palette1([[u8; 4]; 2]),
+}
+
+impl PngFormat {
+    /// 每行像素数据的字节数（不含过滤类型字节）
+    pub fn row_bytes(self, width: u32) -> usize {
+        match self {
+            PngFormat::Gray1 | PngFormat::Palette1(_) => (width as usize).div_ceil(8),
+            PngFormat::Gray8 => width as usize,
+        }
+    }
+
+    fn bit_depth(self) -> u8 {
+        match self {
+            PngFormat::Gray1 | PngFormat::Palette1(_) => 1,
+            PngFormat::Gray8 => 8,
+        }
+    }
+
+    fn color_type(self) -> u8 {
+        match self {
+            PngFormat::Gray1 | PngFormat::Gray8 => 0,
+            PngFormat::Palette1(_) => 3,
+        }
+    }
+}
+
+/// 渲染为 PNG：每个模块 `module_px` 像素，四周 `quiet_zone` 个模块的空白边
+///
+/// 不透明的纯黑白输出 1 位灰度，其他颜色输出 1 位调色板（下标 0 = 浅色，1 = 深色）。
+pub fn render_png(
+    matrix: &QrMatrix,
+    module_px: u32,
+    quiet_zone: u32,
+    dark: [u8; 4],
+    light: [u8; 4],
+) -> Vec<u8> {
+    render(
+        matrix,
+        quiet_zone as usize,
+        None,
+        PngRenderer::new(module_px, dark, light),
+    )
+}
+
+/// PNG 后端：逐行打包 1 位像素，每个模块行只生成一次再重复 `module_px` 次
+#[derive(Debug, Clone)]
+pub struct PngRenderer {
+    module_px: usize,
+    format: PngFormat,
+    width: u32,
+    height: u32,
+    line: Vec<u8>,
+    data: Vec<u8>,
+}
+
+impl PngRenderer {
+    /// 像素格式的选择同 [`render_png`]
+    pub fn new(module_px: u32, dark: [u8; 4], light: [u8; 4]) -> Self {
+        let format = if dark == [0, 0, 0, 255] && light == [255; 4] {
+            PngFormat::Gray1
+        } else {
+            PngFormat::Palette1([light, dark])
+        };
+        PngRenderer {
+            module_px: module_px as usize,
+            format,
+            width: 0,
+            height: 0,
+            line: Vec::new(),
+            data: Vec::new(),
+        }
+    }
+}
+
+impl Renderer for PngRenderer {
+    type Output = Vec<u8>;
+
+    fn begin(&mut self, info: &RenderInfo) {
+        self.width = (info.width * self.module_px) as u32;
+        self.height = (info.height * self.module_px) as u32;
+        let row_bytes = self.format.row_bytes(self.width);
+        self.line = vec![0; row_bytes];
+        self.data = Vec::with_capacity(row_bytes * self.height as usize);
+    }
+
+    fn draw_module(&mut self, x: usize, y: usize, dark: bool, role: Option<ModuleRole>) {
+        self.draw_run(x, y, 1, dark, role);
+    }
+
+    fn draw_run(&mut self, x: usize, _y: usize, len: usize, dark: bool, _role: Option<ModuleRole>) {
+        // 灰度中 1 为白色，调色板中 1 为深色
+        if dark == (self.format == PngFormat::Gray1) {
+            return;
+        }
+        for px in x * self.module_px..(x + len) * self.module_px {
+            self.line[px / 8] |= 0x80 >> (px % 8);
+        }
+    }
+
+    fn end_row(&mut self, _y: usize) {
+        for _ in 0..self.module_px {
+            self.data.extend_from_slice(&self.line);
+        }
+        self.line.fill(0);
+    }
+
+    fn finish(self) -> Vec<u8> {
+        encode_png(self.width, self.height, self.format, &self.data)
+    }
+}
+
+/// 把按 `format` 打包好的像素行（行优先、无过滤字节）编码为 PNG 文件
+pub fn encode_png(width: u32, height: u32, format: PngFormat, data: &[u8]) -> Vec<u8> {
+    let row_bytes = format.row_bytes(width);
+    assert_eq!(
+        data.len(),
+        row_bytes * height as usize,
+        "wrong pixel data size"
+    );
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    // 位深、颜色类型、压缩方法、过滤方法、隔行扫描
+    ihdr.extend_from_slice(&[format.bit_depth(), format.color_type(), 0, 0, 0]);
+    write_chunk(&mut png, b"IHDR", &ihdr);
+
+    if let PngFormat::Palette1(palette) = format {
+        let rgb: Vec<u8> = palette.iter().flat_map(|c| [c[0], c[1], c[2]]).collect();
+        write_chunk(&mut png, b"PLTE", &rgb);
+        if palette.iter().any(|c| c[3] != 255) {
+            write_chunk(&mut png, b"tRNS", &[palette[0][3], palette[1][3]]);
+        }
+    }
+
+    // 每行前加过滤类型 0（None）
+    let mut raw = Vec::with_capacity((row_bytes + 1) * height as usize);
+    for row in data.chunks_exact(row_bytes.max(1)).take(height as usize) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+    if row_bytes == 0 {
+        raw.resize(height as usize, 0);
+    }
+    write_chunk(&mut png, b"IDAT", &zlib_compress(&raw));
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+/// PNG 的 data URL（`data:image/png;base64,...`），可直接用作 `<img src>` 或下载链接
+pub fn png_data_url(png: &[u8]) -> String {
+    format!("data:image/png;base64,{}", qr_base64::encode(png))
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], body: &[u8]) {
+    png.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(body);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+// ============================================
+// CRC32 / Adler-32
+// ============================================
+
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 {
+                0xedb8_8320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+};
+
+/// CRC32（PNG 块校验）
+pub fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |c, &b| {
+        CRC_TABLE[((c ^ b as u32) & 0xff) as usize] ^ (c >> 8)
+    })
+}
+
+/// Adler-32（zlib 流校验）
+pub fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (mut a, mut b) = (1u32, 0u32);
+    // 5552 字节内累加不会溢出
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= MOD;
+        b %= MOD;
+    }
+    (b << 16) | a
+}
+
+// ============================================
+// zlib / DEFLATE
+// ============================================
+
+/// zlib 压缩：固定霍夫曼块，结果不小于存储块时改用存储块
+pub fn zlib_compress(data: &[u8]) -> Vec<u8> {
+    // CMF = 0x78（deflate，32K 窗口），FLG 使 (CMF << 8 | FLG) 为 31 的倍数
+    let mut out = vec![0x78, 0x01];
+    let fixed = deflate_fixed(data);
+    if fixed.len() < stored_len(data.len()) {
+        out.extend_from_slice(&fixed);
+    } else {
+        deflate_stored(data, &mut out);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+const MAX_STORED: usize = 65535;
+
+fn stored_len(len: usize) -> usize {
+    len + 5 * len.div_ceil(MAX_STORED).max(1)
+}
+
+fn deflate_stored(data: &[u8], out: &mut Vec<u8>) {
+    let mut blocks = data.chunks(MAX_STORED).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+        return;
+    }
+    while let Some(block) = blocks.next() {
+        // BFINAL + BTYPE = 00，随后按字节对齐
+        out.push(blocks.peek().is_none() as u8);
+        let len = block.len() as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+}
+
+const WINDOW: usize = 32768;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+const MAX_CHAIN: usize = 32;
+const HASH_BITS: u32 = 15;
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+/// 单个固定霍夫曼块，LZ77 用哈希链查找最长匹配
+fn deflate_fixed(data: &[u8]) -> Vec<u8> {
+    let mut out = BitWriter::default();
+    out.bits(1, 1); // BFINAL
+    out.bits(1, 2); // BTYPE = 01
+
+    let mut head = vec![usize::MAX; 1 << HASH_BITS];
+    let mut prev = vec![usize::MAX; data.len()];
+    let insert = |i: usize, head: &mut [usize], prev: &mut [usize]| {
+        if i + MIN_MATCH <= data.len() {
+            let h = hash(data, i);
+            prev[i] = head[h];
+            head[h] = i;
+        }
+    };
+
+    let mut i = 0;
+    while i < data.len() {
+        let (mut best_len, mut best_dist) = (0, 0);
+        if i + MIN_MATCH <= data.len() {
+            let max = (data.len() - i).min(MAX_MATCH);
+            let mut candidate = head[hash(data, i)];
+            let mut chain = MAX_CHAIN;
+            while candidate != usize::MAX && chain > 0 && i - candidate <= WINDOW {
+                let len = data[candidate..]
+                    .iter()
+                    .zip(&data[i..i + max])
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                if len > best_len {
+                    (best_len, best_dist) = (len, i - candidate);
+                    if len == max {
+                        break;
+                    }
+                }
+                candidate = prev[candidate];
+                chain -= 1;
+            }
+        }
+
+        if best_len >= MIN_MATCH {
+            out.length(best_len);
+            out.distance(best_dist);
+            for j in i..i + best_len {
+                insert(j, &mut head, &mut prev);
+            }
+            i += best_len;
+        } else {
+            out.symbol(data[i] as u16);
+            insert(i, &mut head, &mut prev);
+            i += 1;
+        }
+    }
+    out.symbol(256);
+    out.finish()
+}
+
+/// 前 3 个字节的哈希
+fn hash(data: &[u8], i: usize) -> usize {
+    let v = u32::from_le_bytes([data[i], data[i + 1], data[i + 2], 0]);
+    (v.wrapping_mul(0x9e37_79b1) >> (32 - HASH_BITS)) as usize
+}
+
+/// DEFLATE 位流：数据位低位在前，霍夫曼码高位在前
+#[derive(Default)]
+struct BitWriter {
+    out: Vec<u8>,
+    acc: u64,
+    count: u32,
+}
+
+impl BitWriter {
+    fn bits(&mut self, value: u32, count: u32) {
+        self.acc |= (value as u64) << self.count;
+        self.count += count;
+        while self.count >= 8 {
+            self.out.push(self.acc as u8);
+            self.acc >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    fn code(&mut self, code: u32, len: u32) {
+        self.bits(code.reverse_bits() >> (32 - len), len);
+    }
+
+    /// 固定霍夫曼表中的字面量 / 长度符号
+    fn symbol(&mut self, symbol: u16) {
+        let symbol = symbol as u32;
+        match symbol {
+            0..=143 => self.code(0x30 + symbol, 8),
+            144..=255 => self.code(0x190 + symbol - 144, 9),
+            256..=279 => self.code(symbol - 256, 7),
+            _ => self.code(0xc0 + symbol - 280, 8),
+        }
+    }
+
+    fn length(&mut self, len: usize) {
+        let index = LENGTH_BASE.partition_point(|&base| base as usize <= len) - 1;
+        self.symbol(257 + index as u16);
+        self.bits(
+            (len - LENGTH_BASE[index] as usize) as u32,
+            LENGTH_EXTRA[index] as u32,
+        );
+    }
+
+    fn distance(&mut self, dist: usize) {
+        let index = DIST_BASE.partition_point(|&base| base as usize <= dist) - 1;
+        self.code(index as u32, 5);
+        self.bits(
+            (dist - DIST_BASE[index] as usize) as u32,
+            DIST_EXTRA[index] as u32,
+        );
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.out.push(self.acc as u8);
+        }
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(png: &[u8]) -> (png::OutputInfo, Vec<u8>) {
+        let mut decoder = png::Decoder::new(png);
+        // 展开为 8 位灰度 / RGBA，便于逐像素比较
+        decoder.set_transformations(png::Transformations::EXPAND);
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        pixels.truncate(info.buffer_size());
+        (info, pixels)
+    }
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+        assert_eq!(adler32(&[0xff; 100_000]), {
+            let (a, b) = (0..100_000u64).fold((1u64, 0u64), |(a, b), _| {
+                ((a + 255) % 65521, (b + a + 255) % 65521)
+            });
+            ((b << 16) | a) as u32
+        });
+    }
+
+    #[test]
+    fn test_render_png_gray() {
+        let symbol = crate::qr_builder::QrBuilder::new("Hello PNG")
+            .build()
+            .unwrap();
+        let matrix = symbol.matrix();
+        let png = render_png(matrix, 3, 4, [0, 0, 0, 255], [255; 4]);
+        let (info, pixels) = decode(&png);
+
+        let size = (matrix.width() + 8) * 3;
+        assert_eq!((info.width, info.height), (size as u32, size as u32));
+        assert_eq!(info.color_type, png::ColorType::Grayscale);
+        for y in 0..size {
+            for x in 0..size {
+                let dark = matrix.get((x / 3).wrapping_sub(4), (y / 3).wrapping_sub(4));
+                assert_eq!(pixels[y * size + x], if dark { 0 } else { 255 });
+            }
+        }
+        // 重复的像素行几乎都被 LZ77 匹配掉
+        assert!(png.len() < 600, "{} bytes", png.len());
+    }
+
+    #[test]
+    fn test_render_png_palette_alpha() {
+        let symbol = crate::qr_builder::QrBuilder::new("alpha").build().unwrap();
+        let (dark, light) = ([0x1e, 0x40, 0xaf, 0xff], [0xff, 0xff, 0xff, 0x00]);
+        let (info, pixels) = decode(&render_png(symbol.matrix(), 2, 0, dark, light));
+
+        assert_eq!(info.color_type, png::ColorType::Rgba);
+        let size = info.width as usize;
+        let at = |x: usize, y: usize| &pixels[(y * size + x) * 4..(y * size + x) * 4 + 4];
+        assert_eq!(at(0, 0), dark);
+        assert_eq!(at(2, 2), light);
+        assert_eq!(at(1, size - 1), dark);
+        assert_eq!(at(size - 15, 2), light);
+    }
+
+    #[test]
+    fn test_encode_png_gray8_and_stored() {
+        // 伪随机数据压缩不了，走存储块
+        let mut seed = 0x1234_5678u32;
+        let noise: Vec<u8> = (0..300 * 250)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                (seed >> 16) as u8
+            })
+            .collect();
+        let png = encode_png(300, 250, PngFormat::Gray8, &noise);
+        let (info, pixels) = decode(&png);
+        assert_eq!((info.width, info.height), (300, 250));
+        assert_eq!(pixels, noise);
+
+        // 超过 65535 字节的存储块需要拆分
+        let raw = zlib_compress(&noise);
+        assert_eq!(raw.len(), 2 + stored_len(noise.len()) + 4);
+    }
+
+    #[test]
+    fn test_data_url() {
+        let png = encode_png(1, 1, PngFormat::Gray1, &[0x80]);
+        let url = png_data_url(&png);
+        assert!(url.starts_with("data:image/png;base64,iVBORw0KGgo"));
+        assert_eq!(decode(&png).1, [255]);
+    }
+}
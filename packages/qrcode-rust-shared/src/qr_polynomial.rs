@@ -92,6 +92,57 @@ impl Polynomial {
     }
 }
 
+/// GF(256) 乘法
+#[inline]
+fn gf_mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    QRMath::gexp(QRMath::glog(a as i32) + QRMath::glog(b as i32)) as u8
+}
+
+/// RS 生成多项式 g(x) = (x + α^0)(x + α^1)...(x + α^(ec_count-1))
+///
+/// 系数按降序排列，长度为 `ec_count + 1`，首项恒为 1。
+pub fn rs_generator(ec_count: usize) -> Vec<u8> {
+    let mut poly = Vec::with_capacity(ec_count + 1);
+    rs_generator_into(ec_count, &mut poly);
+    poly
+}
+
+/// 同 [`rs_generator`]，结果写入 `poly`（先清空，复用已有容量）
+pub fn rs_generator_into(ec_count: usize, poly: &mut Vec<u8>) {
+    poly.clear();
+    poly.push(1u8);
+    for i in 0..ec_count {
+        let root = QRMath::gexp(i as i32) as u8;
+        poly.push(0);
+        for j in (1..poly.len()).rev() {
+            poly[j] ^= gf_mul(poly[j - 1], root);
+        }
+    }
+}
+
+/// 计算 `data * x^n mod g(x)`，结果写入 `ec`（长度 n = `generator.len() - 1`）
+///
+/// 使用移位寄存器逐码字除法，不分配临时多项式。
+pub fn rs_remainder(data: &[u8], generator: &[u8], ec: &mut [u8]) {
+    debug_assert_eq!(ec.len() + 1, generator.len());
+    ec.fill(0);
+    for &d in data {
+        let factor = d ^ ec.first().copied().unwrap_or(0);
+        ec.copy_within(1.., 0);
+        if let Some(last) = ec.last_mut() {
+            *last = 0;
+        }
+        if factor != 0 {
+            for (e, &g) in ec.iter_mut().zip(&generator[1..]) {
+                *e ^= gf_mul(g, factor);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,4 +204,59 @@ mod tests {
         let result = p.r#mod_with_shift(&divisor, 4);
         assert!(result.len() <= 4);
     }
+
+    #[test]
+    fn test_rs_generator_matches_polynomial() {
+        let mut reused = Vec::new();
+        for ec_count in [7, 10, 30] {
+            let expected: Vec<u8> = Polynomial::generate_rs_poly(ec_count)
+                .num
+                .iter()
+                .map(|&c| c as u8)
+                .collect();
+            assert_eq!(rs_generator(ec_count as usize), expected);
+            rs_generator_into(ec_count as usize, &mut reused);
+            assert_eq!(reused, expected);
+        }
+    }
+
+    #[test]
+    fn test_rs_remainder_matches_polynomial() {
+        let data: Vec<u8> = (0..40u32).map(|i| (i * 37 + 11) as u8).collect();
+        for ec_count in [7usize, 18, 30] {
+            let generator = rs_generator(ec_count);
+            let mut ec = vec![0u8; ec_count];
+            rs_remainder(&data, &generator, &mut ec);
+
+            let mut raw: Vec<i32> = data.iter().map(|&d| d as i32).collect();
+            raw.extend(std::iter::repeat_n(0, ec_count));
+            let rem = Polynomial::new(raw, 0).r#mod(&Polynomial::generate_rs_poly(ec_count as i32));
+            let expected: Vec<u8> = (0..ec_count)
+                .map(|i| {
+                    let index = i as i32 + rem.len() as i32 - ec_count as i32;
+                    if index >= 0 {
+                        rem.get(index as usize) as u8
+                    } else {
+                        0
+                    }
+                })
+                .collect();
+            assert_eq!(ec, expected);
+        }
+    }
+
+    #[test]
+    fn test_rs_remainder_known_vector() {
+        // ISO/IEC 18004 附录 I："01234567" 1-M
+        let data = [
+            0x10, 0x20, 0x0C, 0x56, 0x61, 0x80, 0xEC, 0x11, 0xEC, 0x11, 0xEC, 0x11, 0xEC, 0x11,
+            0xEC, 0x11,
+        ];
+        let mut ec = [0u8; 10];
+        rs_remainder(&data, &rs_generator(10), &mut ec);
+        assert_eq!(
+            ec,
+            [0xA5, 0x24, 0xD4, 0xC1, 0xED, 0x36, 0xC7, 0x87, 0x2C, 0x55]
+        );
+    }
 }
@@ -0,0 +1,239 @@
+//! QR Code Print - 按物理尺寸规划输出
+//!
+//! 印刷需求通常写成"边长不超过 15 mm，打印机 300 dpi，模块不小于 0.33 mm"。
+//! [`QrBuilder::plan_print`] 在这些约束下选出能容纳数据的最高纠错级别及对应的最小版本，
+//! 并给出整数的每模块像素数，输出尺寸因此是精确的整数像素。
+
+use crate::qr_builder::QrBuilder;
+use crate::qr_code_model::EcLevel;
+use crate::qr_error::{QrError, QrResult};
+use crate::qr_pdf::{render_pdf, PdfOptions};
+use crate::qr_raster::render_rgba;
+use crate::qr_svg::render_svg;
+use crate::qr_symbol::QrSymbol;
+
+const MM_PER_INCH: f64 = 25.4;
+
+/// 物理尺寸约束
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrintSpec {
+    /// 可用的最大边长（毫米，含空白边）
+    pub size_mm: f64,
+    /// 输出分辨率
+    pub dpi: f64,
+    /// 模块的最小边长（毫米）
+    pub min_module_mm: f64,
+    /// 空白边宽度（模块数）
+    pub quiet_zone: u32,
+}
+
+impl PrintSpec {
+    /// 不限制最小模块尺寸、空白边为 4 个模块
+    pub fn new(size_mm: f64, dpi: f64) -> Self {
+        PrintSpec {
+            size_mm,
+            dpi,
+            min_module_mm: 0.0,
+            quiet_zone: 4,
+        }
+    }
+
+    /// 可用的像素边长
+    fn available_px(&self) -> u32 {
+        let px = self.size_mm / MM_PER_INCH * self.dpi;
+        if px.is_finite() && px > 0.0 {
+            px as u32
+        } else {
+            0
+        }
+    }
+
+    /// 满足最小模块尺寸的每模块像素数（至少 1）
+    fn min_module_px(&self) -> u32 {
+        let px = (self.min_module_mm / MM_PER_INCH * self.dpi).ceil();
+        if px.is_finite() && px > 1.0 {
+            px as u32
+        } else {
+            1
+        }
+    }
+}
+
+/// 规划结果
+#[derive(Debug, Clone)]
+pub struct PrintPlan {
+    /// 按选定的版本与纠错级别编码的符号
+    pub symbol: QrSymbol,
+    /// 每模块像素数
+    pub module_px: u32,
+    /// 空白边宽度（模块数）
+    pub quiet_zone: u32,
+    /// 输出边长（像素，含空白边）
+    pub size_px: u32,
+    pub dpi: f64,
+}
+
+impl PrintPlan {
+    /// 实际的模块边长（毫米）
+    pub fn module_mm(&self) -> f64 {
+        self.module_px as f64 / self.dpi * MM_PER_INCH
+    }
+
+    /// 实际的输出边长（毫米，含空白边）
+    pub fn size_mm(&self) -> f64 {
+        self.size_px as f64 / self.dpi * MM_PER_INCH
+    }
+
+    /// 按规划尺寸生成 SVG（含空白边，边长 `size_px`）
+    pub fn to_svg(&self, color_dark: &str, color_light: &str) -> String {
+        let padded = self
+            .symbol
+            .matrix()
+            .with_quiet_zone(self.quiet_zone as usize);
+        render_svg(&padded, self.size_px as i32, color_dark, color_light)
+    }
+
+    /// 按规划尺寸生成矢量 PDF（边长 `size_mm()`），颜色、页面、出血等取自 `options`
+    pub fn to_pdf(&self, options: &PdfOptions) -> Vec<u8> {
+        render_pdf(
+            self.symbol.matrix(),
+            &PdfOptions {
+                size_mm: self.size_mm(),
+                quiet_zone: self.quiet_zone,
+                ..options.clone()
+            },
+        )
+    }
+
+    /// 按规划尺寸生成 RGBA 像素（边长 `size_px`）
+    pub fn to_rgba(&self, dark: [u8; 4], light: [u8; 4]) -> Vec<u8> {
+        render_rgba(
+            self.symbol.matrix(),
+            self.module_px,
+            self.quiet_zone,
+            dark,
+            light,
+        )
+    }
+}
+
+impl QrBuilder {
+    /// 在物理尺寸约束下选择最高的纠错级别与最小版本并编码
+    ///
+    /// 忽略已设置的纠错级别与 `boost_ecl`；版本范围、掩码与 ECI 照常生效。
+    pub fn plan_print(&self, spec: &PrintSpec) -> QrResult<PrintPlan> {
+        let available_px = spec.available_px();
+        let min_module_px = spec.min_module_px();
+        let quiet_modules = spec.quiet_zone as i32 * 2;
+
+        let mut required_px = None;
+        let mut too_long = None;
+        for &level in EcLevel::ALL.iter().rev() {
+            let builder = self.clone().ec_level(level).boost_ecl(false);
+            let version = match builder.resolve() {
+                Ok((version, _)) => version,
+                Err(err @ QrError::DataTooLong { .. }) => {
+                    too_long = Some(err);
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+            let modules = (version.module_count() + quiet_modules) as u32;
+            let module_px = available_px / modules;
+            if module_px < min_module_px {
+                // 纠错级别越低版本越小，报错时给出最低级别所需的尺寸
+                required_px = Some(modules * min_module_px);
+                continue;
+            }
+
+            let symbol = builder.version_range(version..=version).build()?;
+            return Ok(PrintPlan {
+                symbol,
+                module_px,
+                quiet_zone: spec.quiet_zone,
+                size_px: modules * module_px,
+                dpi: spec.dpi,
+            });
+        }
+
+        Err(match required_px {
+            Some(required_px) => QrError::PrintSizeTooSmall {
+                available_px,
+                required_px,
+            },
+            // 任何纠错级别都放不下数据
+            None => too_long.expect("every EC level was rejected"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qr_code_model::QRErrorCorrectLevel;
+
+    #[test]
+    fn test_plan_print_15mm_300dpi() {
+        let spec = PrintSpec {
+            min_module_mm: 0.33,
+            ..PrintSpec::new(15.0, 300.0)
+        };
+        let plan = QrBuilder::new("https://example.com")
+            .plan_print(&spec)
+            .unwrap();
+
+        // 15 mm @ 300 dpi = 177 px，最小模块 ceil(3.9) = 4 px；
+        // H 需要版本 3（29 + 8 = 37 模块），37 × 4 = 148 px
+        assert_eq!(plan.symbol.ec_level(), QRErrorCorrectLevel::H);
+        assert_eq!(plan.symbol.version().get(), 3);
+        assert_eq!(plan.module_px, 4);
+        assert_eq!(plan.size_px, 148);
+        assert!(plan.size_mm() <= 15.0);
+        assert!(plan.module_mm() >= 0.33);
+
+        let svg = plan.to_svg("#000000", "#ffffff");
+        assert!(svg.contains(r#"width="148" height="148""#));
+        assert!(svg.contains("M16 16h4v4h-4z"));
+        assert_eq!(plan.to_rgba([0; 4], [255; 4]).len(), 148 * 148 * 4);
+
+        // 148 px @ 300 dpi = 35.52 pt
+        let pdf = plan.to_pdf(&PdfOptions::default());
+        assert!(String::from_utf8_lossy(&pdf).contains("/TrimBox [0 0 35.52 35.52]"));
+    }
+
+    #[test]
+    fn test_plan_print_lowers_ec_level() {
+        let spec = PrintSpec {
+            min_module_mm: 0.5,
+            quiet_zone: 2,
+            ..PrintSpec::new(20.0, 300.0)
+        };
+        // 236 px，最小模块 6 px → 最多 39 模块（版本 ≤ 4 且含边距）
+        let plan = QrBuilder::new(&"x".repeat(40)).plan_print(&spec).unwrap();
+        assert!(plan.symbol.version().get() <= 4);
+        assert!(plan.symbol.ec_level() != QRErrorCorrectLevel::H);
+        assert!(plan.module_px >= 6);
+    }
+
+    #[test]
+    fn test_plan_print_errors() {
+        let spec = PrintSpec {
+            min_module_mm: 1.0,
+            ..PrintSpec::new(10.0, 300.0)
+        };
+        let err = QrBuilder::new("Hello").plan_print(&spec).unwrap_err();
+        // 118 px；版本 1-L 需要 (21 + 8) × 12 = 348 px
+        assert_eq!(
+            err,
+            QrError::PrintSizeTooSmall {
+                available_px: 118,
+                required_px: 348,
+            }
+        );
+
+        let err = QrBuilder::new(&"x".repeat(3000))
+            .plan_print(&PrintSpec::new(1000.0, 300.0))
+            .unwrap_err();
+        assert!(matches!(err, QrError::DataTooLong { .. }));
+    }
+}
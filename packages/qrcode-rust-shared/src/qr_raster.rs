@@ -0,0 +1,669 @@
+//! QR Code Raster - 整数倍像素的位图渲染
+//!
+//! 每个模块占 `module_px × module_px` 个像素，没有缩放误差，适合打印。
+//! [`render_rgba_fit`] 则把二维码居中铺满任意尺寸的画布，模块边长可以是小数。
+//! [`render_into`] 直接写入调用方的缓冲区（视频帧、标签位图、帧缓冲），
+//! 支持任意行跨度、偏移和常见的像素格式。
+
+use crate::qr_error::{QrError, QrResult};
+use crate::qr_matrix::QrMatrix;
+use crate::qr_module_role::ModuleRole;
+use crate::qr_render::{render, RenderInfo, Renderer};
+
+/// 渲染为 RGBA8888 像素（行优先），边长 `(width + 2 * quiet_zone) * module_px`
+pub fn render_rgba(
+    matrix: &QrMatrix,
+    module_px: u32,
+    quiet_zone: u32,
+    dark: [u8; 4],
+    light: [u8; 4],
+) -> Vec<u8> {
+    render(
+        matrix,
+        quiet_zone as usize,
+        None,
+        RgbaRenderer::new(module_px, dark, light),
+    )
+}
+
+/// RGBA8888 位图后端：先铺满浅色，再按整数 `module_px` 填充深色模块
+#[derive(Debug, Clone)]
+pub struct RgbaRenderer {
+    module_px: usize,
+    canvas: Option<(usize, usize)>,
+    width: usize,
+    height: usize,
+    dark: [u8; 4],
+    light: [u8; 4],
+    pixels: Vec<u8>,
+}
+
+impl RgbaRenderer {
+    /// 画布边长为 模块数（含空白边）× `module_px`
+    pub fn new(module_px: u32, dark: [u8; 4], light: [u8; 4]) -> Self {
+        RgbaRenderer {
+            module_px: module_px as usize,
+            canvas: None,
+            width: 0,
+            height: 0,
+            dark,
+            light,
+            pixels: Vec::new(),
+        }
+    }
+
+    /// 固定画布尺寸，模块从左上角开始绘制，超出画布的部分被裁掉
+    pub fn with_canvas(
+        width: u32,
+        height: u32,
+        module_px: u32,
+        dark: [u8; 4],
+        light: [u8; 4],
+    ) -> Self {
+        RgbaRenderer {
+            canvas: Some((width as usize, height as usize)),
+            ..Self::new(module_px, dark, light)
+        }
+    }
+}
+
+impl Renderer for RgbaRenderer {
+    type Output = Vec<u8>;
+
+    fn begin(&mut self, info: &RenderInfo) {
+        (self.width, self.height) = self
+            .canvas
+            .unwrap_or((info.width * self.module_px, info.height * self.module_px));
+        self.pixels = self.light.repeat(self.width * self.height);
+    }
+
+    fn draw_module(&mut self, x: usize, y: usize, dark: bool, role: Option<ModuleRole>) {
+        self.draw_run(x, y, 1, dark, role);
+    }
+
+    fn draw_run(&mut self, x: usize, y: usize, len: usize, dark: bool, _role: Option<ModuleRole>) {
+        if !dark {
+            return;
+        }
+        let px = self.module_px;
+        let (left, right) = ((x * px).min(self.width), ((x + len) * px).min(self.width));
+        for row in (y * px).min(self.height)..((y + 1) * px).min(self.height) {
+            let line =
+                &mut self.pixels[(row * self.width + left) * 4..(row * self.width + right) * 4];
+            for pixel in line.chunks_exact_mut(4) {
+                pixel.copy_from_slice(&self.dark);
+            }
+        }
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.pixels
+    }
+}
+
+/// 模块边长不是整数像素时的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Scaling {
+    /// 模块边界取整到像素，余数分散到各个模块，只有深浅两种颜色
+    Snap,
+    /// 按像素被深色模块覆盖的面积混合颜色（抗锯齿）
+    #[default]
+    Antialias,
+}
+
+/// 把二维码（含空白边）居中绘制到 `width × height` 的 RGBA8888 画布上
+///
+/// 二维码边长等于画布较短的一边，模块边长为 `min(width, height) / (模块数 + 2 * quiet_zone)`，
+/// 可以是小数；画布其余部分填充浅色。模块边长为整数时两种 [`Scaling`] 的结果都与
+/// [`render_rgba`] 一致。
+pub fn render_rgba_fit(
+    matrix: &QrMatrix,
+    quiet_zone: u32,
+    width: u32,
+    height: u32,
+    scaling: Scaling,
+    dark: [u8; 4],
+    light: [u8; 4],
+) -> Vec<u8> {
+    let (width, height) = (width as usize, height as usize);
+    let quiet_zone = quiet_zone as usize;
+    // 空矩阵且没有空白边时无可绘制的模块，整张画布为浅色
+    if matrix.width().min(matrix.height()) + quiet_zone * 2 == 0 {
+        return light.repeat(width * height);
+    }
+    let side = width.min(height);
+    let columns = axis_weights(width, side, quiet_zone, matrix.width(), scaling);
+    let rows = axis_weights(height, side, quiet_zone, matrix.height(), scaling);
+
+    let mut pixels = Vec::with_capacity(width * height * 4);
+    for row in &rows {
+        for column in &columns {
+            let mut coverage = 0.0;
+            for &(y, wy) in row {
+                for &(x, wx) in column {
+                    if matrix.get(x, y) {
+                        coverage += wy * wx;
+                    }
+                }
+            }
+            pixels.extend_from_slice(&blend(light, dark, coverage));
+        }
+    }
+    pixels
+}
+
+/// 沿一个轴计算每个像素覆盖的矩阵模块及覆盖长度（只含矩阵内的模块，不含空白边）
+fn axis_weights(
+    pixels: usize,
+    side: usize,
+    quiet_zone: usize,
+    modules: usize,
+    scaling: Scaling,
+) -> Vec<Vec<(usize, f64)>> {
+    let total = modules + quiet_zone * 2;
+    let offset = (pixels - side) / 2;
+    let in_matrix = |m: usize| m.checked_sub(quiet_zone).filter(|&m| m < modules);
+
+    (0..pixels)
+        .map(|p| {
+            let Some(q) = p.checked_sub(offset).filter(|&q| q < side) else {
+                return Vec::new();
+            };
+            match scaling {
+                // 模块 m 占据像素 [m * side / total, (m + 1) * side / total)，都向下取整
+                Scaling::Snap => in_matrix(((q + 1) * total - 1) / side)
+                    .map(|m| vec![(m, 1.0)])
+                    .unwrap_or_default(),
+                Scaling::Antialias => {
+                    let cell = side as f64 / total as f64;
+                    let (lo, hi) = (q as f64, (q + 1) as f64);
+                    let first = (lo / cell).floor() as usize;
+                    let last = ((hi / cell).ceil() as usize).min(total);
+                    (first..last)
+                        .filter_map(|m| {
+                            let overlap = hi.min((m + 1) as f64 * cell) - lo.max(m as f64 * cell);
+                            in_matrix(m).filter(|_| overlap > 0.0).map(|m| (m, overlap))
+                        })
+                        .collect()
+                }
+            }
+        })
+        .collect()
+}
+
+/// 按深色覆盖率混合两种颜色（在预乘 alpha 空间中插值）
+fn blend(light: [u8; 4], dark: [u8; 4], coverage: f64) -> [u8; 4] {
+    if coverage <= 0.0 {
+        return light;
+    }
+    if coverage >= 1.0 {
+        return dark;
+    }
+    let (la, da) = (light[3] as f64 / 255.0, dark[3] as f64 / 255.0);
+    let alpha = la + (da - la) * coverage;
+    if alpha <= 0.0 {
+        return [0; 4];
+    }
+    let channel = |i: usize| {
+        ((light[i] as f64 * la * (1.0 - coverage) + dark[i] as f64 * da * coverage) / alpha).round()
+            as u8
+    };
+    [
+        channel(0),
+        channel(1),
+        channel(2),
+        (alpha * 255.0).round() as u8,
+    ]
+}
+
+/// 缓冲区的像素格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PixelFormat {
+    /// 8 位灰度
+    Gray8,
+    /// 1 位灰度（亮度 ≥ 128 为 1），每字节 8 个像素，最高位在左
+    Gray1Msb,
+    /// 1 位灰度，最低位在左
+    Gray1Lsb,
+    /// 16 位 RGB 5-6-5，小端
+    Rgb565,
+    Rgb888,
+    Rgba8888,
+    /// 字节顺序 B, G, R, A
+    Bgra8888,
+}
+
+impl PixelFormat {
+    /// 每像素位数
+    pub fn bits_per_pixel(self) -> usize {
+        match self {
+            PixelFormat::Gray1Msb | PixelFormat::Gray1Lsb => 1,
+            PixelFormat::Gray8 => 8,
+            PixelFormat::Rgb565 => 16,
+            PixelFormat::Rgb888 => 24,
+            PixelFormat::Rgba8888 | PixelFormat::Bgra8888 => 32,
+        }
+    }
+
+    /// `width` 个像素占用的字节数
+    pub fn row_bytes(self, width: usize) -> usize {
+        (width * self.bits_per_pixel()).div_ceil(8)
+    }
+
+    /// RGBA 颜色在此格式下的字节（1 位格式返回 0 或 1），只有前 `bits_per_pixel / 8` 个有效
+    pub fn encode(self, [r, g, b, a]: [u8; 4]) -> [u8; 4] {
+        let luma = ((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8;
+        match self {
+            PixelFormat::Gray8 => [luma, 0, 0, 0],
+            PixelFormat::Gray1Msb | PixelFormat::Gray1Lsb => [(luma >= 128) as u8, 0, 0, 0],
+            PixelFormat::Rgb565 => {
+                let v = (r as u16 >> 3) << 11 | (g as u16 >> 2) << 5 | b as u16 >> 3;
+                let [lo, hi] = v.to_le_bytes();
+                [lo, hi, 0, 0]
+            }
+            PixelFormat::Rgb888 => [r, g, b, 0],
+            PixelFormat::Rgba8888 => [r, g, b, a],
+            PixelFormat::Bgra8888 => [b, g, r, a],
+        }
+    }
+}
+
+/// [`render_into`] 的目标布局与颜色
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RasterOptions {
+    pub format: PixelFormat,
+    /// 每行字节数；0 表示按 `x` 偏移加二维码宽度紧密排列
+    pub stride: usize,
+    /// 二维码左上角（含空白边）在缓冲区中的像素坐标
+    pub x: usize,
+    pub y: usize,
+    /// 每个模块的边长（像素）
+    pub scale: usize,
+    /// 空白边宽度（模块数）
+    pub quiet_zone: usize,
+    pub dark: [u8; 4],
+    /// 浅色模块与空白边的颜色；`None` 时保留缓冲区原有内容，用于叠加到画面上
+    pub light: Option<[u8; 4]>,
+}
+
+impl Default for RasterOptions {
+    fn default() -> Self {
+        RasterOptions {
+            format: PixelFormat::Rgba8888,
+            stride: 0,
+            x: 0,
+            y: 0,
+            scale: 4,
+            quiet_zone: 4,
+            dark: [0, 0, 0, 255],
+            light: Some([255, 255, 255, 255]),
+        }
+    }
+}
+
+impl RasterOptions {
+    /// 二维码（含空白边）的像素边长
+    pub fn size_px(&self, matrix: &QrMatrix) -> usize {
+        (matrix.width() + self.quiet_zone * 2) * self.scale
+    }
+
+    /// 实际使用的行跨度
+    pub fn stride_for(&self, matrix: &QrMatrix) -> usize {
+        if self.stride == 0 {
+            self.format.row_bytes(self.x + self.size_px(matrix))
+        } else {
+            self.stride
+        }
+    }
+
+    /// 缓冲区至少需要的字节数
+    pub fn required_len(&self, matrix: &QrMatrix) -> usize {
+        let size = self.size_px(matrix);
+        if size == 0 {
+            return 0;
+        }
+        (self.y + size - 1) * self.stride_for(matrix) + self.format.row_bytes(self.x + size)
+    }
+}
+
+/// 把二维码绘制到调用方的像素缓冲区中，缓冲区其余部分保持不变
+///
+/// 行跨度或缓冲区长度不够时返回错误，不会写入任何像素。
+pub fn render_into(matrix: &QrMatrix, buffer: &mut [u8], options: &RasterOptions) -> QrResult<()> {
+    let stride = options.stride_for(matrix);
+    let row_bytes = options
+        .format
+        .row_bytes(options.x + options.size_px(matrix));
+    if stride < row_bytes {
+        return Err(QrError::StrideTooSmall {
+            stride,
+            required: row_bytes,
+        });
+    }
+    let required = options.required_len(matrix);
+    if buffer.len() < required {
+        return Err(QrError::BufferTooSmall {
+            len: buffer.len(),
+            required,
+        });
+    }
+
+    render(
+        matrix,
+        options.quiet_zone,
+        None,
+        BufferRenderer {
+            buffer,
+            stride,
+            options,
+            dark: options.format.encode(options.dark),
+            light: options.light.map(|light| options.format.encode(light)),
+        },
+    );
+    Ok(())
+}
+
+/// 写入调用方缓冲区的后端，尺寸已由 [`render_into`] 检查
+struct BufferRenderer<'a> {
+    buffer: &'a mut [u8],
+    stride: usize,
+    options: &'a RasterOptions,
+    dark: [u8; 4],
+    light: Option<[u8; 4]>,
+}
+
+impl Renderer for BufferRenderer<'_> {
+    type Output = ();
+
+    fn draw_module(&mut self, x: usize, y: usize, dark: bool, role: Option<ModuleRole>) {
+        self.draw_run(x, y, 1, dark, role);
+    }
+
+    fn draw_run(&mut self, x: usize, y: usize, len: usize, dark: bool, _role: Option<ModuleRole>) {
+        let Some(color) = (if dark { Some(self.dark) } else { self.light }) else {
+            return;
+        };
+        let RasterOptions { format, scale, .. } = *self.options;
+        let left = self.options.x + x * scale;
+        let right = left + len * scale;
+        let top = self.options.y + y * scale;
+
+        for row in top..top + scale {
+            let line = &mut self.buffer[row * self.stride..];
+            match format {
+                PixelFormat::Gray1Msb | PixelFormat::Gray1Lsb => {
+                    for px in left..right {
+                        let bit = match format {
+                            PixelFormat::Gray1Msb => 0x80 >> (px % 8),
+                            _ => 1 << (px % 8),
+                        };
+                        if color[0] == 1 {
+                            line[px / 8] |= bit;
+                        } else {
+                            line[px / 8] &= !bit;
+                        }
+                    }
+                }
+                _ => {
+                    let bytes = format.bits_per_pixel() / 8;
+                    for pixel in line[left * bytes..right * bytes].chunks_exact_mut(bytes) {
+                        pixel.copy_from_slice(&color[..bytes]);
+                    }
+                }
+            }
+        }
+    }
+
+    fn finish(self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_rgba() {
+        const B: [u8; 4] = [0, 0, 0, 255];
+        const W: [u8; 4] = [255, 255, 255, 255];
+        let mut m = QrMatrix::square(2);
+        m.set(1, 0, true);
+
+        let pixels = render_rgba(&m, 2, 1, B, W);
+        // (2 + 2) * 2 = 8 像素边长
+        assert_eq!(pixels.len(), 8 * 8 * 4);
+        let at = |x: usize, y: usize| &pixels[(y * 8 + x) * 4..(y * 8 + x) * 4 + 4];
+        assert_eq!(at(0, 0), W);
+        assert_eq!(at(4, 2), B);
+        assert_eq!(at(5, 3), B);
+        assert_eq!(at(6, 2), W);
+        assert_eq!(at(2, 2), W);
+        assert_eq!(at(4, 4), W);
+    }
+
+    #[test]
+    fn test_canvas_clipping() {
+        const B: [u8; 4] = [0, 0, 0, 255];
+        const W: [u8; 4] = [255, 255, 255, 255];
+        let mut m = QrMatrix::square(2);
+        m.set(1, 1, true);
+
+        // 2 个模块 × 3 像素 = 6，画布只有 5 × 4
+        let pixels = render(&m, 0, None, RgbaRenderer::with_canvas(5, 4, 3, B, W));
+        assert_eq!(pixels.len(), 5 * 4 * 4);
+        let at = |x: usize, y: usize| &pixels[(y * 5 + x) * 4..(y * 5 + x) * 4 + 4];
+        assert_eq!(at(3, 3), B);
+        assert_eq!(at(4, 3), B);
+        assert_eq!(at(2, 3), W);
+        assert_eq!(at(4, 2), W);
+    }
+
+    #[test]
+    fn test_fit_integral_matches_rgba() {
+        let symbol = crate::qr_builder::QrBuilder::new("Hello").build().unwrap();
+        let matrix = symbol.matrix();
+        let expected = render_rgba(matrix, 4, 2, [0, 0, 0, 255], [255; 4]);
+        for scaling in [Scaling::Snap, Scaling::Antialias] {
+            let fit = render_rgba_fit(matrix, 2, 100, 100, scaling, [0, 0, 0, 255], [255; 4]);
+            assert_eq!(fit, expected, "{scaling:?}");
+        }
+    }
+
+    #[test]
+    fn test_fit_fills_canvas() {
+        // 256 / 37 不是整数，整数模块边长会在右下留出 34 像素
+        let mut m = QrMatrix::square(37);
+        m.set(0, 0, true);
+        m.set(36, 36, true);
+        for scaling in [Scaling::Snap, Scaling::Antialias] {
+            let pixels = render_rgba_fit(&m, 0, 256, 256, scaling, [0, 0, 0, 255], [255; 4]);
+            let at = |x: usize, y: usize| &pixels[(y * 256 + x) * 4..][..4];
+            assert_eq!(at(0, 0), [0, 0, 0, 255]);
+            assert_eq!(at(255, 255), [0, 0, 0, 255]);
+            assert_eq!(at(128, 128), [255; 4]);
+        }
+
+        // 非正方形画布：二维码边长 200，水平居中
+        let pixels = render_rgba_fit(&m, 0, 300, 200, Scaling::Snap, [0, 0, 0, 255], [255; 4]);
+        let at = |x: usize, y: usize| &pixels[(y * 300 + x) * 4..][..4];
+        assert_eq!(pixels.len(), 300 * 200 * 4);
+        assert_eq!(at(49, 0), [255; 4]);
+        assert_eq!(at(50, 0), [0, 0, 0, 255]);
+        assert_eq!(at(249, 199), [0, 0, 0, 255]);
+        assert_eq!(at(250, 199), [255; 4]);
+    }
+
+    #[test]
+    fn test_fit_empty_matrix() {
+        // 0 个模块、0 空白边：不能按模块数换算像素，整张画布为浅色
+        let m = QrMatrix::square(0);
+        for scaling in [Scaling::Snap, Scaling::Antialias] {
+            let pixels = render_rgba_fit(&m, 0, 3, 2, scaling, [0, 0, 0, 255], [9, 8, 7, 6]);
+            assert_eq!(pixels, [9, 8, 7, 6].repeat(6), "{scaling:?}");
+        }
+    }
+
+    #[test]
+    fn test_fit_antialias_coverage() {
+        // 1 个深色模块加 1 个模块的空白边画在 4×4 上：模块边长 4/3，覆盖 [1.33, 2.67)
+        let mut m = QrMatrix::square(1);
+        m.set(0, 0, true);
+        let pixels = render_rgba_fit(&m, 1, 4, 4, Scaling::Antialias, [0, 0, 0, 255], [255; 4]);
+        let at = |x: usize, y: usize| &pixels[(y * 4 + x) * 4..][..4];
+        assert_eq!(at(0, 0), [255; 4]);
+        // 覆盖率 (2/3)²，255 × (1 - 4/9) ≈ 142
+        assert_eq!(at(1, 1), [142, 142, 142, 255]);
+        assert_eq!(at(2, 2), [142, 142, 142, 255]);
+        assert_eq!(at(1, 3), [255; 4]);
+
+        // 透明背景只改变 alpha
+        let pixels = render_rgba_fit(
+            &m,
+            1,
+            4,
+            4,
+            Scaling::Antialias,
+            [0, 0, 0, 255],
+            [255, 255, 255, 0],
+        );
+        assert_eq!(&pixels[(4 + 1) * 4..][..4], [0, 0, 0, 113]);
+
+        // Snap 只有两种颜色，模块占据像素 1..2
+        let pixels = render_rgba_fit(&m, 1, 4, 4, Scaling::Snap, [0, 0, 0, 255], [255; 4]);
+        let dark: Vec<usize> = (0..16).filter(|i| pixels[i * 4] == 0).collect();
+        assert_eq!(dark, [5]);
+    }
+
+    #[test]
+    fn test_pixel_formats() {
+        assert_eq!(
+            PixelFormat::Rgb565.encode([255, 0, 0, 255]),
+            [0x00, 0xF8, 0, 0]
+        );
+        assert_eq!(PixelFormat::Bgra8888.encode([1, 2, 3, 4]), [3, 2, 1, 4]);
+        assert_eq!(PixelFormat::Gray8.encode([255, 255, 255, 0])[0], 255);
+        assert_eq!(PixelFormat::Gray1Msb.encode([200, 200, 200, 255])[0], 1);
+        assert_eq!(PixelFormat::Gray1Lsb.encode([20, 20, 20, 255])[0], 0);
+        assert_eq!(PixelFormat::Gray1Msb.row_bytes(9), 2);
+        assert_eq!(PixelFormat::Rgb888.row_bytes(3), 9);
+    }
+
+    #[test]
+    fn test_render_into_matches_rgba() {
+        let symbol = crate::qr_builder::QrBuilder::new("Hello").build().unwrap();
+        let matrix = symbol.matrix();
+        let expected = render_rgba(matrix, 3, 2, [0, 0, 0, 255], [255; 4]);
+
+        let options = RasterOptions {
+            scale: 3,
+            quiet_zone: 2,
+            ..Default::default()
+        };
+        let mut buffer = vec![0u8; options.required_len(matrix)];
+        render_into(matrix, &mut buffer, &options).unwrap();
+        assert_eq!(buffer, expected);
+
+        // BGRA 与 RGB888 只是通道顺序和宽度不同
+        let mut bgra = vec![0u8; buffer.len()];
+        let bgra_options = RasterOptions {
+            format: PixelFormat::Bgra8888,
+            dark: [10, 20, 30, 255],
+            ..options.clone()
+        };
+        render_into(matrix, &mut bgra, &bgra_options).unwrap();
+        assert_eq!(&bgra[..4], [255; 4]);
+        let first_dark = (2 * 3 * 75 + 2 * 3) * 4;
+        assert_eq!(&bgra[first_dark..first_dark + 4], [30, 20, 10, 255]);
+    }
+
+    #[test]
+    fn test_render_into_offset_stride_and_bits() {
+        let mut m = QrMatrix::square(2);
+        m.set(0, 0, true);
+        m.set(1, 1, true);
+
+        // 偏移 (3, 1)，行跨度 2 字节，保留缓冲区中已有的位
+        let options = RasterOptions {
+            format: PixelFormat::Gray1Msb,
+            stride: 2,
+            x: 3,
+            y: 1,
+            scale: 2,
+            quiet_zone: 0,
+            dark: [255; 4],
+            light: None,
+        };
+        let mut buffer = [0b1000_0001u8; 10];
+        render_into(&m, &mut buffer, &options).unwrap();
+        assert_eq!(
+            buffer,
+            [
+                0b1000_0001,
+                0b1000_0001,
+                0b1001_1001,
+                0b1000_0001,
+                0b1001_1001,
+                0b1000_0001,
+                0b1000_0111,
+                0b1000_0001,
+                0b1000_0111,
+                0b1000_0001,
+            ]
+        );
+
+        // LSB 顺序，浅色写 0；每字节高 4 位在二维码之外，保持原值
+        let lsb = RasterOptions {
+            format: PixelFormat::Gray1Lsb,
+            stride: 0,
+            x: 0,
+            y: 0,
+            light: Some([0, 0, 0, 255]),
+            ..options.clone()
+        };
+        let mut buffer = [0xFFu8; 4];
+        render_into(&m, &mut buffer, &lsb).unwrap();
+        assert_eq!(buffer, [0xF3, 0xF3, 0xFC, 0xFC]);
+
+        // RGB565 小端
+        let rgb565 = RasterOptions {
+            format: PixelFormat::Rgb565,
+            scale: 1,
+            ..lsb.clone()
+        };
+        let mut buffer = [0u8; 8];
+        render_into(&m, &mut buffer, &rgb565).unwrap();
+        assert_eq!(buffer, [0xFF, 0xFF, 0, 0, 0, 0, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn test_render_into_errors() {
+        let m = QrMatrix::square(21);
+        let options = RasterOptions {
+            format: PixelFormat::Rgb888,
+            stride: 100,
+            scale: 2,
+            quiet_zone: 0,
+            ..Default::default()
+        };
+        let mut buffer = vec![0u8; 5_000];
+        assert_eq!(
+            render_into(&m, &mut buffer, &options),
+            Err(QrError::StrideTooSmall {
+                stride: 100,
+                required: 126
+            })
+        );
+        let options = RasterOptions {
+            stride: 0,
+            ..options
+        };
+        assert_eq!(options.required_len(&m), 42 * 126);
+        assert_eq!(
+            render_into(&m, &mut buffer, &options),
+            Err(QrError::BufferTooSmall {
+                len: 5_000,
+                required: 5292
+            })
+        );
+        assert!(buffer.iter().all(|&b| b == 0));
+    }
+}
@@ -0,0 +1,207 @@
+//! QR Code Render - 可插拔的渲染接口
+//!
+//! [`render`] 按行扫描矩阵（含空白边），把连续的同色模块交给 [`Renderer`]。
+//! 内置的 SVG、位图和 PNG 输出都实现了这个接口；新的输出格式（例如 Cairo、Skia 后端）
+//! 只需实现 `draw_module` 或 `draw_run`，空白边和角色表由 [`render`] 处理。
+
+use crate::qr_matrix::QrMatrix;
+use crate::qr_module_role::{ModuleRole, RoleMap};
+
+/// 渲染网格，坐标都以模块为单位并包含空白边
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderInfo {
+    /// 总列数（含两侧空白边）
+    pub width: usize,
+    /// 总行数（含两侧空白边）
+    pub height: usize,
+    /// 空白边宽度，矩阵的 (0, 0) 位于网格的 (quiet_zone, quiet_zone)
+    pub quiet_zone: usize,
+}
+
+/// 渲染后端
+///
+/// 调用顺序：`begin` 一次，然后逐行（从上到下、从左到右）调用 `draw_run`，
+/// 每行结束调用 `end_row`，最后 `finish` 返回输出。
+/// 深色和浅色模块都会绘制；在 `begin` 中铺满背景的后端可以忽略浅色模块。
+pub trait Renderer {
+    type Output;
+
+    /// 开始渲染
+    fn begin(&mut self, info: &RenderInfo) {
+        let _ = info;
+    }
+
+    /// 绘制 (x, y) 处的模块；空白边和未提供角色表时 `role` 为 `None`
+    fn draw_module(&mut self, x: usize, y: usize, dark: bool, role: Option<ModuleRole>);
+
+    /// 绘制第 `y` 行从 `x` 开始的 `len` 个颜色和角色都相同的模块，默认逐个调用 `draw_module`
+    fn draw_run(&mut self, x: usize, y: usize, len: usize, dark: bool, role: Option<ModuleRole>) {
+        for i in 0..len {
+            self.draw_module(x + i, y, dark, role);
+        }
+    }
+
+    /// 第 `y` 行绘制完毕
+    fn end_row(&mut self, y: usize) {
+        let _ = y;
+    }
+
+    /// 结束渲染并返回输出
+    fn finish(self) -> Self::Output;
+}
+
+/// 用 `renderer` 渲染矩阵，四周加 `quiet_zone` 个模块的空白边
+///
+/// 提供 `roles` 时，行程在角色变化处断开，每段都带上对应的 [`ModuleRole`]。
+pub fn render<R: Renderer>(
+    matrix: &QrMatrix,
+    quiet_zone: usize,
+    roles: Option<&RoleMap>,
+    mut renderer: R,
+) -> R::Output {
+    let info = RenderInfo {
+        width: matrix.width() + quiet_zone * 2,
+        height: matrix.height() + quiet_zone * 2,
+        quiet_zone,
+    };
+    let module = |x: usize, y: usize| {
+        let (Some(col), Some(row)) = (x.checked_sub(quiet_zone), y.checked_sub(quiet_zone)) else {
+            return (false, None);
+        };
+        let role = roles.and_then(|roles| roles.get(row as i32, col as i32));
+        (matrix.get(col, row), role)
+    };
+
+    renderer.begin(&info);
+    for y in 0..info.height {
+        let mut x = 0;
+        while x < info.width {
+            let (dark, role) = module(x, y);
+            let start = x;
+            x += 1;
+            while x < info.width && module(x, y) == (dark, role) {
+                x += 1;
+            }
+            renderer.draw_run(start, y, x - start, dark, role);
+        }
+        renderer.end_row(y);
+    }
+    renderer.finish()
+}
+
+/// 收集为 [`QrMatrix`]（含空白边），适合需要整幅矩阵的输出，也可用来检查其他后端
+#[derive(Debug, Clone, Default)]
+pub struct MatrixRenderer {
+    matrix: QrMatrix,
+}
+
+impl Renderer for MatrixRenderer {
+    type Output = QrMatrix;
+
+    fn begin(&mut self, info: &RenderInfo) {
+        self.matrix = QrMatrix::new(info.width, info.height);
+    }
+
+    fn draw_module(&mut self, x: usize, y: usize, dark: bool, _role: Option<ModuleRole>) {
+        if dark {
+            self.matrix.set(x, y, true);
+        }
+    }
+
+    fn finish(self) -> QrMatrix {
+        self.matrix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qr_builder::QrBuilder;
+
+    /// 记录所有调用
+    #[derive(Default)]
+    struct Log {
+        info: Option<RenderInfo>,
+        runs: Vec<(usize, usize, usize, bool, Option<ModuleRole>)>,
+        rows: Vec<usize>,
+    }
+
+    impl Renderer for Log {
+        type Output = Log;
+
+        fn begin(&mut self, info: &RenderInfo) {
+            self.info = Some(*info);
+        }
+
+        fn draw_module(&mut self, _: usize, _: usize, _: bool, _: Option<ModuleRole>) {
+            unreachable!("draw_run is overridden");
+        }
+
+        fn draw_run(
+            &mut self,
+            x: usize,
+            y: usize,
+            len: usize,
+            dark: bool,
+            role: Option<ModuleRole>,
+        ) {
+            self.runs.push((x, y, len, dark, role));
+        }
+
+        fn end_row(&mut self, y: usize) {
+            self.rows.push(y);
+        }
+
+        fn finish(self) -> Log {
+            self
+        }
+    }
+
+    #[test]
+    fn test_runs_and_quiet_zone() {
+        let mut m = QrMatrix::square(3);
+        m.set(1, 0, true);
+        m.set(2, 0, true);
+        let log = render(&m, 1, None, Log::default());
+
+        assert_eq!(
+            log.info,
+            Some(RenderInfo {
+                width: 5,
+                height: 5,
+                quiet_zone: 1
+            })
+        );
+        assert_eq!(log.rows, [0, 1, 2, 3, 4]);
+        assert_eq!(
+            &log.runs[..2],
+            [(0, 0, 5, false, None), (0, 1, 2, false, None)]
+        );
+        assert_eq!(log.runs[2], (2, 1, 2, true, None));
+        assert_eq!(log.runs[3], (4, 1, 1, false, None));
+    }
+
+    #[test]
+    fn test_roles_split_runs() {
+        let symbol = QrBuilder::new("Hello").build().unwrap();
+        let log = render(symbol.matrix(), 0, Some(symbol.roles()), Log::default());
+        // 第 0 行：7 个深色定位图案，1 个浅色分隔符，然后是格式信息
+        assert_eq!(log.runs[0], (0, 0, 7, true, Some(ModuleRole::Finder)));
+        assert_eq!(log.runs[1], (7, 0, 1, false, Some(ModuleRole::Separator)));
+        assert_eq!(log.runs[2].4, Some(ModuleRole::FormatInfo));
+
+        let total: usize = log.runs.iter().map(|run| run.2).sum();
+        assert_eq!(total, 21 * 21);
+    }
+
+    #[test]
+    fn test_matrix_renderer() {
+        let symbol = QrBuilder::new("Hello").build().unwrap();
+        let matrix = symbol.matrix();
+        assert_eq!(render(matrix, 0, None, MatrixRenderer::default()), *matrix);
+        assert_eq!(
+            render(matrix, 4, Some(symbol.roles()), MatrixRenderer::default()),
+            matrix.with_quiet_zone(4)
+        );
+    }
+}
@@ -180,12 +180,19 @@ const RS_BLOCK_TABLE: &[[i32; 6]] = &[
     [20, 45, 15, 61, 46, 16],
 ];
 
-pub fn get_rs_blocks(type_number: i32, error_correct_level: QRErrorCorrectLevel) -> Vec<QRRSBlock> {
-    let level_map = [1, 0, 3, 2];
-    let level_index = level_map[error_correct_level as usize];
-    let index = ((type_number - 1) * 4 + level_index) as usize;
+/// RS 块表中的一行：`[count, total_count, data_count, count2, total_count2, data_count2]`
+///
+/// 不分配内存，适合热路径；同一行内两组块的纠错码字数相同。
+pub fn rs_block_row(
+    type_number: i32,
+    error_correct_level: QRErrorCorrectLevel,
+) -> &'static [i32; 6] {
+    let index = ((type_number - 1) * 4 + error_correct_level.ordinal() as i32) as usize;
+    RS_BLOCK_TABLE.get(index).unwrap_or(&RS_BLOCK_TABLE[0])
+}
 
-    let rs_block = RS_BLOCK_TABLE.get(index).unwrap_or(&RS_BLOCK_TABLE[0]);
+pub fn get_rs_blocks(type_number: i32, error_correct_level: QRErrorCorrectLevel) -> Vec<QRRSBlock> {
+    let rs_block = rs_block_row(type_number, error_correct_level);
     let mut list = Vec::new();
 
     let length = rs_block.len() / 3;
@@ -0,0 +1,133 @@
+//! QR Code Segment - 数据段模型
+//!
+//! 一个符号的数据由若干段依次拼接：每段写入 4 位模式指示符，
+//! 随后是该模式的字符计数或参数，再是数据本身。
+
+use crate::qr_bit_buffer::BitBuffer;
+use crate::qr_code_model::QRMode;
+use crate::qr_util::get_length_in_bits;
+
+/// 数据段模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SegmentMode {
+    /// ECI 指派（扩展解释）
+    Eci,
+    /// 8 位字节
+    Byte,
+}
+
+impl SegmentMode {
+    /// 4 位模式指示符
+    pub fn indicator(self) -> u32 {
+        match self {
+            SegmentMode::Eci => QRMode::MODE_ECI as u32,
+            SegmentMode::Byte => QRMode::MODE_8BIT_BYTE as u32,
+        }
+    }
+}
+
+/// 数据段
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    /// ECI 指派号 (0-999999)
+    Eci(u32),
+    /// 原始字节
+    Byte(Vec<u8>),
+}
+
+impl Segment {
+    pub fn mode(&self) -> SegmentMode {
+        match self {
+            Segment::Eci(_) => SegmentMode::Eci,
+            Segment::Byte(_) => SegmentMode::Byte,
+        }
+    }
+
+    /// 字符计数字段的取值（ECI 段没有计数字段，返回 0）
+    pub fn char_count(&self) -> usize {
+        match self {
+            Segment::Eci(_) => 0,
+            Segment::Byte(data) => data.len(),
+        }
+    }
+
+    /// 指定版本下该段占用的总位数（含模式指示符）
+    pub fn bit_len(&self, version: i32) -> usize {
+        match self {
+            Segment::Eci(assignment) => 4 + eci_designator_bits(*assignment),
+            Segment::Byte(data) => {
+                4 + get_length_in_bits(QRMode::MODE_8BIT_BYTE, version) as usize + data.len() * 8
+            }
+        }
+    }
+
+    /// 把该段写入位缓冲区
+    pub fn write(&self, buffer: &mut BitBuffer, version: i32) {
+        buffer.put(self.mode().indicator(), 4);
+        match self {
+            Segment::Eci(assignment) => {
+                let bits = eci_designator_bits(*assignment);
+                let prefix = match bits {
+                    8 => 0,
+                    16 => 0b10 << 14,
+                    _ => 0b110 << 21,
+                };
+                buffer.put(prefix | *assignment, bits);
+            }
+            Segment::Byte(data) => {
+                buffer.put(
+                    data.len() as u32,
+                    get_length_in_bits(QRMode::MODE_8BIT_BYTE, version) as usize,
+                );
+                buffer.put_bytes(data);
+            }
+        }
+    }
+}
+
+/// ECI 指派号的编码位数（8 / 16 / 24）
+fn eci_designator_bits(assignment: u32) -> usize {
+    if assignment < 1 << 7 {
+        8
+    } else if assignment < 1 << 14 {
+        16
+    } else {
+        24
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_segment() {
+        let seg = Segment::Byte(b"Hi".to_vec());
+        assert_eq!(seg.mode(), SegmentMode::Byte);
+        assert_eq!(seg.char_count(), 2);
+        assert_eq!(seg.bit_len(1), 4 + 8 + 16);
+        assert_eq!(seg.bit_len(10), 4 + 16 + 16);
+
+        let mut buf = BitBuffer::new();
+        seg.write(&mut buf, 1);
+        assert_eq!(buf.len_bits(), 28);
+        assert_eq!(buf.as_bytes(), &[0x40, 0x24, 0x86, 0x90]);
+    }
+
+    #[test]
+    fn test_eci_segment() {
+        for (assignment, bits, expected) in [
+            (26, 12, vec![0x71, 0xA0]),
+            (1000, 20, vec![0x78, 0x3E, 0x80]),
+            (100_000, 28, vec![0x7C, 0x18, 0x6A, 0x00]),
+        ] {
+            let seg = Segment::Eci(assignment);
+            assert_eq!(seg.bit_len(1), bits);
+            let mut buf = BitBuffer::new();
+            seg.write(&mut buf, 1);
+            assert_eq!(buf.len_bits(), bits);
+            assert_eq!(buf.as_bytes(), expected.as_slice());
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! QR Code Serde - 序列化支持（`serde` 特性）
+//!
+//! [`QrSymbol`] 序列化为紧凑结构：
+//!
+//! ```json
+//! {"version":1,"ec_level":"H","mask":3,"modules":"<base64>"}
+//! ```
+//!
+//! `modules` 为按行优先顺序连续打包的模块位（见 [`QrMatrix::to_bytes`]）。
+//! 反序列化时校验尺寸与取值范围，并重新计算模块角色，
+//! 因此还原后的符号与原符号完全相等，渲染结果逐字节一致。
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::qr_base64;
+use crate::qr_builder::symbol_roles;
+use crate::qr_code_model::QRErrorCorrectLevel;
+use crate::qr_mask::Mask;
+use crate::qr_matrix::QrMatrix;
+use crate::qr_symbol::QrSymbol;
+use crate::qr_version::Version;
+
+#[derive(Serialize, Deserialize)]
+struct SymbolRepr {
+    version: Version,
+    ec_level: QRErrorCorrectLevel,
+    mask: Mask,
+    modules: String,
+}
+
+impl Serialize for QrSymbol {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SymbolRepr {
+            version: self.version,
+            ec_level: self.ec_level,
+            mask: self.mask,
+            modules: qr_base64::encode(&self.matrix.to_bytes()),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for QrSymbol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = SymbolRepr::deserialize(deserializer)?;
+        let size = repr.version.module_count() as usize;
+        let bytes = qr_base64::decode(&repr.modules)
+            .ok_or_else(|| D::Error::custom("modules is not valid base64"))?;
+        let matrix = QrMatrix::from_bytes(size, size, &bytes).ok_or_else(|| {
+            D::Error::custom(format_args!(
+                "modules has {} bytes, expected {} for version {}",
+                bytes.len(),
+                (size * size).div_ceil(8),
+                repr.version
+            ))
+        })?;
+
+        Ok(QrSymbol {
+            version: repr.version,
+            ec_level: repr.ec_level,
+            mask: repr.mask,
+            matrix,
+            roles: symbol_roles(repr.version, repr.ec_level),
+        })
+    }
+}
+
+impl QrSymbol {
+    /// 序列化为 JSON 字符串
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    /// 从 [`to_json`](Self::to_json) 的输出还原
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::qr_builder::QrBuilder;
+    use crate::qr_svg::render_svg;
+
+    use super::*;
+
+    #[test]
+    fn test_json_round_trip() {
+        for text in ["Hello World", &"x".repeat(200)] {
+            let symbol = QrBuilder::new(text)
+                .ec_level(QRErrorCorrectLevel::Q)
+                .build()
+                .unwrap();
+            let json = symbol.to_json().unwrap();
+            let restored = QrSymbol::from_json(&json).unwrap();
+            assert_eq!(restored, symbol);
+            assert_eq!(
+                render_svg(restored.matrix(), 256, "#000000", "#ffffff"),
+                render_svg(symbol.matrix(), 256, "#000000", "#ffffff")
+            );
+        }
+    }
+
+    #[test]
+    fn test_json_layout() {
+        let symbol = QrBuilder::new("a")
+            .mask(Mask::new(2).unwrap())
+            .build()
+            .unwrap();
+        let json = symbol.to_json().unwrap();
+        assert!(json.starts_with(r#"{"version":1,"ec_level":"H","mask":2,"modules":""#));
+    }
+
+    #[test]
+    fn test_rejects_invalid() {
+        let symbol = QrBuilder::new("a").build().unwrap();
+        let json = symbol.to_json().unwrap();
+
+        let bad_version = json.replace(r#""version":1"#, r#""version":2"#);
+        assert!(QrSymbol::from_json(&bad_version).is_err());
+        let bad_mask = json.replace(&format!(r#""mask":{}"#, symbol.mask()), r#""mask":8"#);
+        assert!(QrSymbol::from_json(&bad_mask).is_err());
+        let bad_level = json.replace(r#""ec_level":"H""#, r#""ec_level":"X""#);
+        assert!(QrSymbol::from_json(&bad_level).is_err());
+    }
+}
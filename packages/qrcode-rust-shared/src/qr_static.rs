@@ -0,0 +1,118 @@
+//! QR Code Static - 编译期生成的二维码
+//!
+//! `qrcode-macros` 的 `qr!` 在编译期完成编码，展开为 [`StaticQrMatrix`] 常量表达式：
+//!
+//! ```ignore
+//! static HOME: StaticQrMatrix = qr!("https://example.com", ec = H);
+//! ```
+//!
+//! 运行时只读取打包好的模块数据，固定内容不需要链接任何编码代码。
+
+use crate::qr_code_model::EcLevel;
+use crate::qr_mask::{Mask, MASK_COUNT};
+use crate::qr_matrix::QrMatrix;
+use crate::qr_version::Version;
+
+/// 编译期编码的二维码，模块按 [`QrMatrix::to_bytes`] 的格式打包
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StaticQrMatrix {
+    version: Version,
+    ec_level: EcLevel,
+    mask: Mask,
+    bits: &'static [u8],
+}
+
+impl StaticQrMatrix {
+    /// 由宏生成的代码调用；参数不合法时在编译期报错
+    #[doc(hidden)]
+    pub const fn from_raw(version: u8, ec_level: EcLevel, mask: u8, bits: &'static [u8]) -> Self {
+        assert!(version >= 1 && version <= 40, "version out of range");
+        assert!(mask < MASK_COUNT, "mask out of range");
+        let count = version as usize * 4 + 17;
+        assert!(
+            bits.len() == (count * count).div_ceil(8),
+            "wrong matrix size"
+        );
+        StaticQrMatrix {
+            version: Version(version),
+            ec_level,
+            mask: Mask(mask),
+            bits,
+        }
+    }
+
+    /// 每边模块数
+    pub const fn module_count(&self) -> i32 {
+        self.version.0 as i32 * 4 + 17
+    }
+
+    /// 读取 (row, col) 处的模块，越界返回 `false`
+    pub const fn is_dark(&self, row: i32, col: i32) -> bool {
+        let count = self.module_count();
+        if row < 0 || row >= count || col < 0 || col >= count {
+            return false;
+        }
+        let i = (row * count + col) as usize;
+        self.bits[i / 8] & (0x80 >> (i % 8)) != 0
+    }
+
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    pub fn ec_level(&self) -> EcLevel {
+        self.ec_level
+    }
+
+    pub fn mask(&self) -> Mask {
+        self.mask
+    }
+
+    /// 打包后的模块数据（行优先，高位在前）
+    pub const fn as_bytes(&self) -> &'static [u8] {
+        self.bits
+    }
+
+    /// 转换为共享的位压缩矩阵
+    pub fn to_matrix(&self) -> QrMatrix {
+        let count = self.module_count() as usize;
+        QrMatrix::from_bytes(count, count, self.bits).expect("size checked in from_raw")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qr_builder::QrBuilder;
+
+    #[test]
+    fn test_static_matrix_round_trip() {
+        let symbol = QrBuilder::new("Hello")
+            .ec_level(EcLevel::M)
+            .build()
+            .unwrap();
+        // 与宏展开的形式相同：字节切片提升为 'static
+        let bytes: &'static [u8] = symbol.matrix().to_bytes().leak();
+        let qr = StaticQrMatrix::from_raw(
+            symbol.version().get() as u8,
+            EcLevel::M,
+            symbol.mask().get(),
+            bytes,
+        );
+
+        assert_eq!(qr.module_count(), symbol.module_count());
+        assert_eq!(qr.mask(), symbol.mask());
+        assert_eq!(qr.to_matrix(), *symbol.matrix());
+        for row in -1..=qr.module_count() {
+            for col in -1..=qr.module_count() {
+                assert_eq!(qr.is_dark(row, col), symbol.is_dark(row, col));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "wrong matrix size")]
+    fn test_from_raw_checks_size() {
+        StaticQrMatrix::from_raw(1, EcLevel::L, 0, &[0; 10]);
+    }
+}
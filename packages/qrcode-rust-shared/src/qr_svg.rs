@@ -0,0 +1,654 @@
+//! QR Code SVG 渲染
+//!
+//! 只依赖 [`QrMatrix`]，因此任何实现生成的矩阵都可以用同一个渲染器输出。
+//! [`render_svg`] 按整数像素绘制；[`SvgOptions`] 以模块为 viewBox 单位，可无损缩放。
+
+use core::fmt::Write;
+
+use crate::qr_matrix::QrMatrix;
+use crate::qr_module_role::ModuleRole;
+use crate::qr_render::{render, RenderInfo, Renderer};
+
+/// 深色模块的路径写法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SvgPathMode {
+    /// 每个深色模块一个方块子路径
+    #[default]
+    Modules,
+    /// 每行连续的深色模块合并为一个矩形，生成最快
+    Runs,
+    /// 描出相连深色区域的轮廓多边形，孔洞靠 even-odd 填充；
+    /// 体积最小，相邻模块之间也不会出现抗锯齿接缝
+    Contours,
+}
+
+/// 生成 SVG 字符串（单个 Path 合并所有深色模块）
+///
+/// 模块按整数 `cell_size = size / count` 绘制并居中。
+pub fn render_svg(matrix: &QrMatrix, size: i32, color_dark: &str, color_light: &str) -> String {
+    render_svg_with(matrix, size, color_dark, color_light, SvgPathMode::Modules)
+}
+
+/// 按 `mode` 生成 SVG 字符串，尺寸与居中方式同 [`render_svg`]
+pub fn render_svg_with(
+    matrix: &QrMatrix,
+    size: i32,
+    color_dark: &str,
+    color_light: &str,
+    mode: SvgPathMode,
+) -> String {
+    let count = matrix.width() as i32;
+    if count == 0 {
+        return String::new();
+    }
+
+    let cell_size = size / count;
+    let offset = (size - cell_size * count) / 2;
+
+    // 每个深色模块的路径数据：方块约 20-25 字节，合并后远少于此
+    let per_module = match mode {
+        SvgPathMode::Modules => 25,
+        SvgPathMode::Runs => 12,
+        SvgPathMode::Contours => 8,
+    };
+    let mut svg = String::with_capacity(200 + matrix.count_dark() * per_module);
+
+    svg.push_str(r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 "#);
+    push_i32(&mut svg, size);
+    svg.push(' ');
+    push_i32(&mut svg, size);
+    svg.push_str(r#"" width=""#);
+    push_i32(&mut svg, size);
+    svg.push_str(r#"" height=""#);
+    push_i32(&mut svg, size);
+    svg.push_str(r#""><path d="M0 0h"#);
+    push_i32(&mut svg, size);
+    svg.push('v');
+    push_i32(&mut svg, size);
+    svg.push_str(r#"H0z" fill=""#);
+    svg.push_str(color_light);
+    svg.push_str(r#""/><path fill=""#);
+    svg.push_str(color_dark);
+    if mode == SvgPathMode::Contours {
+        svg.push_str(r#"" fill-rule="evenodd"#);
+    }
+    svg.push_str(r#"" d=""#);
+
+    svg.push_str(&render(
+        matrix,
+        0,
+        None,
+        SvgPathRenderer::new(mode, cell_size, offset),
+    ));
+
+    svg.push_str(r#""/></svg>"#);
+    svg
+}
+
+/// 输出尺寸（SVG `width` / `height` 属性，单位见 [`SvgOptions::unit`]）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SvgSize {
+    /// 每个模块的边长，总尺寸 = 模块数（含空白边）× 边长
+    Module(f64),
+    /// 总边长（含空白边）
+    Total(f64),
+}
+
+/// 可配置的 SVG 输出
+///
+/// 路径坐标以模块为单位（viewBox 为 `0 0 n n`，n 含空白边），
+/// 缩放只改变 `width` / `height`，不会像整数像素那样留下不均匀的边距。
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgOptions {
+    pub size: SvgSize,
+    /// `width` / `height` 的单位，例如 `"mm"`；空字符串表示用户单位（像素）
+    pub unit: String,
+    /// 空白边宽度（模块数）
+    pub quiet_zone: usize,
+    pub color_dark: String,
+    pub color_light: String,
+    pub path_mode: SvgPathMode,
+    /// 添加 `shape-rendering="crispEdges"`，关闭模块边缘的抗锯齿
+    pub crisp_edges: bool,
+    /// 无障碍标题：输出 `<title>`，文档模式下同时加上 `role="img"` 与 `aria-label`
+    pub title: Option<String>,
+    /// 只输出 `<g>` 片段，用于嵌入更大的 SVG 文档
+    ///
+    /// 片段内坐标仍以模块为单位，由外层的 `transform` 负责定位和缩放，
+    /// 此时忽略 `size` 与 `unit`。
+    pub fragment: bool,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            size: SvgSize::Total(256.0),
+            unit: String::new(),
+            quiet_zone: 4,
+            color_dark: String::from("#000000"),
+            color_light: String::from("#ffffff"),
+            path_mode: SvgPathMode::Contours,
+            crisp_edges: true,
+            title: None,
+            fragment: false,
+        }
+    }
+}
+
+/// 按 [`SvgOptions`] 生成 SVG 文档或 `<g>` 片段
+pub fn render_svg_with_options(matrix: &QrMatrix, options: &SvgOptions) -> String {
+    if matrix.is_empty() {
+        return String::new();
+    }
+
+    let qz = options.quiet_zone;
+    let count = (matrix.width() + qz * 2) as i32;
+    let mut svg = String::with_capacity(300 + matrix.count_dark() * 8);
+
+    if options.fragment {
+        svg.push_str("<g");
+    } else {
+        let total = match options.size {
+            SvgSize::Module(module) => module * count as f64,
+            SvgSize::Total(total) => total,
+        };
+        let _ = write!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {count} {count}" width="{total}{unit}" height="{total}{unit}""#,
+            unit = options.unit,
+        );
+        if let Some(title) = &options.title {
+            svg.push_str(r#" role="img" aria-label=""#);
+            push_escaped(&mut svg, title);
+            svg.push('"');
+        }
+    }
+    if options.crisp_edges {
+        svg.push_str(r#" shape-rendering="crispEdges""#);
+    }
+    svg.push('>');
+    if let Some(title) = &options.title {
+        svg.push_str("<title>");
+        push_escaped(&mut svg, title);
+        svg.push_str("</title>");
+    }
+
+    svg.push_str(r#"<path d="M0 0h"#);
+    push_i32(&mut svg, count);
+    svg.push('v');
+    push_i32(&mut svg, count);
+    svg.push_str(r#"H0z" fill=""#);
+    push_escaped(&mut svg, &options.color_light);
+    svg.push_str(r#""/><path fill=""#);
+    push_escaped(&mut svg, &options.color_dark);
+    if options.path_mode == SvgPathMode::Contours {
+        svg.push_str(r#"" fill-rule="evenodd"#);
+    }
+    svg.push_str(r#"" d=""#);
+    svg.push_str(&render(
+        matrix,
+        qz,
+        None,
+        SvgPathRenderer::new(options.path_mode, 1, 0),
+    ));
+    svg.push_str(r#""/>"#);
+
+    svg.push_str(if options.fragment { "</g>" } else { "</svg>" });
+    svg
+}
+
+/// 转义 XML 文本与属性值中的特殊字符
+fn push_escaped(svg: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => svg.push_str("&amp;"),
+            '<' => svg.push_str("&lt;"),
+            '>' => svg.push_str("&gt;"),
+            '"' => svg.push_str("&quot;"),
+            _ => svg.push(c),
+        }
+    }
+}
+
+/// SVG 路径数据（`<path d="...">` 的内容）后端
+///
+/// 网格坐标 (x, y) 绘制在 `(x * cell_size + offset, y * cell_size + offset)`。
+/// `Contours` 需要整幅网格，先收集深色模块，在 `finish` 中描边。
+#[derive(Debug, Clone)]
+pub struct SvgPathRenderer {
+    mode: SvgPathMode,
+    cell_size: i32,
+    offset: i32,
+    grid: QrMatrix,
+    d: String,
+}
+
+impl SvgPathRenderer {
+    pub fn new(mode: SvgPathMode, cell_size: i32, offset: i32) -> Self {
+        SvgPathRenderer {
+            mode,
+            cell_size,
+            offset,
+            grid: QrMatrix::default(),
+            d: String::new(),
+        }
+    }
+}
+
+impl Renderer for SvgPathRenderer {
+    type Output = String;
+
+    fn begin(&mut self, info: &RenderInfo) {
+        if self.mode == SvgPathMode::Contours {
+            self.grid = QrMatrix::new(info.width, info.height);
+        }
+    }
+
+    fn draw_module(&mut self, x: usize, y: usize, dark: bool, role: Option<ModuleRole>) {
+        self.draw_run(x, y, 1, dark, role);
+    }
+
+    fn draw_run(&mut self, x: usize, y: usize, len: usize, dark: bool, _role: Option<ModuleRole>) {
+        if !dark {
+            return;
+        }
+        let (cell_size, offset) = (self.cell_size, self.offset);
+        match self.mode {
+            SvgPathMode::Modules => {
+                for x in x..x + len {
+                    push_rect(&mut self.d, x, y, 1, cell_size, offset);
+                }
+            }
+            SvgPathMode::Runs => push_rect(&mut self.d, x, y, len, cell_size, offset),
+            SvgPathMode::Contours => {
+                for x in x..x + len {
+                    self.grid.set(x, y, true);
+                }
+            }
+        }
+    }
+
+    fn finish(mut self) -> String {
+        if self.mode == SvgPathMode::Contours {
+            push_contour_paths(&mut self.d, &self.grid, self.cell_size, self.offset);
+        }
+        self.d
+    }
+}
+
+/// 每个深色模块一个 `<rect>` 的后端，坐标换算同 [`SvgPathRenderer`]
+#[derive(Debug, Clone)]
+pub struct SvgRectRenderer {
+    cell_size: i32,
+    offset: i32,
+    color_dark: String,
+    out: String,
+}
+
+impl SvgRectRenderer {
+    pub fn new(cell_size: i32, offset: i32, color_dark: &str) -> Self {
+        SvgRectRenderer {
+            cell_size,
+            offset,
+            color_dark: color_dark.to_string(),
+            out: String::new(),
+        }
+    }
+}
+
+impl Renderer for SvgRectRenderer {
+    type Output = String;
+
+    fn draw_module(&mut self, x: usize, y: usize, dark: bool, _role: Option<ModuleRole>) {
+        if dark {
+            let _ = write!(
+                self.out,
+                r#"<rect x="{}" y="{}" width="{2}" height="{2}" fill="{3}"/>"#,
+                x as i32 * self.cell_size + self.offset,
+                y as i32 * self.cell_size + self.offset,
+                self.cell_size,
+                self.color_dark
+            );
+        }
+    }
+
+    fn finish(self) -> String {
+        self.out
+    }
+}
+
+/// `len` 个模块宽、一个模块高的矩形子路径
+fn push_rect(svg: &mut String, x: usize, y: usize, len: usize, cell_size: i32, offset: i32) {
+    let w = len as i32 * cell_size;
+    svg.push('M');
+    push_i32(svg, x as i32 * cell_size + offset);
+    svg.push(' ');
+    push_i32(svg, y as i32 * cell_size + offset);
+    svg.push('h');
+    push_i32(svg, w);
+    svg.push('v');
+    push_i32(svg, cell_size);
+    svg.push_str("h-");
+    push_i32(svg, w);
+    svg.push('z');
+}
+
+// 网格顶点上出边的方向
+const EAST: u8 = 1;
+const SOUTH: u8 = 2;
+const WEST: u8 = 4;
+const NORTH: u8 = 8;
+
+/// 描出深色区域的边界，返回每个闭合多边形的转角顶点（网格坐标）
+///
+/// 深色与浅色模块之间的每条单位边都是一条有向边，方向取深色在右侧，
+/// 这样每个网格顶点的入边数等于出边数，沿出边一定能走回起点。
+/// 鞍点（两个深色模块对角相接）优先右转，对角相接的区域各自成为独立的多边形。
+/// 共线的单位边合并为一条边；第一个顶点是路径起点，最后一条边回到它。
+/// SVG 与 PDF 输出共用这份几何。
+pub(crate) fn contour_polygons(matrix: &QrMatrix) -> Vec<Vec<(usize, usize)>> {
+    let (width, height) = (matrix.width(), matrix.height());
+    let stride = width + 1;
+    let mut out = vec![0u8; stride * (height + 1)];
+
+    for y in 0..=height {
+        for x in 0..=width {
+            let v = y * stride + x;
+            // 水平边 (x, y) → (x + 1, y)：上方模块 (x, y - 1)，下方模块 (x, y)
+            if x < width {
+                let above = y > 0 && matrix.get(x, y - 1);
+                let below = matrix.get(x, y);
+                if below && !above {
+                    out[v] |= EAST;
+                } else if above && !below {
+                    out[v + 1] |= WEST;
+                }
+            }
+            // 竖直边 (x, y) → (x, y + 1)：左侧模块 (x - 1, y)，右侧模块 (x, y)
+            if y < height {
+                let left = x > 0 && matrix.get(x - 1, y);
+                let right = matrix.get(x, y);
+                if right && !left {
+                    out[v + stride] |= NORTH;
+                } else if left && !right {
+                    out[v] |= SOUTH;
+                }
+            }
+        }
+    }
+
+    let mut polygons = Vec::new();
+    for start in 0..out.len() {
+        while out[start] != 0 {
+            let mut polygon = vec![(start % stride, start / stride)];
+            let mut v = start;
+            let mut dir = out[v] & out[v].wrapping_neg();
+            loop {
+                out[v] &= !dir;
+                v = match dir {
+                    EAST => v + 1,
+                    WEST => v - 1,
+                    SOUTH => v + stride,
+                    _ => v - stride,
+                };
+
+                let exits = out[v];
+                if exits == 0 {
+                    // 回到起点
+                    break;
+                }
+                let right = if dir == NORTH { EAST } else { dir << 1 };
+                let left = if dir == EAST { NORTH } else { dir >> 1 };
+                let next = [right, dir, left]
+                    .into_iter()
+                    .find(|&d| exits & d != 0)
+                    .unwrap_or(dir);
+                if next != dir {
+                    polygon.push((v % stride, v / stride));
+                    dir = next;
+                }
+            }
+            polygons.push(polygon);
+        }
+    }
+    polygons
+}
+
+/// 描边路径：每个多边形一个子路径，最后一段由 `z` 补齐
+fn push_contour_paths(svg: &mut String, matrix: &QrMatrix, cell_size: i32, offset: i32) {
+    for polygon in contour_polygons(matrix) {
+        let (x, y) = polygon[0];
+        svg.push('M');
+        push_i32(svg, x as i32 * cell_size + offset);
+        svg.push(' ');
+        push_i32(svg, y as i32 * cell_size + offset);
+        for pair in polygon.windows(2) {
+            let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+            if y0 == y1 {
+                svg.push('h');
+                push_i32(svg, (x1 as i32 - x0 as i32) * cell_size);
+            } else {
+                svg.push('v');
+                push_i32(svg, (y1 as i32 - y0 as i32) * cell_size);
+            }
+        }
+        svg.push('z');
+    }
+}
+
+/// 生成 SVG 字符串（每个深色模块一个 `<rect>`，与 qrcode-wasm 的输出一致）
+pub fn render_svg_rects(
+    matrix: &QrMatrix,
+    size: i32,
+    color_dark: &str,
+    color_light: &str,
+) -> String {
+    let count = matrix.width() as i32;
+    if count == 0 {
+        return String::new();
+    }
+
+    let cell_size = size / count;
+    let offset = (size - cell_size * count) / 2; // 居中偏移
+
+    let mut svg = String::with_capacity(200 + matrix.count_dark() * 70);
+    write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {0} {0}" width="{0}" height="{0}"><rect width="{0}" height="{0}" fill="{1}"/>"#,
+        size, color_light
+    )
+    .unwrap();
+
+    svg.push_str(&render(
+        matrix,
+        0,
+        None,
+        SvgRectRenderer::new(cell_size, offset, color_dark),
+    ));
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// 快速将 i32 推入字符串
+#[inline(always)]
+pub(crate) fn push_i32(s: &mut String, n: i32) {
+    if n == 0 {
+        s.push('0');
+        return;
+    }
+
+    if n < 0 {
+        s.push('-');
+    }
+    // 取绝对值用 u32，`i32::MIN` 取反不会溢出
+    let mut n = n.unsigned_abs();
+
+    let mut buf = [0u8; 10];
+    let mut i = 10;
+
+    while n > 0 {
+        i -= 1;
+        buf[i] = (n % 10) as u8 + b'0';
+        n /= 10;
+    }
+
+    // SAFETY: buf[i..] 只包含 ASCII 数字
+    s.push_str(unsafe { core::str::from_utf8_unchecked(&buf[i..]) });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qr_builder::QrBuilder;
+    use crate::qr_code_model::EcLevel;
+    use crate::qr_conformance::rasterize_path;
+
+    fn sample() -> QrMatrix {
+        let mut m = QrMatrix::square(2);
+        m.set(0, 0, true);
+        m.set(1, 1, true);
+        m
+    }
+
+    #[test]
+    fn test_render_svg_path() {
+        let svg = render_svg(&sample(), 4, "#000", "#fff");
+        assert_eq!(
+            svg,
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 4 4" width="4" height="4">"#,
+                r##"<path d="M0 0h4v4H0z" fill="#fff"/><path fill="#000" d="M0 0h2v2h-2zM2 2h2v2h-2z"/></svg>"##
+            )
+        );
+    }
+
+    /// 取出最后一个 `d` 属性，按 even-odd 规则栅格化
+    fn rasterize(svg: &str, count: usize) -> QrMatrix {
+        let d = svg.rsplit(" d=\"").next().unwrap();
+        rasterize_path(&d[..d.find('"').unwrap()], count)
+    }
+
+    #[test]
+    fn test_path_modes_cover_dark_modules() {
+        for (text, level) in [
+            ("Hello", EcLevel::H),
+            ("https://github.com/veaba/qrcodes", EcLevel::M),
+            (&"X".repeat(300) as &str, EcLevel::L),
+        ] {
+            let symbol = QrBuilder::new(text).ec_level(level).build().unwrap();
+            let matrix = symbol.matrix();
+            let count = matrix.width();
+            for mode in [
+                SvgPathMode::Modules,
+                SvgPathMode::Runs,
+                SvgPathMode::Contours,
+            ] {
+                let svg = render_svg_with(matrix, count as i32, "#000", "#fff", mode);
+                assert_eq!(&rasterize(&svg, count), matrix, "{mode:?} v{}", count);
+            }
+        }
+    }
+
+    #[test]
+    fn test_contours() {
+        // 3×3 的环：外轮廓加一个孔
+        let mut ring = QrMatrix::square(3);
+        for (x, y) in (0..3).flat_map(|y| (0..3).map(move |x| (x, y))) {
+            ring.set(x, y, (x, y) != (1, 1));
+        }
+        let svg = render_svg_with(&ring, 3, "#000", "#fff", SvgPathMode::Contours);
+        assert!(svg.ends_with(
+            r##"<path fill="#000" fill-rule="evenodd" d="M0 0h3v3h-3zM1 1v1h1v-1z"/></svg>"##
+        ));
+
+        // 对角相接的两个模块是两个独立的多边形
+        let svg = render_svg_with(&sample(), 4, "#000", "#fff", SvgPathMode::Contours);
+        assert!(svg.ends_with(r#"d="M0 0h2v2h-2zM2 2h2v2h-2z"/></svg>"#));
+        let svg = render_svg_with(&sample(), 4, "#000", "#fff", SvgPathMode::Runs);
+        assert!(svg.ends_with(r#"d="M0 0h2v2h-2zM2 2h2v2h-2z"/></svg>"#));
+
+        // 版本 10：轮廓 < 行合并 < 逐模块
+        let symbol = QrBuilder::new(&"A".repeat(150))
+            .ec_level(EcLevel::M)
+            .build()
+            .unwrap();
+        let [modules, runs, contours] = [
+            SvgPathMode::Modules,
+            SvgPathMode::Runs,
+            SvgPathMode::Contours,
+        ]
+        .map(|mode| render_svg_with(symbol.matrix(), 570, "#000", "#fff", mode).len());
+        assert!(
+            contours < runs && runs < modules,
+            "{contours} {runs} {modules}"
+        );
+        assert!(contours * 2 < modules);
+    }
+
+    #[test]
+    fn test_svg_options() {
+        let options = SvgOptions {
+            size: SvgSize::Module(0.5),
+            unit: String::from("mm"),
+            quiet_zone: 1,
+            path_mode: SvgPathMode::Runs,
+            title: Some(String::from("Pay <ACME> & co")),
+            ..Default::default()
+        };
+        let svg = render_svg_with_options(&sample(), &options);
+        assert_eq!(
+            svg,
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 4 4" width="2mm" height="2mm" "#,
+                r#"role="img" aria-label="Pay &lt;ACME&gt; &amp; co" shape-rendering="crispEdges">"#,
+                "<title>Pay &lt;ACME&gt; &amp; co</title>",
+                r##"<path d="M0 0h4v4H0z" fill="#ffffff"/><path fill="#000000" d="M1 1h1v1h-1zM2 2h1v1h-1z"/></svg>"##
+            )
+        );
+
+        let fragment = SvgOptions {
+            fragment: true,
+            crisp_edges: false,
+            quiet_zone: 0,
+            ..Default::default()
+        };
+        assert_eq!(
+            render_svg_with_options(&sample(), &fragment),
+            r##"<g><path d="M0 0h2v2H0z" fill="#ffffff"/><path fill="#000000" fill-rule="evenodd" d="M0 0h1v1h-1zM1 1h1v1h-1z"/></g>"##
+        );
+
+        // 总尺寸不必是模块数的整数倍
+        let symbol = QrBuilder::new("Hello").build().unwrap();
+        let svg = render_svg_with_options(symbol.matrix(), &SvgOptions::default());
+        assert!(svg.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 29 29" width="256" height="256""#
+        ));
+        assert!(render_svg_with_options(&QrMatrix::default(), &fragment).is_empty());
+    }
+
+    #[test]
+    fn test_render_svg_rects() {
+        let svg = render_svg_rects(&sample(), 5, "#000", "#fff");
+        assert!(svg.contains(r##"<rect width="5" height="5" fill="#fff"/>"##));
+        assert!(svg.contains(r##"<rect x="0" y="0" width="2" height="2" fill="#000"/>"##));
+        assert!(svg.contains(r##"<rect x="2" y="2" width="2" height="2" fill="#000"/>"##));
+        assert!(svg.ends_with("</svg>"));
+    }
+
+    #[test]
+    fn test_empty_matrix() {
+        assert!(render_svg(&QrMatrix::default(), 256, "#000", "#fff").is_empty());
+        assert!(render_svg_rects(&QrMatrix::default(), 256, "#000", "#fff").is_empty());
+    }
+
+    #[test]
+    fn test_push_i32() {
+        let mut s = String::new();
+        for n in [0, 7, -42, 1234567890, i32::MAX, i32::MIN] {
+            push_i32(&mut s, n);
+            s.push(',');
+        }
+        assert_eq!(s, "0,7,-42,1234567890,2147483647,-2147483648,");
+    }
+}
@@ -0,0 +1,97 @@
+//! QR Code Symbol - 编码完成的不可变二维码符号
+//!
+//! 只包含编码结果（版本、纠错级别、掩码、模块矩阵），
+//! 尺寸、颜色等渲染参数由各渲染器单独传入。
+
+use crate::qr_builder::symbol_roles;
+use crate::qr_code_model::QRErrorCorrectLevel;
+use crate::qr_error::{QrError, QrResult};
+use crate::qr_mask::Mask;
+use crate::qr_matrix::QrMatrix;
+use crate::qr_module_role::{ModuleRole, RoleMap};
+use crate::qr_version::Version;
+
+/// 编码完成的二维码符号，由 [`QrBuilder`](crate::qr_builder::QrBuilder) 生成
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QrSymbol {
+    pub(crate) version: Version,
+    pub(crate) ec_level: QRErrorCorrectLevel,
+    pub(crate) mask: Mask,
+    pub(crate) matrix: QrMatrix,
+    pub(crate) roles: RoleMap,
+}
+
+impl QrSymbol {
+    /// 由其他实现生成的正方形矩阵构造符号，模块角色按版本重新计算
+    pub fn from_matrix(
+        version: Version,
+        ec_level: QRErrorCorrectLevel,
+        mask: Mask,
+        matrix: QrMatrix,
+    ) -> QrResult<Self> {
+        let expected = version.module_count() as usize;
+        if matrix.width() != expected || matrix.height() != expected {
+            return Err(QrError::MatrixSizeMismatch {
+                expected,
+                actual: matrix.width(),
+            });
+        }
+        Ok(QrSymbol {
+            version,
+            ec_level,
+            mask,
+            matrix,
+            roles: symbol_roles(version, ec_level),
+        })
+    }
+
+    /// 版本号
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// 实际使用的纠错级别（开启 `boost_ecl` 时可能高于请求的级别）
+    pub fn ec_level(&self) -> QRErrorCorrectLevel {
+        self.ec_level
+    }
+
+    /// 实际使用的掩码
+    pub fn mask(&self) -> Mask {
+        self.mask
+    }
+
+    /// 每边模块数
+    pub fn module_count(&self) -> i32 {
+        self.matrix.width() as i32
+    }
+
+    /// 读取 (row, col) 处的模块，越界返回 false
+    pub fn is_dark(&self, row: i32, col: i32) -> bool {
+        row >= 0 && col >= 0 && self.matrix.get(col as usize, row as usize)
+    }
+
+    /// 位压缩的模块矩阵，可直接传给渲染器
+    pub fn matrix(&self) -> &QrMatrix {
+        &self.matrix
+    }
+
+    /// 获取指定位置模块的角色，越界返回 `None`
+    pub fn module_role(&self, row: i32, col: i32) -> Option<ModuleRole> {
+        self.roles.get(row, col)
+    }
+
+    /// 按行优先顺序遍历指定角色的模块，产出 `(row, col)`
+    pub fn modules_with_role(&self, role: ModuleRole) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.roles.iter_role(role)
+    }
+
+    /// 获取完整的角色表
+    pub fn roles(&self) -> &RoleMap {
+        &self.roles
+    }
+
+    /// 取出模块矩阵
+    pub fn into_matrix(self) -> QrMatrix {
+        self.matrix
+    }
+}
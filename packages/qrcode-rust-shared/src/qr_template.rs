@@ -0,0 +1,378 @@
+//! QR Code Template - 按版本缓存的功能图案模板
+//!
+//! 位置探测、校正、定时图案、版本信息与固定暗模块只与版本有关。
+//! [`VersionTemplate::get`] 在第一次用到某个版本时绘制并缓存，
+//! 之后每次编码只需复制模板，再写入格式信息和数据；8 个掩码的位图也随模板缓存。
+//! 格式信息（32 个）与版本信息（34 个）的 BCH 码字在编译期生成查找表。
+
+use std::sync::OnceLock;
+
+use crate::qr_code_model::{get_pattern_position, QRErrorCorrectLevel};
+use crate::qr_mask::{mask_bit, Mask, MASK_COUNT};
+use crate::qr_matrix::QrMatrix;
+use crate::qr_module_role::{ModuleRole, RoleMap};
+use crate::qr_version::Version;
+
+const G15: u32 = 0b101_0011_0111;
+const G15_MASK: u32 = 0b101_0100_0001_0010;
+const G18: u32 = 0b1_1111_0010_0101;
+
+/// 15 位格式信息（已异或 0x5412），下标为 `纠错级别指示符 << 3 | 掩码`
+pub const FORMAT_INFO: [u16; 32] = {
+    let mut table = [0; 32];
+    let mut data = 0;
+    while data < 32 {
+        table[data] =
+            (((data as u32) << 10 | bch_remainder((data as u32) << 10, G15)) ^ G15_MASK) as u16;
+        data += 1;
+    }
+    table
+};
+
+/// 18 位版本信息，下标为 `版本 - 7`（版本 7-40）
+pub const VERSION_INFO: [u32; 34] = {
+    let mut table = [0; 34];
+    let mut i = 0;
+    while i < 34 {
+        let data = (i as u32 + 7) << 12;
+        table[i] = data | bch_remainder(data, G18);
+        i += 1;
+    }
+    table
+};
+
+/// 计算 BCH 余数
+const fn bch_remainder(data: u32, generator: u32) -> u32 {
+    let digits = 32 - generator.leading_zeros();
+    let mut d = data;
+    while 32 - d.leading_zeros() >= digits {
+        d ^= generator << (32 - d.leading_zeros() - digits);
+    }
+    d
+}
+
+/// 15 位格式信息（查表）
+pub fn format_info_bits(ec_level: QRErrorCorrectLevel, mask: Mask) -> i32 {
+    FORMAT_INFO[(ec_level.format_bits() as usize) << 3 | mask.get() as usize] as i32
+}
+
+/// 18 位版本信息（查表），版本 7 以下没有版本信息，返回 0
+pub fn version_info_bits(version: Version) -> i32 {
+    match version.get() {
+        v @ 7.. => VERSION_INFO[v as usize - 7] as i32,
+        _ => 0,
+    }
+}
+
+/// 按版本缓存的功能图案模板
+#[derive(Debug)]
+pub struct VersionTemplate {
+    version: Version,
+    function: QrMatrix,
+    reserved: QrMatrix,
+    roles: RoleMap,
+    states: Vec<u8>,
+    masks: [QrMatrix; MASK_COUNT as usize],
+}
+
+static TEMPLATES: [OnceLock<VersionTemplate>; 40] = [const { OnceLock::new() }; 40];
+
+impl VersionTemplate {
+    /// 获取指定版本的模板，第一次调用时构建
+    pub fn get(version: Version) -> &'static VersionTemplate {
+        TEMPLATES[version.get() as usize - 1].get_or_init(|| Self::build(version))
+    }
+
+    fn build(version: Version) -> Self {
+        let (function, roles) = function_patterns(version);
+        let count = version.module_count() as usize;
+        let mut reserved = QrMatrix::square(count);
+        let mut states = vec![0u8; count * count];
+        for row in 0..count {
+            for col in 0..count {
+                if roles
+                    .get(row as i32, col as i32)
+                    .is_some_and(ModuleRole::is_function)
+                {
+                    reserved.set(col, row, true);
+                    states[row * count + col] = if function.get(col, row) { 1 } else { 2 };
+                }
+            }
+        }
+        let masks = Mask::ALL.map(|mask| {
+            let mut pattern = QrMatrix::square(count);
+            for row in 0..count {
+                for col in 0..count {
+                    if !reserved.get(col, row) && mask_bit(mask, row, col) {
+                        pattern.set(col, row, true);
+                    }
+                }
+            }
+            pattern
+        });
+        VersionTemplate {
+            version,
+            function,
+            reserved,
+            roles,
+            states,
+            masks,
+        }
+    }
+
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// 每边模块数
+    pub fn module_count(&self) -> i32 {
+        self.version.module_count()
+    }
+
+    /// 功能图案（深色 = true），格式信息区域为浅色
+    pub fn function(&self) -> &QrMatrix {
+        &self.function
+    }
+
+    /// 保留区域（功能图案 + 格式信息），数据不能写入
+    pub fn reserved(&self) -> &QrMatrix {
+        &self.reserved
+    }
+
+    /// 功能图案的角色，其余模块为 [`ModuleRole::Data`]
+    pub fn roles(&self) -> &RoleMap {
+        &self.roles
+    }
+
+    /// 数据区的掩码位图：与未加掩码的符号逐行异或即完成掩码
+    pub fn mask_pattern(&self, mask: Mask) -> &QrMatrix {
+        &self.masks[mask.get() as usize]
+    }
+
+    /// qrcode-fast 的一维状态数组：0 = 未设置, 1 = 深色, 2 = 已设置但浅色
+    pub fn states(&self) -> &[u8] {
+        &self.states
+    }
+
+    /// qrcode-rust / wasm 的模块表：数据区为 `None`
+    pub fn to_option_rows(&self) -> Vec<Vec<Option<bool>>> {
+        self.states
+            .chunks_exact(self.module_count() as usize)
+            .map(|row| {
+                row.iter()
+                    .map(|&state| (state != 0).then_some(state == 1))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// 把格式信息写入一维状态数组（1 = 深色, 2 = 浅色）
+pub fn write_format_states(
+    states: &mut [u8],
+    count: i32,
+    ec_level: QRErrorCorrectLevel,
+    mask: Mask,
+) {
+    let bits = format_info_bits(ec_level, mask);
+    for i in 0..15 {
+        let state = if (bits >> i) & 1 == 1 { 1 } else { 2 };
+        let ((r1, c1), (r2, c2)) = format_info_positions(count, i);
+        states[(r1 * count + c1) as usize] = state;
+        states[(r2 * count + c2) as usize] = state;
+    }
+}
+
+/// 第 i 位格式信息的两个位置 `((row, col), (row, col))`
+pub fn format_info_positions(count: i32, i: i32) -> ((i32, i32), (i32, i32)) {
+    let vertical = if i < 6 {
+        (i, 8)
+    } else if i < 8 {
+        (i + 1, 8)
+    } else {
+        (count - 15 + i, 8)
+    };
+    let horizontal = if i < 8 {
+        (8, count - 1 - i)
+    } else if i < 9 {
+        (8, 7)
+    } else {
+        (8, 14 - i)
+    };
+    (vertical, horizontal)
+}
+
+pub(crate) fn put(
+    matrix: &mut QrMatrix,
+    roles: &mut RoleMap,
+    row: i32,
+    col: i32,
+    dark: bool,
+    role: ModuleRole,
+) {
+    matrix.set(col as usize, row as usize, dark);
+    roles.set(row, col, role);
+}
+
+/// 绘制除格式信息取值以外的所有功能图案，并预留格式信息区域
+fn function_patterns(version: Version) -> (QrMatrix, RoleMap) {
+    let count = version.module_count();
+    let mut matrix = QrMatrix::square(count as usize);
+    let mut roles = RoleMap::new(count as usize);
+
+    // 位置探测图案及分隔符
+    for (row, col) in [(0, 0), (count - 7, 0), (0, count - 7)] {
+        for r in -1..=7 {
+            for c in -1..=7 {
+                if !(0..count).contains(&(row + r)) || !(0..count).contains(&(col + c)) {
+                    continue;
+                }
+                let in_finder = (0..=6).contains(&r) && (0..=6).contains(&c);
+                let dark = in_finder
+                    && (r == 0
+                        || r == 6
+                        || c == 0
+                        || c == 6
+                        || ((2..=4).contains(&r) && (2..=4).contains(&c)));
+                let role = if in_finder {
+                    ModuleRole::Finder
+                } else {
+                    ModuleRole::Separator
+                };
+                put(&mut matrix, &mut roles, row + r, col + c, dark, role);
+            }
+        }
+    }
+
+    // 位置校正图案（跳过与位置探测图案重叠的位置）
+    let pos = get_pattern_position(version.get());
+    for &row in &pos {
+        for &col in &pos {
+            if roles.get(row, col).is_some_and(ModuleRole::is_function) {
+                continue;
+            }
+            for r in -2..=2 {
+                for c in -2..=2 {
+                    let dark = r == -2 || r == 2 || c == -2 || c == 2 || (r == 0 && c == 0);
+                    put(
+                        &mut matrix,
+                        &mut roles,
+                        row + r,
+                        col + c,
+                        dark,
+                        ModuleRole::Alignment,
+                    );
+                }
+            }
+        }
+    }
+
+    // 定时图案
+    for i in 8..count - 8 {
+        if roles.get(i, 6) == Some(ModuleRole::Data) {
+            put(
+                &mut matrix,
+                &mut roles,
+                i,
+                6,
+                i % 2 == 0,
+                ModuleRole::Timing,
+            );
+        }
+        if roles.get(6, i) == Some(ModuleRole::Data) {
+            put(
+                &mut matrix,
+                &mut roles,
+                6,
+                i,
+                i % 2 == 0,
+                ModuleRole::Timing,
+            );
+        }
+    }
+
+    // 格式信息区域（取值在选定掩码后写入）与固定暗模块
+    for i in 0..15 {
+        let ((r1, c1), (r2, c2)) = format_info_positions(count, i);
+        roles.set(r1, c1, ModuleRole::FormatInfo);
+        roles.set(r2, c2, ModuleRole::FormatInfo);
+    }
+    put(
+        &mut matrix,
+        &mut roles,
+        count - 8,
+        8,
+        true,
+        ModuleRole::DarkModule,
+    );
+
+    // 版本信息
+    if version.get() >= 7 {
+        let bits = version_info_bits(version);
+        for i in 0..18 {
+            let dark = (bits >> i) & 1 == 1;
+            let (a, b) = (i / 3, i % 3 + count - 11);
+            put(&mut matrix, &mut roles, a, b, dark, ModuleRole::VersionInfo);
+            put(&mut matrix, &mut roles, b, a, dark, ModuleRole::VersionInfo);
+        }
+    }
+
+    (matrix, roles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bch_reference(data: i32, generator: i32) -> i32 {
+        use crate::qr_util::get_bch_digit;
+        let mut d = data;
+        while get_bch_digit(d) - get_bch_digit(generator) >= 0 {
+            d ^= generator << (get_bch_digit(d) - get_bch_digit(generator));
+        }
+        d
+    }
+
+    #[test]
+    fn test_lookup_tables_match_bch() {
+        for data in 0..32 {
+            let expected = ((data << 10) | bch_reference(data << 10, G15 as i32)) ^ G15_MASK as i32;
+            assert_eq!(FORMAT_INFO[data as usize] as i32, expected);
+        }
+        for version in 7..=40 {
+            let data = version << 12;
+            let expected = data | bch_reference(data, G18 as i32);
+            assert_eq!(VERSION_INFO[version as usize - 7] as i32, expected);
+        }
+        assert_eq!(version_info_bits(Version::MIN), 0);
+    }
+
+    #[test]
+    fn test_template_cached_and_consistent() {
+        for version in [Version::MIN, Version::new(7).unwrap(), Version::MAX] {
+            let template = VersionTemplate::get(version);
+            assert!(core::ptr::eq(template, VersionTemplate::get(version)));
+
+            let count = template.module_count();
+            let rows = template.to_option_rows();
+            for row in 0..count {
+                for col in 0..count {
+                    let (x, y) = (col as usize, row as usize);
+                    let is_function = template.roles().get(row, col).unwrap().is_function();
+                    assert_eq!(template.reserved().get(x, y), is_function);
+                    let state = template.states()[y * count as usize + x];
+                    assert_eq!(state != 0, is_function);
+                    assert_eq!(state == 1, template.function().get(x, y));
+                    assert_eq!(rows[y][x], is_function.then_some(state == 1));
+                    for mask in Mask::ALL {
+                        let flipped = !is_function && mask_bit(mask, y, x);
+                        assert_eq!(template.mask_pattern(mask).get(x, y), flipped);
+                    }
+                }
+            }
+        }
+        // 版本 1 共 26 个码字 = 208 个数据模块，其余 233 个模块均被保留
+        let v1 = VersionTemplate::get(Version::MIN);
+        assert_eq!(v1.reserved().count_dark(), 21 * 21 - 208);
+    }
+}
@@ -0,0 +1,424 @@
+//! QR Code Terminal - 终端输出
+//!
+//! 文本模式：半块字符（`▀▄█`，一行字符显示两行模块）、盲文点阵（一个字符 2×4 个模块）
+//! 和自定义字符的 ASCII，可选 ANSI 24 位颜色。
+//! 图像模式：sixel 与 kitty 图形协议，由支持图像的终端按像素显示。
+//!
+//! 文本模式的字符“墨迹”使用终端前景色：浅色背景的终端上墨迹表示深色模块；
+//! 深色背景的终端前景是浅色，需要 `invert` 让墨迹表示浅色模块，扫码器才能识别。
+//! [`TerminalHints::from_env`] 根据环境变量给出判断依据。
+
+use core::fmt::Write;
+
+use crate::qr_base64;
+use crate::qr_matrix::QrMatrix;
+use crate::qr_png::render_png;
+
+/// 文本模式选项
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerminalOptions {
+    /// 空白边宽度（模块数）
+    pub quiet_zone: usize,
+    /// 反色：墨迹表示浅色模块，用于深色背景的终端
+    pub invert: bool,
+    /// ANSI 24 位颜色 `(深色, 浅色)`，显式指定前景和背景，此时忽略 `invert`
+    pub colors: Option<([u8; 3], [u8; 3])>,
+}
+
+impl Default for TerminalOptions {
+    fn default() -> Self {
+        TerminalOptions {
+            quiet_zone: 2,
+            invert: false,
+            colors: None,
+        }
+    }
+}
+
+impl TerminalOptions {
+    /// 按终端提示选择默认选项：深色背景时反色
+    pub fn for_terminal(hints: &TerminalHints) -> Self {
+        TerminalOptions {
+            invert: hints.dark_background,
+            ..Default::default()
+        }
+    }
+}
+
+/// 终端支持的图像协议
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    Sixel,
+    Kitty,
+}
+
+/// 从环境变量推断的终端能力，只是提示，无法保证准确
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TerminalHints {
+    /// 背景为深色（`COLORFGBG`），文本模式应当反色
+    pub dark_background: bool,
+    /// 支持 24 位颜色（`COLORTERM`，设置 `NO_COLOR` 时为 false）
+    pub truecolor: bool,
+    /// 支持的图像协议（`TERM`、`TERM_PROGRAM`、`KITTY_WINDOW_ID`）
+    pub graphics: Option<GraphicsProtocol>,
+}
+
+impl TerminalHints {
+    /// 读取当前进程的环境变量
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    /// 从任意变量来源推断（例如 SSH 会话转发的环境）
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        // COLORFGBG = "前景;背景"，背景色号 0-6 与 8 为深色
+        let dark_background = var("COLORFGBG")
+            .and_then(|v| v.rsplit(';').next().and_then(|bg| bg.parse::<u8>().ok()))
+            .is_some_and(|bg| bg < 7 || bg == 8);
+        let truecolor = var("NO_COLOR").is_none()
+            && var("COLORTERM").is_some_and(|v| v == "truecolor" || v == "24bit");
+
+        let term = var("TERM").unwrap_or_default();
+        let program = var("TERM_PROGRAM").unwrap_or_default();
+        let graphics = if var("KITTY_WINDOW_ID").is_some()
+            || term.contains("kitty")
+            || ["WezTerm", "ghostty"].contains(&program.as_str())
+        {
+            Some(GraphicsProtocol::Kitty)
+        } else if term.contains("sixel")
+            || ["foot", "mlterm", "contour"]
+                .iter()
+                .any(|name| term.starts_with(name))
+        {
+            Some(GraphicsProtocol::Sixel)
+        } else {
+            None
+        };
+
+        TerminalHints {
+            dark_background,
+            truecolor,
+            graphics,
+        }
+    }
+}
+
+/// 半块字符：每个字符显示上下两个模块
+pub fn render_half_blocks(matrix: &QrMatrix, options: &TerminalOptions) -> String {
+    let ink = ink_matrix(matrix, options);
+    let mut out = String::new();
+    for y in (0..ink.height()).step_by(2) {
+        let line: String = (0..ink.width())
+            .map(|x| match (ink.get(x, y), ink.get(x, y + 1)) {
+                (true, true) => '█',
+                (true, false) => '▀',
+                (false, true) => '▄',
+                (false, false) => ' ',
+            })
+            .collect();
+        push_line(&mut out, &line, options);
+    }
+    out
+}
+
+/// 盲文点阵：每个字符显示 2 列 × 4 行模块，最紧凑但点之间有间隙
+pub fn render_braille(matrix: &QrMatrix, options: &TerminalOptions) -> String {
+    // 盲文点位 (列, 行) 对应的位
+    const DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+    let ink = ink_matrix(matrix, options);
+    let mut out = String::new();
+    for y in (0..ink.height()).step_by(4) {
+        let line: String = (0..ink.width())
+            .step_by(2)
+            .map(|x| {
+                let mut bits = 0;
+                for (dx, column) in DOTS.iter().enumerate() {
+                    for (dy, bit) in column.iter().enumerate() {
+                        if ink.get(x + dx, y + dy) {
+                            bits |= bit;
+                        }
+                    }
+                }
+                char::from_u32(0x2800 + bits).unwrap_or(' ')
+            })
+            .collect();
+        push_line(&mut out, &line, options);
+    }
+    out
+}
+
+/// 每个模块输出一段自定义字符串，例如 `("##", "  ")`；`invert` 时两者互换
+pub fn render_ascii(
+    matrix: &QrMatrix,
+    dark: &str,
+    light: &str,
+    options: &TerminalOptions,
+) -> String {
+    let ink = ink_matrix(matrix, options);
+    let mut out = String::new();
+    let mut line = String::new();
+    for y in 0..ink.height() {
+        line.clear();
+        for x in 0..ink.width() {
+            line.push_str(if ink.get(x, y) { dark } else { light });
+        }
+        push_line(&mut out, &line, options);
+    }
+    out
+}
+
+/// sixel 图像：每个模块 `module_px` 像素，四周 `quiet_zone` 个模块的空白边
+pub fn render_sixel(
+    matrix: &QrMatrix,
+    module_px: u32,
+    quiet_zone: u32,
+    dark: [u8; 3],
+    light: [u8; 3],
+) -> String {
+    let padded = matrix.with_quiet_zone(quiet_zone as usize);
+    let px = module_px.max(1) as usize;
+    let size = padded.width() * px;
+    let pixel = |x: usize, y: usize| padded.get(x / px, y / px);
+
+    // DCS q，像素宽高比 1:1；颜色寄存器 0 = 浅色，1 = 深色（RGB 百分比）
+    let mut out = format!("\x1bP0;1;0q\"1;1;{size};{size}");
+    for (register, [r, g, b]) in [light, dark].into_iter().enumerate() {
+        let percent = |c: u8| c as u32 * 100 / 255;
+        let _ = write!(
+            out,
+            "#{register};2;{};{};{}",
+            percent(r),
+            percent(g),
+            percent(b)
+        );
+    }
+
+    // 每 6 行像素为一带，两种颜色分别输出后用 `$` 回到行首叠加
+    for band in (0..size).step_by(6) {
+        for (register, dark) in [false, true].into_iter().enumerate() {
+            let _ = write!(out, "#{register}");
+            let mut run: Option<(u8, usize)> = None;
+            for x in 0..size {
+                let mut bits = 0u8;
+                for k in 0..6 {
+                    if band + k < size && pixel(x, band + k) == dark {
+                        bits |= 1 << k;
+                    }
+                }
+                let ch = 63 + bits;
+                match &mut run {
+                    Some((c, n)) if *c == ch => *n += 1,
+                    _ => {
+                        push_sixel_run(&mut out, run);
+                        run = Some((ch, 1));
+                    }
+                }
+            }
+            push_sixel_run(&mut out, run);
+            out.push(if dark { '-' } else { '$' });
+        }
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+/// kitty 图形协议：传输 PNG（`f=100`）并立即显示，数据按 4096 字节分块
+pub fn render_kitty(
+    matrix: &QrMatrix,
+    module_px: u32,
+    quiet_zone: u32,
+    dark: [u8; 3],
+    light: [u8; 3],
+) -> String {
+    let [dr, dg, db] = dark;
+    let [lr, lg, lb] = light;
+    let png = render_png(
+        matrix,
+        module_px.max(1),
+        quiet_zone,
+        [dr, dg, db, 255],
+        [lr, lg, lb, 255],
+    );
+    let data = qr_base64::encode(&png);
+
+    let mut out = String::new();
+    let mut chunks = data.as_bytes().chunks(4096).peekable();
+    let mut first = true;
+    while let Some(chunk) = chunks.next() {
+        let more = chunks.peek().is_some() as u8;
+        let control = if first {
+            format!("a=T,f=100,m={more}")
+        } else {
+            format!("m={more}")
+        };
+        first = false;
+        // base64 只含 ASCII
+        let chunk = core::str::from_utf8(chunk).unwrap_or_default();
+        let _ = write!(out, "\x1b_G{control};{chunk}\x1b\\");
+    }
+    out
+}
+
+/// 加上空白边，并按 `invert` 转换为墨迹位图
+fn ink_matrix(matrix: &QrMatrix, options: &TerminalOptions) -> QrMatrix {
+    let padded = matrix.with_quiet_zone(options.quiet_zone);
+    if !options.invert || options.colors.is_some() {
+        return padded;
+    }
+    let mut ink = padded;
+    for y in 0..ink.height() {
+        for x in 0..ink.width() {
+            ink.toggle(x, y);
+        }
+    }
+    ink
+}
+
+fn push_line(out: &mut String, line: &str, options: &TerminalOptions) {
+    match options.colors {
+        Some(([dr, dg, db], [lr, lg, lb])) => {
+            let _ = writeln!(
+                out,
+                "\x1b[38;2;{dr};{dg};{db}m\x1b[48;2;{lr};{lg};{lb}m{line}\x1b[0m"
+            );
+        }
+        None => {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+}
+
+/// 输出一段重复的 sixel 字符，4 个及以上用 `!n` 压缩
+fn push_sixel_run(out: &mut String, run: Option<(u8, usize)>) {
+    match run {
+        Some((ch, n)) if n >= 4 => {
+            let _ = write!(out, "!{n}{}", ch as char);
+        }
+        Some((ch, n)) => out.extend(core::iter::repeat_n(ch as char, n)),
+        None => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 3×3：对角线为深色
+    fn diagonal() -> QrMatrix {
+        let mut m = QrMatrix::square(3);
+        for i in 0..3 {
+            m.set(i, i, true);
+        }
+        m
+    }
+
+    fn bare() -> TerminalOptions {
+        TerminalOptions {
+            quiet_zone: 0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_half_blocks() {
+        assert_eq!(render_half_blocks(&diagonal(), &bare()), "▀▄ \n  ▀\n");
+
+        let inverted = TerminalOptions {
+            invert: true,
+            ..bare()
+        };
+        assert_eq!(render_half_blocks(&diagonal(), &inverted), "▄▀█\n▀▀ \n");
+
+        // 空白边 1：5×5，三行字符
+        let padded = TerminalOptions {
+            quiet_zone: 1,
+            ..bare()
+        };
+        assert_eq!(
+            render_half_blocks(&diagonal(), &padded),
+            " ▄   \n  ▀▄ \n     \n"
+        );
+    }
+
+    #[test]
+    fn test_braille_and_ascii() {
+        // 第一个字符：(0,0) (1,1) → 0x01 | 0x10；第二个：(2,2) → 0x04
+        assert_eq!(render_braille(&diagonal(), &bare()), "\u{2811}\u{2804}\n");
+        assert_eq!(
+            render_ascii(&diagonal(), "##", "..", &bare()),
+            "##....\n..##..\n....##\n"
+        );
+    }
+
+    #[test]
+    fn test_ansi_colors() {
+        let colored = TerminalOptions {
+            invert: true,
+            colors: Some(([0, 0, 0], [255, 255, 255])),
+            ..bare()
+        };
+        let out = render_ascii(&diagonal(), "#", " ", &colored);
+        let first = out.lines().next().unwrap();
+        // 显式颜色时忽略 invert
+        assert_eq!(first, "\x1b[38;2;0;0;0m\x1b[48;2;255;255;255m#  \x1b[0m");
+        assert_eq!(out.lines().count(), 3);
+    }
+
+    #[test]
+    fn test_sixel() {
+        let sixel = render_sixel(&diagonal(), 2, 0, [0, 0, 0], [255, 255, 255]);
+        assert!(sixel.starts_with("\x1bP0;1;0q\"1;1;6;6#0;2;100;100;100#1;2;0;0;0"));
+        assert!(sixel.ends_with("\x1b\\"));
+        // 6×6 像素正好一带：浅色段以 `$` 结束，深色段以 `-` 结束
+        // 深色第 0、1 列为第 0-1 行像素 → 0b000011 + 63 = 'B'
+        assert!(sixel.contains("#0{{rrNN$#1BBKKoo-"));
+    }
+
+    #[test]
+    fn test_kitty_chunks() {
+        let mut big = QrMatrix::square(177);
+        let mut seed = 7u32;
+        for y in 0..177 {
+            for x in 0..177 {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                big.set(x, y, seed >> 31 == 1);
+            }
+        }
+        let out = render_kitty(&big, 4, 4, [0, 0, 0], [255, 255, 255]);
+        let chunks: Vec<&str> = out.split("\x1b\\").filter(|c| !c.is_empty()).collect();
+        assert!(chunks.len() > 1);
+        assert!(chunks[0].starts_with("\x1b_Ga=T,f=100,m=1;iVBORw0KGgo"));
+        assert!(chunks[1].starts_with("\x1b_Gm=1;") || chunks.len() == 2);
+        assert!(chunks.last().unwrap().starts_with("\x1b_Gm=0;"));
+    }
+
+    #[test]
+    fn test_hints_from_vars() {
+        let vars = |pairs: &'static [(&'static str, &'static str)]| {
+            TerminalHints::from_vars(move |name| {
+                pairs
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            })
+        };
+        assert_eq!(vars(&[]), TerminalHints::default());
+
+        let dark = vars(&[("COLORFGBG", "15;0"), ("COLORTERM", "truecolor")]);
+        assert!(dark.dark_background && dark.truecolor);
+        assert!(TerminalOptions::for_terminal(&dark).invert);
+        assert!(!vars(&[("COLORFGBG", "0;default;15")]).dark_background);
+        assert!(!vars(&[("COLORTERM", "24bit"), ("NO_COLOR", "1")]).truecolor);
+
+        assert_eq!(
+            vars(&[("TERM", "xterm-kitty")]).graphics,
+            Some(GraphicsProtocol::Kitty)
+        );
+        assert_eq!(
+            vars(&[("TERM", "foot-extra")]).graphics,
+            Some(GraphicsProtocol::Sixel)
+        );
+        assert_eq!(vars(&[("TERM", "xterm-256color")]).graphics, None);
+    }
+}
@@ -0,0 +1,87 @@
+//! QR Code Version - 经过校验的版本号
+
+use core::fmt;
+
+use crate::qr_error::{QrError, QrResult};
+
+/// 版本号，保证在 1..=40 内
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "i32", into = "i32")
+)]
+pub struct Version(pub(crate) u8);
+
+impl Version {
+    pub const MIN: Version = Version(1);
+    pub const MAX: Version = Version(40);
+
+    /// 校验并创建版本号
+    pub fn new(version: i32) -> QrResult<Self> {
+        if (1..=40).contains(&version) {
+            Ok(Version(version as u8))
+        } else {
+            Err(QrError::InvalidVersion(version))
+        }
+    }
+
+    /// 版本号 (1-40)
+    pub fn get(self) -> i32 {
+        self.0 as i32
+    }
+
+    /// 每边模块数 (`4 * version + 17`)
+    pub fn module_count(self) -> i32 {
+        self.get() * 4 + 17
+    }
+
+    /// 所有版本，从小到大
+    pub fn all() -> impl DoubleEndedIterator<Item = Version> {
+        (1..=40).map(Version)
+    }
+}
+
+impl TryFrom<i32> for Version {
+    type Error = QrError;
+
+    fn try_from(version: i32) -> QrResult<Self> {
+        Version::new(version)
+    }
+}
+
+impl From<Version> for i32 {
+    fn from(version: Version) -> i32 {
+        version.get()
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_range() {
+        assert_eq!(Version::new(1).unwrap(), Version::MIN);
+        assert_eq!(Version::new(40).unwrap(), Version::MAX);
+        assert_eq!(Version::new(0), Err(QrError::InvalidVersion(0)));
+        assert_eq!(Version::try_from(41), Err(QrError::InvalidVersion(41)));
+        assert_eq!(Version::all().count(), 40);
+    }
+
+    #[test]
+    fn test_version_values() {
+        let v = Version::new(7).unwrap();
+        assert_eq!(v.get(), 7);
+        assert_eq!(i32::from(v), 7);
+        assert_eq!(v.module_count(), 45);
+        assert_eq!(v.to_string(), "7");
+        assert!(Version::MIN < v);
+    }
+}
@@ -1,7 +1,7 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 // 我们的实现
-use qrcode_rust::{QRCode, QRErrorCorrectLevel};
+use qrcode_rust::{QRCode, QRErrorCorrectLevel, SvgPathMode};
 
 // kennytm 的实现
 use qrcode_kennytm::{EcLevel, QrCode};
@@ -441,6 +441,69 @@ fn benchmark_comparison_three_way(c: &mut Criterion) {
     group.finish();
 }
 
+// ========== SVG 路径写法：体积与耗时 ==========
+
+/// 输出三种路径写法的 SVG 体积并测量耗时，`svg` 按写法生成 SVG
+fn bench_svg_path_modes(
+    c: &mut Criterion,
+    label: &str,
+    group_name: &str,
+    module_count: i32,
+    svg: impl Fn(SvgPathMode) -> String,
+) {
+    let modes = [
+        ("modules", SvgPathMode::Modules),
+        ("runs", SvgPathMode::Runs),
+        ("contours", SvgPathMode::Contours),
+    ];
+    let baseline = svg(SvgPathMode::Modules).len();
+    println!("SVG 体积（{label}，{module_count}×{module_count} 模块）:");
+    for (name, mode) in modes {
+        let len = svg(mode).len();
+        println!(
+            "  {:<8} {:>6} 字节  {:>5.1}%",
+            name,
+            len,
+            len as f64 * 100.0 / baseline as f64
+        );
+    }
+
+    let mut group = c.benchmark_group(group_name);
+    for (name, mode) in modes {
+        group.bench_function(name, |b| {
+            b.iter(|| black_box(svg(mode)));
+        });
+    }
+    group.finish();
+}
+
+const SVG_PATH_MODES_TEXT: &str =
+    "https://example.com/very/long/path/with/many/segments?param1=value1&param2=value2&param3=value3";
+
+fn benchmark_veaba_svg_path_modes(c: &mut Criterion) {
+    let mut qr = QRCode::new();
+    qr.make_code(SVG_PATH_MODES_TEXT);
+    bench_svg_path_modes(
+        c,
+        "qrcode-rust",
+        "veaba_svg_path_modes",
+        qr.module_count,
+        |mode| qr.get_svg_with(mode),
+    );
+}
+
+fn benchmark_fast_svg_path_modes(c: &mut Criterion) {
+    let mut qr = FastQRCode::new();
+    qr.make_code(SVG_PATH_MODES_TEXT);
+    bench_svg_path_modes(
+        c,
+        "qrcode-fast",
+        "fast_svg_path_modes",
+        qr.module_count,
+        |mode| qr.get_svg_with(mode),
+    );
+}
+
 criterion_group!(
     veaba_benches,
     benchmark_veaba_single_generation,
@@ -475,9 +538,16 @@ criterion_group!(
     benchmark_comparison_three_way
 );
 
+criterion_group!(
+    svg_mode_benches,
+    benchmark_veaba_svg_path_modes,
+    benchmark_fast_svg_path_modes
+);
+
 criterion_main!(
     veaba_benches,
     fast_benches,
     kennytm_benches,
-    comparison_benches
+    comparison_benches,
+    svg_mode_benches
 );
@@ -5,18 +5,70 @@
 
 // 本地模块：核心 QRCode 实现（特有，不共享）
 mod qr_code;
+// 位图 / PNG 输出
+#[cfg(feature = "image-support")]
+mod qr_image;
 
 // 从 qrcode-rust-shared 重新导出
 pub use qrcode_rust_shared::{
     qr_8bit_byte::QR8bitByte,
     qr_bit_buffer::BitBuffer,
-    qr_code_model::{get_type_number, QRErrorCorrectLevel, QRMode, PATTERN_POSITION_TABLE},
+    qr_builder::QrBuilder,
+    qr_code_model::{
+        get_type_number, get_type_number_bytes, EcLevel, QRErrorCorrectLevel, QRMode,
+        PATTERN_POSITION_TABLE,
+    },
+    qr_color::parse_color,
+    qr_damage::{BlockDamage, DamageReport, ModuleRect},
+    qr_encoder::{check_capacity, QrEncoder},
+    qr_error::{QrError, QrResult},
+    qr_explain::{BlockInfo, Explanation, SegmentInfo},
+    qr_mask::Mask,
     qr_math::QRMath,
+    qr_matrix::QrMatrix,
+    qr_module_role::{ModuleRole, RoleMap},
+    qr_pdf::{render_pdf, PdfColor, PdfOptions},
+    qr_png::{render_png, PngRenderer},
     qr_polynomial::Polynomial,
+    qr_print::{PrintPlan, PrintSpec},
+    qr_raster::{render_into, render_rgba, PixelFormat, RasterOptions, RgbaRenderer},
+    qr_render::{render, MatrixRenderer, RenderInfo, Renderer},
     qr_rs_block::{get_rs_blocks, QRRSBlock},
+    qr_segment::{Segment, SegmentMode},
+    qr_static::StaticQrMatrix,
+    qr_svg::{
+        render_svg, render_svg_rects, render_svg_with, render_svg_with_options, SvgOptions,
+        SvgPathMode, SvgPathRenderer, SvgRectRenderer, SvgSize,
+    },
+    qr_symbol::QrSymbol,
+    qr_terminal::{
+        render_ascii, render_braille, render_half_blocks, render_kitty, render_sixel,
+        GraphicsProtocol, TerminalHints, TerminalOptions,
+    },
     qr_util::{get_bch_digit, get_length_in_bits},
+    qr_version::Version,
 };
 
+// `to_image` 返回的位图类型来自 image crate
+#[cfg(feature = "image-support")]
+pub use image;
+
+// 编译期生成（`macros` 特性）
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub use qrcode_macros as __macros;
+#[cfg(feature = "macros")]
+pub use qrcode_macros::qr_svg;
+
+/// 编译期编码为 [`StaticQrMatrix`]，参数见 `qrcode_macros::qr!`
+#[cfg(feature = "macros")]
+#[macro_export]
+macro_rules! qr {
+    ($($args:tt)*) => {
+        $crate::__macros::qr!(crate = $crate, $($args)*)
+    };
+}
+
 // 重新导出本地模块
 pub use qr_code::{QRCode, QRCodeOptions};
 
@@ -30,6 +82,11 @@ pub struct QRCodeNative {
 
 impl QRCodeNative {
     pub fn new(text: &str, correct_level: QRErrorCorrectLevel) -> Self {
+        Self::from_bytes(text.as_bytes(), correct_level)
+    }
+
+    /// 以字节模式编码原始二进制数据
+    pub fn from_bytes(data: &[u8], correct_level: QRErrorCorrectLevel) -> Self {
         let mut qr = QRCode::with_options(QRCodeOptions {
             width: 256,
             height: 256,
@@ -37,7 +94,7 @@ impl QRCodeNative {
             color_light: String::from("#ffffff"),
             correct_level,
         });
-        qr.make_code(text);
+        qr.make_code_bytes(data);
         QRCodeNative { qr }
     }
 
@@ -49,47 +106,31 @@ impl QRCodeNative {
         self.qr.get_module_count()
     }
 
+    pub fn version(&self) -> Option<Version> {
+        self.qr.version()
+    }
+
     pub fn is_dark(&self, row: i32, col: i32) -> bool {
         self.qr.is_dark(row, col)
     }
 
-    pub fn to_svg(&self, size: i32) -> String {
-        let count = self.qr.get_module_count();
-        if count == 0 {
-            return String::new();
-        }
-
-        let cell_size = size / count;
-        let actual_size = cell_size * count;
-        let offset = (size - actual_size) / 2;
-
-        let mut svg = format!(
-            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {} {}" width="{}" height="{}">"#,
-            size, size, size, size
-        );
-
-        svg.push_str(&format!(
-            r#"<rect width="{}" height="{}" fill="{}"/>"#,
-            size, size, self.qr.options.color_light
-        ));
+    /// 获取指定位置模块的角色
+    pub fn module_role(&self, row: i32, col: i32) -> Option<ModuleRole> {
+        self.qr.module_role(row, col)
+    }
 
-        for row in 0..count {
-            for col in 0..count {
-                if self.qr.is_dark(row, col) {
-                    svg.push_str(&format!(
-                        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
-                        col * cell_size + offset,
-                        row * cell_size + offset,
-                        cell_size,
-                        cell_size,
-                        self.qr.options.color_dark
-                    ));
-                }
-            }
-        }
+    /// 转换为共享的位压缩矩阵
+    pub fn to_matrix(&self) -> QrMatrix {
+        self.qr.to_matrix()
+    }
 
-        svg.push_str("</svg>");
-        svg
+    pub fn to_svg(&self, size: i32) -> String {
+        render_svg_rects(
+            &self.qr.to_matrix(),
+            size,
+            &self.qr.options.color_dark,
+            &self.qr.options.color_light,
+        )
     }
 }
 
@@ -106,6 +147,7 @@ impl Default for QRCodeNative {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use qrcode_rust_shared::qr_conformance as conformance;
 
     #[test]
     fn test_qrcode_basic_creation() {
@@ -114,6 +156,8 @@ mod tests {
 
         assert!(qr.module_count > 0, "模块数应该大于 0");
         assert!(qr.type_number > 0, "类型号应该大于 0");
+        assert_eq!(qr.version().map(Version::get), Some(qr.type_number));
+        assert_eq!(QRCode::new().version(), None);
 
         // 检查 data_cache
         if let Some(ref data) = qr.data_cache {
@@ -146,6 +190,37 @@ mod tests {
         assert_eq!(qr.options.color_light, "#ffffff");
     }
 
+    #[test]
+    fn test_qrcode_svg_options() {
+        // height 不参与渲染，输出为边长 width 的正方形
+        let mut qr = QRCode::with_options(QRCodeOptions {
+            width: 300,
+            height: 120,
+            color_dark: String::from("#112233"),
+            ..Default::default()
+        });
+        qr.make_code("Test");
+        let count = qr.module_count;
+
+        // get_svg 使用 options.width
+        assert!(qr.get_svg().contains(r#"viewBox="0 0 300 300""#));
+
+        let mut options = qr.svg_options();
+        let svg = qr.get_svg_with_options(&options);
+        let n = count + 8;
+        assert!(svg.contains(&format!(
+            r#"viewBox="0 0 {n} {n}" width="300" height="300""#
+        )));
+        assert!(svg.contains(r##"fill="#112233""##));
+        assert!(svg.contains(r#"shape-rendering="crispEdges""#));
+
+        options.fragment = true;
+        options.title = Some(String::from("Test"));
+        let fragment = qr.get_svg_with_options(&options);
+        assert!(fragment.starts_with(r#"<g shape-rendering="crispEdges"><title>Test</title>"#));
+        assert!(fragment.ends_with("</g>"));
+    }
+
     #[test]
     fn test_qrcode_is_dark_in_range() {
         let mut qr = QRCode::new();
@@ -184,6 +259,124 @@ mod tests {
         assert!(svg.contains("</svg>"), "SVG 应该包含 </svg> 标签");
     }
 
+    #[test]
+    fn test_qrcode_custom_renderer() {
+        /// 只统计深色定位图案模块和总模块数
+        #[derive(Default)]
+        struct FinderCount {
+            finder: usize,
+            total: usize,
+        }
+
+        impl Renderer for FinderCount {
+            type Output = (usize, usize);
+
+            fn draw_module(&mut self, _x: usize, _y: usize, dark: bool, role: Option<ModuleRole>) {
+                self.total += 1;
+                if dark && role == Some(ModuleRole::Finder) {
+                    self.finder += 1;
+                }
+            }
+
+            fn finish(self) -> (usize, usize) {
+                (self.finder, self.total)
+            }
+        }
+
+        let mut qr = QRCode::new();
+        qr.make_code("Hello");
+        let count = qr.module_count as usize;
+        // 每个定位图案 7×7 中有 33 个深色模块
+        assert_eq!(
+            qr.render(2, FinderCount::default()),
+            (3 * 33, (count + 4) * (count + 4))
+        );
+
+        // 内置后端与对应的函数输出一致
+        let matrix = qr.to_matrix();
+        assert_eq!(
+            qr.render(4, RgbaRenderer::new(2, [0, 0, 0, 255], [255; 4])),
+            render_rgba(&matrix, 2, 4, [0, 0, 0, 255], [255; 4])
+        );
+        assert_eq!(qr.render(0, MatrixRenderer::default()), matrix);
+    }
+
+    #[test]
+    fn test_qrcode_pdf() {
+        let mut qr = QRCode::new();
+        qr.make_code("Hello");
+        let options = PdfOptions {
+            size_mm: 20.0,
+            dark: PdfColor::Cmyk(0.0, 0.0, 0.0, 1.0),
+            light: None,
+            compress: false,
+            ..Default::default()
+        };
+        let pdf = qr.get_pdf(&options);
+        assert_eq!(pdf, render_pdf(&qr.to_matrix(), &options));
+
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.contains("/TrimBox [0 0 56.6929 56.6929]"));
+        assert!(text.contains("0 0 0 1 k\n0 0 m\n7 0 l\n7 7 l\n0 7 l\nh\n"));
+    }
+
+    #[test]
+    fn test_qrcode_render_into() {
+        let mut qr = QRCode::new();
+        qr.make_code("Hello");
+        let count = qr.module_count as usize;
+
+        // 在 100×100 的 RGB565 帧中 (10, 20) 处叠加 2 倍大小的二维码，不画浅色
+        let mut frame = vec![0x55u8; 100 * 100 * 2];
+        let options = RasterOptions {
+            format: PixelFormat::Rgb565,
+            stride: 200,
+            x: 10,
+            y: 20,
+            scale: 2,
+            quiet_zone: 0,
+            dark: [0, 0, 0, 255],
+            light: None,
+        };
+        qr.render_into(&mut frame, &options).unwrap();
+
+        let pixel = |x: usize, y: usize| [frame[y * 200 + x * 2], frame[y * 200 + x * 2 + 1]];
+        assert_eq!(pixel(9, 20), [0x55; 2]);
+        assert_eq!(pixel(10, 20), [0; 2]); // 左上定位图案
+        assert_eq!(pixel(10 + 2, 20 + 2), [0x55; 2]); // 定位图案内圈（浅色不绘制）
+        assert_eq!(pixel(10 + count * 2, 20), [0x55; 2]);
+
+        let mut small = [0u8; 10];
+        assert!(matches!(
+            qr.render_into(&mut small, &options),
+            Err(QrError::BufferTooSmall { .. })
+        ));
+    }
+
+    #[test]
+    fn test_qrcode_terminal_output() {
+        let mut qr = QRCode::new();
+        qr.make_code("Hello");
+        let count = qr.module_count as usize;
+
+        let options = TerminalOptions::default();
+        let text = qr.get_terminal(&options);
+        // 空白边 2：每行 count + 4 个字符，两行模块一行字符
+        assert_eq!(text.lines().count(), (count + 4).div_ceil(2));
+        assert!(text.lines().all(|line| line.chars().count() == count + 4));
+
+        // 第 2 行字符是模块第 0、1 行，左上探测图案的左边缘为 █
+        let line = text.lines().nth(1).unwrap();
+        assert_eq!(line.chars().nth(2), Some('█'));
+
+        let inverted = qr.get_terminal(&TerminalOptions {
+            invert: true,
+            ..options
+        });
+        assert_eq!(inverted.lines().next().unwrap().chars().next(), Some('█'));
+    }
+
     #[test]
     fn test_qrcode_position_detection_patterns() {
         // 测试位置探测图案是否正确
@@ -301,6 +494,77 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_builder_symbol() {
+        let symbol = QrBuilder::new("Hello World")
+            .ec_level(QRErrorCorrectLevel::M)
+            .version_range(Version::new(2).unwrap()..=Version::new(10).unwrap())
+            .mask(Mask::new(4).unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(symbol.version().get(), 2);
+        assert_eq!(symbol.module_count(), 25);
+        assert_eq!(symbol.mask().get(), 4);
+        assert!(symbol.is_dark(0, 0));
+        assert!(!symbol.is_dark(-1, 0));
+
+        // 渲染参数与符号分离
+        let svg = render_svg(symbol.matrix(), 300, "#112233", "#ffffff");
+        assert!(svg.contains(r##"fill="#112233""##));
+
+        let err = QrBuilder::new(&"x".repeat(3000)).build().unwrap_err();
+        assert!(matches!(err, QrError::DataTooLong { .. }));
+    }
+
+    #[test]
+    fn test_qr_encoder() {
+        conformance::check_encoder(QRCode::with_options(QRCodeOptions {
+            correct_level: QRErrorCorrectLevel::M,
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn test_binary_payload() {
+        let mut text = QRCode::new();
+        text.make_code("Hi");
+        let mut bytes = QRCode::new();
+        bytes.make_code_bytes(b"Hi");
+        assert_eq!(text.to_matrix(), bytes.to_matrix());
+
+        // 非 UTF-8 数据按字节计数
+        let mut binary = QRCode::new();
+        binary.make_code_bytes(&[0xFF, 0x00, 0xC3, 0x80, 0xFE]);
+        assert_eq!(binary.get_module_count(), 21);
+    }
+
+    #[test]
+    fn test_module_roles() {
+        let mut qr = QRCode::new();
+        qr.make_code("Hello World");
+        conformance::check_roles(qr.roles());
+
+        // 便捷方法直接查询角色表
+        assert_eq!(qr.module_role(0, 0), Some(ModuleRole::Finder));
+        assert_eq!(
+            qr.modules_with_role(ModuleRole::Data).count(),
+            qr.roles().count(ModuleRole::Data)
+        );
+    }
+
+    #[test]
+    fn test_to_matrix_matches_is_dark() {
+        let mut qr = QRCode::new();
+        qr.make_code("Hello World");
+        let matrix = qr.to_matrix();
+        conformance::check_matrix(&qr, &matrix);
+
+        // 共享渲染器对矩阵的输出与 get_svg 一致
+        assert_eq!(render_svg(&matrix, 256, "#000000", "#ffffff"), qr.get_svg());
+        assert_eq!(qr.get_svg_with(SvgPathMode::Modules), qr.get_svg());
+        assert!(qr.get_svg_with(SvgPathMode::Contours).len() < qr.get_svg().len());
+    }
+
     #[test]
     fn test_empty_string() {
         let mut qr = QRCode::new();
@@ -319,6 +583,20 @@ mod tests {
         assert!(qr.type_number > 1, "长文本应该使用更高的类型号");
     }
 
+    #[test]
+    fn test_make_code_reselects_version() {
+        let mut qr = QRCode::new();
+        qr.make_code("Hi");
+        assert_eq!(qr.type_number, 1);
+
+        // 第二次调用按新数据重新选择版本，而不是沿用上一次的版本
+        qr.make_code(&"a".repeat(100));
+        assert!(qr.type_number > 1);
+        qr.make_code("Hi");
+        assert_eq!(qr.type_number, 1);
+        assert_eq!(qr.module_count, 21);
+    }
+
     #[test]
     fn test_complex_text_hello_world_123() {
         // 测试复杂文本 "Test QR Code 123"
@@ -404,4 +682,106 @@ mod tests {
             );
         }
     }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn test_static_macros() {
+        static HOME: StaticQrMatrix = crate::qr!("https://example.com", ec = M);
+        let symbol = QrBuilder::new("https://example.com")
+            .ec_level(EcLevel::M)
+            .build()
+            .unwrap();
+        assert_eq!(HOME.to_matrix(), *symbol.matrix());
+
+        const SVG: &str = qr_svg!("https://example.com", ec = M);
+        assert_eq!(SVG, render_svg(symbol.matrix(), 256, "#000000", "#ffffff"));
+    }
+
+    #[cfg(feature = "image-support")]
+    #[test]
+    fn test_png_output() {
+        let mut qr = QRCode::new();
+        qr.make_code("Hello PNG");
+        let count = qr.module_count as u32;
+
+        // 默认黑白为灰度图，像素与模块一一对应
+        let image = qr.to_image(3, 4).unwrap();
+        assert!(matches!(image, image::DynamicImage::ImageLuma8(_)));
+        let gray = image.to_luma8();
+        assert_eq!(gray.width(), (count + 8) * 3);
+        assert_eq!(gray.get_pixel(0, 0).0, [255]);
+        assert_eq!(gray.get_pixel(12, 12).0, [0]);
+        assert_eq!(gray.get_pixel(14, 14).0, [0]);
+
+        let png = qr.to_png_bytes().unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        let decoded = image::load_from_memory(&png).unwrap().to_luma8();
+        let module_px = 256 / count;
+        assert_eq!(decoded.width(), count * module_px);
+        for row in 0..qr.module_count {
+            for col in 0..qr.module_count {
+                let (x, y) = (col as u32 * module_px, row as u32 * module_px);
+                let expected = if qr.is_dark(row, col) { 0 } else { 255 };
+                assert_eq!(decoded.get_pixel(x, y).0, [expected]);
+            }
+        }
+
+        // 带透明度的颜色输出 RGBA
+        qr.options.color_dark = String::from("#1e40af");
+        qr.options.color_light = String::from("#ffffff00");
+        let rgba = qr.to_image(1, 0).unwrap().to_rgba8();
+        assert_eq!(rgba.get_pixel(0, 0).0, [0x1e, 0x40, 0xaf, 255]);
+        assert_eq!(rgba.get_pixel(1, 1).0, [255, 255, 255, 0]);
+
+        qr.options.color_dark = String::from("navy");
+        assert_eq!(
+            qr.to_image(1, 0).unwrap_err(),
+            QrError::InvalidColor(String::from("navy"))
+        );
+        assert!(qr.to_png_bytes().is_err());
+        assert!(QRCode::new().to_image(1, 0).is_err());
+    }
+
+    #[cfg(feature = "serde-support")]
+    #[test]
+    fn test_serde_round_trip() {
+        let options = QRCodeOptions {
+            width: 300,
+            color_dark: String::from("#123456"),
+            correct_level: QRErrorCorrectLevel::Q,
+            ..Default::default()
+        };
+        let json = options.to_json().unwrap();
+        assert!(json.contains(r#""correct_level":"Q""#));
+        let restored = QRCodeOptions::from_json(&json).unwrap();
+        assert_eq!(restored.width, 300);
+        assert_eq!(restored.height, 256);
+        assert_eq!(restored.color_dark, "#123456");
+        assert_eq!(restored.correct_level, QRErrorCorrectLevel::Q);
+
+        // 缺省字段取默认值
+        let partial = QRCodeOptions::from_json(r#"{"width":128}"#).unwrap();
+        assert_eq!(partial.width, 128);
+        assert_eq!(partial.correct_level, QRErrorCorrectLevel::H);
+
+        let symbol = QrBuilder::new("Hello World")
+            .ec_level(restored.correct_level)
+            .build()
+            .unwrap();
+        let restored_symbol = QrSymbol::from_json(&symbol.to_json().unwrap()).unwrap();
+        assert_eq!(
+            render_svg(
+                restored_symbol.matrix(),
+                restored.width,
+                &restored.color_dark,
+                &restored.color_light
+            ),
+            render_svg(
+                symbol.matrix(),
+                options.width,
+                &options.color_dark,
+                &options.color_light
+            )
+        );
+    }
 }
@@ -1,20 +1,40 @@
 //! QR Code implementation
 
-use core::fmt::Write;
-
 use qrcode_rust_shared::{
     qr_8bit_byte::QR8bitByte,
     qr_bit_buffer::BitBuffer,
-    qr_code_model::{get_type_number, QRErrorCorrectLevel, QRMode, PATTERN_POSITION_TABLE},
-    qr_polynomial::Polynomial,
+    qr_code_model::{get_type_number_bytes, EcLevel, QRErrorCorrectLevel, QRMode},
+    qr_encoder::{check_capacity, QrEncoder},
+    qr_error::{QrError, QrResult},
+    qr_mask::Mask,
+    qr_matrix::QrMatrix,
+    qr_module_role::{ModuleRole, RoleMap},
+    qr_pdf::{render_pdf, PdfOptions},
+    qr_polynomial::{rs_generator, rs_remainder},
+    qr_raster::{render_into, RasterOptions},
+    qr_render::{render, Renderer},
     qr_rs_block::get_rs_blocks,
-    qr_util::{get_bch_digit, get_length_in_bits},
+    qr_svg::{
+        render_svg, render_svg_with, render_svg_with_options, SvgOptions, SvgPathMode, SvgSize,
+    },
+    qr_symbol::QrSymbol,
+    qr_template::{format_info_bits, format_info_positions, VersionTemplate},
+    qr_terminal::{render_half_blocks, TerminalOptions},
+    qr_util::get_length_in_bits,
+    qr_version::Version,
 };
 
 /// QRCode 选项
 #[derive(Clone)]
+#[cfg_attr(
+    feature = "serde-support",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct QRCodeOptions {
+    /// SVG 与 PNG 的边长（像素），输出始终为正方形
     pub width: i32,
+    /// 保留字段，与 JS 版本的选项保持一致；渲染时不使用，输出边长由 `width` 决定
     pub height: i32,
     pub color_dark: String,
     pub color_light: String,
@@ -33,14 +53,29 @@ impl Default for QRCodeOptions {
     }
 }
 
+#[cfg(feature = "serde-support")]
+impl QRCodeOptions {
+    /// 序列化为 JSON 字符串
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    /// 从 JSON 还原，缺省字段取默认值
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
+
 /// QRCode 结构
 pub struct QRCode {
     pub options: QRCodeOptions,
     pub type_number: i32,
     pub module_count: i32,
     pub modules: Vec<Vec<Option<bool>>>,
-    pub data_cache: Option<Vec<i32>>,
+    pub data_cache: Option<Vec<u8>>,
     pub data_list: Vec<QR8bitByte>,
+    /// 每个模块的角色，由编码过程记录
+    roles: RoleMap,
 }
 
 impl QRCode {
@@ -52,6 +87,7 @@ impl QRCode {
             modules: Vec::new(),
             data_cache: None,
             data_list: Vec::new(),
+            roles: RoleMap::default(),
         }
     }
 
@@ -63,11 +99,17 @@ impl QRCode {
             modules: Vec::new(),
             data_cache: None,
             data_list: Vec::new(),
+            roles: RoleMap::default(),
         }
     }
 
     pub fn add_data(&mut self, data: &str) {
-        self.data_list.push(QR8bitByte::new(data));
+        self.add_data_bytes(data.as_bytes());
+    }
+
+    /// 追加原始字节数据（不要求是合法的 UTF-8）
+    pub fn add_data_bytes(&mut self, data: &[u8]) {
+        self.data_list.push(QR8bitByte::from_bytes(data));
         self.data_cache = None;
     }
 
@@ -82,6 +124,26 @@ impl QRCode {
         self.module_count
     }
 
+    /// 编码后的版本，编码前为 `None`
+    pub fn version(&self) -> Option<Version> {
+        Version::new(self.type_number).ok()
+    }
+
+    /// 获取指定位置模块的角色，越界返回 `None`
+    pub fn module_role(&self, row: i32, col: i32) -> Option<ModuleRole> {
+        self.roles.get(row, col)
+    }
+
+    /// 按行优先顺序遍历指定角色的模块，产出 `(row, col)`
+    pub fn modules_with_role(&self, role: ModuleRole) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.roles.iter_role(role)
+    }
+
+    /// 获取完整的角色表
+    pub fn roles(&self) -> &RoleMap {
+        &self.roles
+    }
+
     pub fn get_modules(&self) -> Option<&Vec<Vec<Option<bool>>>> {
         if self.modules.is_empty() {
             None
@@ -91,8 +153,15 @@ impl QRCode {
     }
 
     pub fn make_code(&mut self, text: &str) {
+        self.make_code_bytes(text.as_bytes());
+    }
+
+    /// 以字节模式编码原始二进制数据
+    pub fn make_code_bytes(&mut self, data: &[u8]) {
         self.data_list.clear();
-        self.add_data(text);
+        self.add_data_bytes(data);
+        // 数据已替换，按新数据重新选择版本
+        self.type_number = 0;
         self.make();
     }
 
@@ -105,176 +174,52 @@ impl QRCode {
         if self.type_number == 0 {
             let mut type_num = 1;
             for data in &self.data_list {
-                type_num = type_num.max(get_type_number(&data.data, self.options.correct_level));
+                type_num = type_num.max(get_type_number_bytes(
+                    &data.data,
+                    self.options.correct_level,
+                ));
             }
             self.type_number = type_num;
         }
 
         self.module_count = self.type_number * 4 + 17;
-        self.modules = vec![vec![None; self.module_count as usize]; self.module_count as usize];
-
-        self.setup_position_probe_pattern(0, 0);
-        self.setup_position_probe_pattern(self.module_count - 7, 0);
-        self.setup_position_probe_pattern(0, self.module_count - 7);
-        self.setup_position_adjust_pattern();
-        self.setup_timing_pattern();
+        // 功能图案和版本信息从按版本缓存的模板复制
+        let template = VersionTemplate::get(
+            Version::new(self.type_number).expect("type number selected within 1..=40"),
+        );
+        self.modules = template.to_option_rows();
+        self.roles.clone_from(template.roles());
         self.setup_type_info(test);
 
-        if self.type_number >= 7 {
-            self.setup_type_number(test);
-        }
-
         if self.data_cache.is_none() {
             self.data_cache = Some(self.create_data());
         }
 
         let data = self.data_cache.as_ref().unwrap().clone();
-        self.map_data(&data);
-    }
-
-    fn setup_position_probe_pattern(&mut self, row: i32, col: i32) {
-        for r in -1..=7 {
-            if row + r <= -1 || self.module_count <= row + r {
-                continue;
-            }
-            for c in -1..=7 {
-                if col + c <= -1 || self.module_count <= col + c {
-                    continue;
-                }
-                let is_dark = ((0..=6).contains(&r) && (c == 0 || c == 6))
-                    || ((0..=6).contains(&c) && (r == 0 || r == 6))
-                    || ((2..=4).contains(&r) && (2..=4).contains(&c));
-                self.modules[(row + r) as usize][(col + c) as usize] = Some(is_dark);
-            }
-        }
-    }
-
-    fn setup_position_adjust_pattern(&mut self) {
-        let pos = PATTERN_POSITION_TABLE[(self.type_number - 1) as usize];
-        for i in 0..pos.len() {
-            for j in 0..pos.len() {
-                let row = pos[i];
-                let col = pos[j];
-
-                // 跳过无效位置（0 表示没有位置调整图案）
-                if row == 0 || col == 0 {
-                    continue;
-                }
-
-                // 跳过与位置探测图案重叠的位置
-                // 位置探测图案在：(0,0), (module_count-7, 0), (0, module_count-7)
-                // 每个位置探测图案占 7x7，加上 1 格分隔符是 9x9
-                if row <= 8 && col <= 8 {
-                    continue; // 左上角区域
-                }
-                if row <= 8 && col >= self.module_count - 8 {
-                    continue; // 右上角区域
-                }
-                if row >= self.module_count - 8 && col <= 8 {
-                    continue; // 左下角区域
-                }
-
-                // 跳过已经设置的位置
-                if self.modules[row as usize][col as usize].is_some() {
-                    continue;
-                }
-
-                for r in -2..=2 {
-                    for c in -2..=2 {
-                        let new_row = row + r;
-                        let new_col = col + c;
-                        // 确保不越界
-                        if new_row < 0
-                            || new_row >= self.module_count
-                            || new_col < 0
-                            || new_col >= self.module_count
-                        {
-                            continue;
-                        }
-                        let is_dark = r == -2 || r == 2 || c == -2 || c == 2 || (r == 0 && c == 0);
-                        self.modules[new_row as usize][new_col as usize] = Some(is_dark);
-                    }
-                }
-            }
-        }
-    }
-
-    fn setup_timing_pattern(&mut self) {
-        for r in 8..self.module_count - 8 {
-            if self.modules[r as usize][6].is_none() {
-                self.modules[r as usize][6] = Some(r % 2 == 0);
-            }
-        }
-        for c in 8..self.module_count - 8 {
-            if self.modules[6][c as usize].is_none() {
-                self.modules[6][c as usize] = Some(c % 2 == 0);
-            }
-        }
+        let data_count = get_rs_blocks(self.type_number, self.options.correct_level)
+            .iter()
+            .map(|b| b.data_count as usize)
+            .sum();
+        self.map_data(&data, data_count);
     }
 
     fn setup_type_info(&mut self, test: bool) {
-        let g15 = (1 << 10) | (1 << 8) | (1 << 5) | (1 << 4) | (1 << 2) | (1 << 1) | (1 << 0);
-        let g15_mask = (1 << 14) | (1 << 12) | (1 << 10) | (1 << 4) | (1 << 1);
-
-        let mask_pattern = 0; // kennytm 选择的最优 mask pattern
-        let correct_level = match self.options.correct_level {
-            QRErrorCorrectLevel::L => 1,
-            QRErrorCorrectLevel::M => 0,
-            QRErrorCorrectLevel::Q => 3,
-            QRErrorCorrectLevel::H => 2,
-        };
-
-        let mut data = (correct_level << 3) | mask_pattern;
-        let mut d = data << 10;
-
-        while get_bch_digit(d) - get_bch_digit(g15) >= 0 {
-            d ^= g15 << (get_bch_digit(d) - get_bch_digit(g15));
-        }
+        // 固定使用 mask 0（kennytm 选择的最优 mask pattern），格式信息查表
+        let data = format_info_bits(self.options.correct_level, Mask::default());
 
-        data = ((data << 10) | d) ^ g15_mask;
-
-        // 垂直类型信息（第 8 列）- 从上到下
         for i in 0..15 {
             // 在 test 模式下，格式信息位设置为 false（白色）
             let bit = !test && ((data >> i) & 1) == 1;
-
-            if i < 6 {
-                // 行 0-5
-                self.modules[i as usize][8] = Some(bit);
-            } else if i < 8 {
-                // 行 7-8（跳过第6行，因为它是定时图案）
-                self.modules[(i + 1) as usize][8] = Some(bit);
-            } else {
-                // 行 module_count-7 到 module_count-1
-                self.modules[(self.module_count - 15 + i) as usize][8] = Some(bit);
-            }
-        }
-
-        // 水平类型信息（第 8 行）- 从右到左
-        for i in 0..15 {
-            let bit = !test && ((data >> i) & 1) == 1;
-
-            if i < 8 {
-                // 列 module_count-1 到 module_count-8
-                self.modules[8][(self.module_count - 1 - i) as usize] = Some(bit);
-            } else if i < 9 {
-                // 列 7
-                self.modules[8][(15 - i - 1 + 1) as usize] = Some(bit);
-            } else {
-                // 列 5 到 0
-                self.modules[8][(15 - i - 1) as usize] = Some(bit);
-            }
+            let ((r1, c1), (r2, c2)) = format_info_positions(self.module_count, i);
+            self.modules[r1 as usize][c1 as usize] = Some(bit);
+            self.modules[r2 as usize][c2 as usize] = Some(bit);
         }
 
         // 固定暗模块 (module_count-8, 8)
         self.modules[(self.module_count - 8) as usize][8] = Some(!test);
     }
 
-    fn setup_type_number(&mut self, _test: bool) {
-        // 简化版本
-    }
-
-    fn map_data(&mut self, data: &[i32]) {
+    fn map_data(&mut self, data: &[u8], data_count: usize) {
         let mut inc = -1;
         let mut row = self.module_count - 1;
         let mut bit_index = 7;
@@ -306,6 +251,11 @@ impl QRCode {
                         }
 
                         self.modules[row as usize][col_idx as usize] = Some(dark);
+                        self.roles.set(
+                            row,
+                            col_idx,
+                            ModuleRole::for_codeword(byte_index, data_count, data.len()),
+                        );
 
                         if bit_index == 0 {
                             bit_index = 7;
@@ -329,121 +279,75 @@ impl QRCode {
         }
     }
 
-    fn create_data(&self) -> Vec<i32> {
+    fn create_data(&self) -> Vec<u8> {
         let rs_blocks = get_rs_blocks(self.type_number, self.options.correct_level);
+        let total_data_count: usize = rs_blocks.iter().map(|b| b.data_count as usize).sum();
+        let total_count: usize = rs_blocks.iter().map(|b| b.total_count as usize).sum();
 
-        let mut buffer = BitBuffer::new();
+        let mut buffer = BitBuffer::with_capacity(total_data_count * 8);
 
         for data in &self.data_list {
-            buffer.put(QRMode::MODE_8BIT_BYTE, 4);
+            buffer.put(QRMode::MODE_8BIT_BYTE as u32, 4);
             buffer.put(
-                data.get_length() as i32,
-                get_length_in_bits(QRMode::MODE_8BIT_BYTE, self.type_number),
+                data.get_length() as u32,
+                get_length_in_bits(QRMode::MODE_8BIT_BYTE, self.type_number) as usize,
             );
             data.write(&mut buffer);
         }
 
-        let mut total_data_count = 0;
-        for block in &rs_blocks {
-            total_data_count += block.data_count;
-        }
-
-        if buffer.length + 4 <= total_data_count as usize * 8 {
+        // 终止符（最多 4 位），into_bytes 后自然补齐到字节边界
+        if buffer.len_bits() + 4 <= total_data_count * 8 {
             buffer.put(0, 4);
         }
 
-        while !buffer.length.is_multiple_of(8) {
-            buffer.put_bit(false);
-        }
-
-        loop {
-            if buffer.length >= total_data_count as usize * 8 {
+        let mut data = buffer.into_bytes();
+        data.truncate(total_data_count);
+        for pad in [0xEC, 0x11].into_iter().cycle() {
+            if data.len() >= total_data_count {
                 break;
             }
-            buffer.put(0xEC, 8);
-            if buffer.length >= total_data_count as usize * 8 {
-                break;
-            }
-            buffer.put(0x11, 8);
+            data.push(pad);
         }
 
-        #[cfg(test)]
-        eprintln!(
-            "DEBUG: buffer.length={}, buffer.buffer.len={}, total_data_count*8={}",
-            buffer.length,
-            buffer.buffer.len(),
-            total_data_count as usize * 8
-        );
-
-        let data = buffer.buffer;
-
-        let mut offset = 0;
-        let max_dc_count = rs_blocks.iter().map(|b| b.data_count).max().unwrap_or(0);
+        // 每块计算纠错码，生成多项式在相同纠错码数的块间复用
+        let max_dc_count = rs_blocks.iter().map(|b| b.data_count).max().unwrap_or(0) as usize;
         let max_ec_count = rs_blocks
             .iter()
             .map(|b| b.total_count - b.data_count)
             .max()
-            .unwrap_or(0);
-
-        let mut dcdata: Vec<Vec<i32>> = Vec::new();
-        let mut ecdata: Vec<Vec<i32>> = Vec::new();
+            .unwrap_or(0) as usize;
 
+        let mut ecdata: Vec<Vec<u8>> = Vec::with_capacity(rs_blocks.len());
+        let mut dc_offsets = Vec::with_capacity(rs_blocks.len());
+        let mut generator = Vec::new();
+        let mut offset = 0;
         for block in &rs_blocks {
-            let dc_count = block.data_count;
-            let ec_count = block.total_count - dc_count;
-
-            dcdata.push(data[offset as usize..(offset + dc_count) as usize].to_vec());
-            offset += dc_count;
-
-            // 生成 Reed-Solomon 纠错码
-            // 数据多项式: d(x) = d_0*x^(n-1) + d_1*x^(n-2) + ... + d_{n-1}
-            // 系数按降序排列（JS 匹配）
-            let rs_poly = Polynomial::generate_rs_poly(ec_count);
-
-            // 创建数据多项式并扩展 ec_count 个零
-            // 在降序表示中，在末尾添加零相当于乘以 x^ec_count
-            let dc = dcdata.last().unwrap();
-            let mut raw_coeff = dc.clone();
-            raw_coeff.extend(std::iter::repeat_n(0, ec_count as usize));
-            let raw_poly = Polynomial::new(raw_coeff, 0);
-
-            // 计算纠错码: (数据多项式 * x^{ec_count}) mod 生成多项式
-            let mod_poly = raw_poly.r#mod(&rs_poly);
-
-            // 提取纠错码：取 mod_poly 的最后 ec_count 个系数（最低次项）
-            // 降序排列中，最低次项在数组末尾
-            // 匹配 JS 实现: const modIndex = i + modPoly.length - ecCount;
-            let mut ec: Vec<i32> = Vec::with_capacity(ec_count as usize);
-            for i in 0..ec_count {
-                let mod_index = i + mod_poly.len() as i32 - ec_count;
-                let val = if mod_index >= 0 {
-                    mod_poly.get(mod_index as usize)
-                } else {
-                    0
-                };
-                #[cfg(test)]
-                if i < 5 {
-                    eprintln!("DEBUG: ec[{}] = {} (mod_index={})", i, val, mod_index);
-                }
-                ec.push(val);
+            let dc_count = block.data_count as usize;
+            let ec_count = (block.total_count - block.data_count) as usize;
+            if generator.len() != ec_count + 1 {
+                generator = rs_generator(ec_count);
             }
+            let mut ec = vec![0u8; ec_count];
+            rs_remainder(&data[offset..offset + dc_count], &generator, &mut ec);
             ecdata.push(ec);
+            dc_offsets.push((offset, dc_count));
+            offset += dc_count;
         }
 
-        let mut result: Vec<i32> = Vec::new();
+        let mut result: Vec<u8> = Vec::with_capacity(total_count);
 
         for i in 0..max_dc_count {
-            for (_r, item) in dcdata.iter().enumerate().take(rs_blocks.len()) {
-                if i < item.len() as i32 {
-                    result.push(item[i as usize]);
+            for &(start, len) in &dc_offsets {
+                if i < len {
+                    result.push(data[start + i]);
                 }
             }
         }
 
         for i in 0..max_ec_count {
-            for (_r, item) in ecdata.iter().enumerate().take(rs_blocks.len()) {
-                if i < item.len() as i32 {
-                    result.push(item[i as usize]);
+            for ec in &ecdata {
+                if let Some(&cw) = ec.get(i) {
+                    result.push(cw);
                 }
             }
         }
@@ -453,50 +357,65 @@ impl QRCode {
 }
 
 impl QRCode {
-    /// 生成 SVG 字符串（高性能版本 - 使用 Path 合并 + write! 优化）
+    /// 转换为共享的位压缩矩阵
+    pub fn to_matrix(&self) -> QrMatrix {
+        QrMatrix::from_option_rows(&self.modules)
+    }
+
+    /// 生成 SVG 字符串（高性能版本 - 使用 Path 合并），边长为 `options.width` 的正方形
     pub fn get_svg(&self) -> String {
-        let count = self.module_count;
-        if count == 0 {
-            return String::new();
-        }
+        render_svg(
+            &self.to_matrix(),
+            self.options.width,
+            &self.options.color_dark,
+            &self.options.color_light,
+        )
+    }
 
-        let size = 256;
-        let cell_size = size / count;
-        let actual_size = cell_size * count;
-        let offset = (size - actual_size) / 2;
-
-        // 估算容量：约 50% 模块为深色，每个模块约 25 字节
-        let estimated_dark = (count * count) as usize / 2;
-        let mut svg = String::with_capacity(350 + estimated_dark * 25);
-
-        // SVG 头部 - 使用 write! 避免临时字符串
-        write!(
-            svg,
-            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {0} {0}" width="{0}" height="{0}"><path d="M0 0h{0}v{0}H0z" fill="{1}"/><path fill="{2}" d=""#,
-            size, self.options.color_light, self.options.color_dark
-        ).unwrap();
-
-        // 使用单个 Path 绘制所有深色模块
-        // 展平二维访问为一维，减少边界检查开销
-        let modules_flat: Vec<bool> = self
-            .modules
-            .iter()
-            .flat_map(|row| row.iter().map(|&m| m.unwrap_or(false)))
-            .collect();
-
-        let count_i32 = count;
-        for (idx, is_dark) in modules_flat.iter().enumerate() {
-            if *is_dark {
-                let row = (idx as i32) / count_i32;
-                let col = (idx as i32) % count_i32;
-                let x = col * cell_size + offset;
-                let y = row * cell_size + offset;
-                write!(svg, "M{x} {y}h{cell_size}v{cell_size}h-{cell_size}z").unwrap();
-            }
+    /// 按路径写法生成 SVG：`Contours` 体积最小且没有模块间接缝，`Runs` 生成最快
+    pub fn get_svg_with(&self, mode: SvgPathMode) -> String {
+        render_svg_with(
+            &self.to_matrix(),
+            self.options.width,
+            &self.options.color_dark,
+            &self.options.color_light,
+            mode,
+        )
+    }
+
+    /// 与当前选项对应的 [`SvgOptions`]：总尺寸 `options.width`、颜色取自 `options`，其余为默认值
+    pub fn svg_options(&self) -> SvgOptions {
+        SvgOptions {
+            size: SvgSize::Total(self.options.width as f64),
+            color_dark: self.options.color_dark.clone(),
+            color_light: self.options.color_light.clone(),
+            ..Default::default()
         }
+    }
+
+    /// 按 [`SvgOptions`] 生成 SVG（模块单位的 viewBox、空白边、标题、`<g>` 片段等）
+    pub fn get_svg_with_options(&self, options: &SvgOptions) -> String {
+        render_svg_with_options(&self.to_matrix(), options)
+    }
+
+    /// 生成单页矢量 PDF（物理尺寸、颜色空间、出血和裁切标记见 [`PdfOptions`]）
+    pub fn get_pdf(&self, options: &PdfOptions) -> Vec<u8> {
+        render_pdf(&self.to_matrix(), options)
+    }
+
+    /// 用自定义后端渲染，四周加 `quiet_zone` 个模块的空白边，每段模块都带有角色信息
+    pub fn render<R: Renderer>(&self, quiet_zone: usize, renderer: R) -> R::Output {
+        render(&self.to_matrix(), quiet_zone, Some(&self.roles), renderer)
+    }
 
-        svg.push_str(r#""/></svg>"#);
-        svg
+    /// 写入调用方的像素缓冲区（视频帧、标签位图、帧缓冲），格式和布局见 [`RasterOptions`]
+    pub fn render_into(&self, buffer: &mut [u8], options: &RasterOptions) -> QrResult<()> {
+        render_into(&self.to_matrix(), buffer, options)
+    }
+
+    /// 终端输出（半块字符，一行显示两行模块），其他样式见 [`crate::render_braille`] 等
+    pub fn get_terminal(&self, options: &TerminalOptions) -> String {
+        render_half_blocks(&self.to_matrix(), options)
     }
 }
 
@@ -505,3 +424,45 @@ impl Default for QRCode {
         Self::new()
     }
 }
+
+impl QrEncoder for QRCode {
+    fn encode(&mut self, data: &[u8]) -> QrResult<QrSymbol> {
+        self.make_code_bytes(data);
+        let version = QRCode::version(self).ok_or(QrError::InvalidVersion(self.type_number))?;
+        check_capacity(data.len(), version, self.options.correct_level)?;
+        QrSymbol::from_matrix(
+            version,
+            self.options.correct_level,
+            Mask::default(),
+            QRCode::to_matrix(self),
+        )
+    }
+
+    fn module_count(&self) -> i32 {
+        self.module_count
+    }
+
+    fn is_dark(&self, row: i32, col: i32) -> bool {
+        // 固有方法越界时 panic，这里按接口约定返回 false
+        (0..self.module_count).contains(&row)
+            && (0..self.module_count).contains(&col)
+            && QRCode::is_dark(self, row, col)
+    }
+
+    fn version(&self) -> Option<Version> {
+        QRCode::version(self)
+    }
+
+    fn ec_level(&self) -> EcLevel {
+        self.options.correct_level
+    }
+
+    fn mask(&self) -> Option<Mask> {
+        // 固定使用掩码 0
+        QRCode::version(self).map(|_| Mask::default())
+    }
+
+    fn to_matrix(&self) -> QrMatrix {
+        QRCode::to_matrix(self)
+    }
+}
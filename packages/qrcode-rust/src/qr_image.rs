@@ -0,0 +1,67 @@
+//! 位图输出（`image-support` 特性）
+//!
+//! 模块按整数像素绘制，颜色取自 `QRCodeOptions`，支持带透明度的 `#rrggbbaa`。
+
+use std::io::Cursor;
+use std::path::Path;
+
+use image::error::{ImageError, ImageResult, ParameterError, ParameterErrorKind};
+use image::{DynamicImage, GrayImage, ImageOutputFormat, RgbaImage};
+use qrcode_rust_shared::{parse_color, render_rgba, QrError, QrResult};
+
+use crate::qr_code::QRCode;
+
+impl QRCode {
+    /// 渲染为位图：每个模块 `module_px × module_px` 像素，四周留 `quiet_zone` 个模块的空白边
+    ///
+    /// 深浅两色都是不透明的灰度色时返回 `ImageLuma8`（如默认的黑白），否则返回 `ImageRgba8`。
+    /// 尚未编码或颜色无法解析时返回错误。
+    pub fn to_image(&self, module_px: u32, quiet_zone: u32) -> QrResult<DynamicImage> {
+        if self.module_count == 0 {
+            return Err(QrError::InvalidVersion(self.type_number));
+        }
+        let dark = parse_color(&self.options.color_dark)?;
+        let light = parse_color(&self.options.color_light)?;
+
+        let size = (self.module_count as u32 + 2 * quiet_zone) * module_px;
+        let pixels = render_rgba(&self.to_matrix(), module_px, quiet_zone, dark, light);
+        let is_gray = |[r, g, b, a]: [u8; 4]| r == g && g == b && a == u8::MAX;
+        let image = if is_gray(dark) && is_gray(light) {
+            let luma = pixels.chunks_exact(4).map(|px| px[0]).collect();
+            DynamicImage::ImageLuma8(GrayImage::from_raw(size, size, luma).expect("size matches"))
+        } else {
+            DynamicImage::ImageRgba8(RgbaImage::from_raw(size, size, pixels).expect("size matches"))
+        };
+        Ok(image)
+    }
+
+    /// 保存为 PNG，尺寸规则同 [`to_png_bytes`](Self::to_png_bytes)
+    pub fn save_png(&self, path: impl AsRef<Path>) -> ImageResult<()> {
+        self.to_image(self.png_module_px(), 0)
+            .map_err(image_error)?
+            .save_with_format(path, image::ImageFormat::Png)
+    }
+
+    /// 编码为 PNG 字节
+    ///
+    /// 与 `get_svg` 一致不加空白边，每个模块取 `options.width / module_count` 像素（至少 1），
+    /// 需要精确尺寸或空白边时使用 [`to_image`](Self::to_image)。
+    pub fn to_png_bytes(&self) -> ImageResult<Vec<u8>> {
+        let image = self
+            .to_image(self.png_module_px(), 0)
+            .map_err(image_error)?;
+        let mut bytes = Vec::new();
+        image.write_to(&mut Cursor::new(&mut bytes), ImageOutputFormat::Png)?;
+        Ok(bytes)
+    }
+
+    fn png_module_px(&self) -> u32 {
+        (self.options.width.max(0) as u32 / self.module_count.max(1) as u32).max(1)
+    }
+}
+
+fn image_error(err: QrError) -> ImageError {
+    ImageError::Parameter(ParameterError::from_kind(ParameterErrorKind::Generic(
+        err.to_string(),
+    )))
+}
@@ -0,0 +1,318 @@
+/*!
+ * Canvas 直接绘制
+ * 通过 web-sys 把二维码画进已有的 Canvas / OffscreenCanvas / 2D 上下文，
+ * 不经过中间像素缓冲区和 ImageData
+ */
+
+use crate::canvas_renderer::encode;
+use crate::CorrectLevel;
+use qrcode_rust_shared::{render, ModuleRole, QrMatrix, Renderer, SvgPathMode, SvgPathRenderer};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    CanvasRenderingContext2d, CanvasWindingRule, HtmlCanvasElement, OffscreenCanvas,
+    OffscreenCanvasRenderingContext2d, Path2d,
+};
+
+/// 模块形状
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleShape {
+    /// 方形，相邻模块合并为整块轮廓，缩放后不会出现接缝
+    Square = 0,
+    /// 圆角方形
+    Rounded = 1,
+    /// 圆点
+    Circle = 2,
+}
+
+/// Canvas 绘制样式
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct CanvasStyle {
+    x: f64,
+    y: f64,
+    size: f64,
+    color_dark: String,
+    // 空字符串表示不绘制背景（保留画布原有内容）
+    color_light: String,
+    shape: ModuleShape,
+    // 渐变颜色（左上到右下）
+    gradient: Option<(String, String)>,
+    quiet_zone: u32,
+}
+
+#[wasm_bindgen]
+impl CanvasStyle {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        CanvasStyle {
+            x: 0.0,
+            y: 0.0,
+            size: 256.0,
+            color_dark: "#000000".to_string(),
+            color_light: "#ffffff".to_string(),
+            shape: ModuleShape::Square,
+            gradient: None,
+            quiet_zone: 4,
+        }
+    }
+
+    /// 左上角在画布中的坐标（Canvas 坐标系，受当前变换影响）
+    pub fn set_position(&mut self, x: f64, y: f64) {
+        self.x = x;
+        self.y = y;
+    }
+
+    /// 二维码（含空白边）的边长，模块边长 = 边长 / 总模块数，可以是小数
+    pub fn set_size(&mut self, size: f64) {
+        self.size = size.max(0.0);
+    }
+
+    /// CSS 颜色；`light` 为空字符串时不绘制背景
+    pub fn set_colors(&mut self, dark: &str, light: &str) {
+        self.color_dark = dark.to_string();
+        self.color_light = light.to_string();
+    }
+
+    pub fn set_shape(&mut self, shape: ModuleShape) {
+        self.shape = shape;
+    }
+
+    pub fn set_gradient(&mut self, enabled: bool, color1: &str, color2: &str) {
+        self.gradient = enabled.then(|| (color1.to_string(), color2.to_string()));
+    }
+
+    pub fn set_quiet_zone(&mut self, zone: u32) {
+        self.quiet_zone = zone;
+    }
+}
+
+impl Default for CanvasStyle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 绘制到 `<canvas>` 元素
+#[wasm_bindgen]
+pub fn draw_qrcode_to_canvas(
+    canvas: &HtmlCanvasElement,
+    text: &str,
+    correct_level: CorrectLevel,
+    style: &CanvasStyle,
+) -> Result<(), JsValue> {
+    let context: CanvasRenderingContext2d = context_2d(canvas.get_context("2d")?)?;
+    draw_qrcode_to_context(&context, text, correct_level, style)
+}
+
+/// 绘制到 `OffscreenCanvas`（可在 Worker 中使用）
+#[wasm_bindgen]
+pub fn draw_qrcode_to_offscreen_canvas(
+    canvas: &OffscreenCanvas,
+    text: &str,
+    correct_level: CorrectLevel,
+    style: &CanvasStyle,
+) -> Result<(), JsValue> {
+    let context: OffscreenCanvasRenderingContext2d = context_2d(canvas.get_context("2d")?)?;
+    draw_qrcode_to_offscreen_context(&context, text, correct_level, style)
+}
+
+/// 绘制到已有的 `CanvasRenderingContext2D`，不改变上下文的状态
+#[wasm_bindgen]
+pub fn draw_qrcode_to_context(
+    context: &CanvasRenderingContext2d,
+    text: &str,
+    correct_level: CorrectLevel,
+    style: &CanvasStyle,
+) -> Result<(), JsValue> {
+    context.draw_matrix(&encode(text.as_bytes(), correct_level), style)
+}
+
+/// 绘制到已有的 `OffscreenCanvasRenderingContext2D`，不改变上下文的状态
+#[wasm_bindgen]
+pub fn draw_qrcode_to_offscreen_context(
+    context: &OffscreenCanvasRenderingContext2d,
+    text: &str,
+    correct_level: CorrectLevel,
+    style: &CanvasStyle,
+) -> Result<(), JsValue> {
+    context.draw_matrix(&encode(text.as_bytes(), correct_level), style)
+}
+
+/// 把 `getContext("2d")` 的结果转换为具体的上下文类型
+fn context_2d<T: JsCast>(context: Option<js_sys::Object>) -> Result<T, JsValue> {
+    context
+        .ok_or_else(|| JsValue::from_str("2d context is not available"))?
+        .dyn_into::<T>()
+        .map_err(JsValue::from)
+}
+
+/// 两种 2D 上下文的 API 相同但类型不同，绘制逻辑由宏为两者各实现一次
+trait DrawMatrix {
+    /// 将任意实现生成的矩阵按样式绘制
+    fn draw_matrix(&self, matrix: &QrMatrix, style: &CanvasStyle) -> Result<(), JsValue>;
+}
+
+macro_rules! impl_draw_matrix {
+    ($context:ty) => {
+        impl DrawMatrix for $context {
+            fn draw_matrix(&self, matrix: &QrMatrix, style: &CanvasStyle) -> Result<(), JsValue> {
+                let total = (matrix.width() + style.quiet_zone as usize * 2) as f64;
+                if total == 0.0 || style.size == 0.0 {
+                    return Ok(());
+                }
+                let path = Path2d::new_with_path_string(&module_path(
+                    matrix,
+                    style.quiet_zone,
+                    style.shape,
+                ))?;
+
+                // 先保存状态，背景与模块的填充色、变换都在 restore 时还原（出错时也还原）
+                self.save();
+                let result = (|| {
+                    if !style.color_light.is_empty() {
+                        self.set_fill_style_str(&style.color_light);
+                        self.fill_rect(style.x, style.y, style.size, style.size);
+                    }
+
+                    // 路径以模块为单位，用变换缩放到目标位置和尺寸
+                    let module = style.size / total;
+                    self.translate(style.x, style.y)?;
+                    self.scale(module, module)?;
+                    match &style.gradient {
+                        Some((color1, color2)) => {
+                            let gradient = self.create_linear_gradient(0.0, 0.0, total, total);
+                            gradient.add_color_stop(0.0, color1)?;
+                            gradient.add_color_stop(1.0, color2)?;
+                            self.set_fill_style_canvas_gradient(&gradient);
+                        }
+                        None => self.set_fill_style_str(&style.color_dark),
+                    }
+                    self.fill_with_path_2d_and_winding(&path, CanvasWindingRule::Evenodd);
+                    Ok(())
+                })();
+                self.restore();
+                result
+            }
+        }
+    };
+}
+
+impl_draw_matrix!(CanvasRenderingContext2d);
+impl_draw_matrix!(OffscreenCanvasRenderingContext2d);
+
+/// 以模块为单位的 SVG 路径数据（含空白边偏移），交给 `Path2D` 解析
+fn module_path(matrix: &QrMatrix, quiet_zone: u32, shape: ModuleShape) -> String {
+    let quiet_zone = quiet_zone as usize;
+    match shape {
+        ModuleShape::Square => render(
+            matrix,
+            quiet_zone,
+            None,
+            SvgPathRenderer::new(SvgPathMode::Contours, 1, 0),
+        ),
+        _ => render(
+            matrix,
+            quiet_zone,
+            None,
+            ShapePath {
+                shape,
+                d: String::with_capacity(matrix.count_dark() * 64),
+            },
+        ),
+    }
+}
+
+/// 每个深色模块一个独立的圆点或圆角方形
+struct ShapePath {
+    shape: ModuleShape,
+    d: String,
+}
+
+impl Renderer for ShapePath {
+    type Output = String;
+
+    fn draw_module(&mut self, x: usize, y: usize, dark: bool, _role: Option<ModuleRole>) {
+        if !dark {
+            return;
+        }
+        match self.shape {
+            ModuleShape::Circle => self.d.push_str(&format!(
+                "M{} {}.5a.5 .5 0 1 0 1 0a.5 .5 0 1 0 -1 0z",
+                x, y
+            )),
+            // 圆角半径 0.3 个模块
+            _ => self.d.push_str(&format!(
+                "M{}.3 {}h.4a.3 .3 0 0 1 .3 .3v.4a.3 .3 0 0 1 -.3 .3h-.4a.3 .3 0 0 1 -.3 -.3v-.4a.3 .3 0 0 1 .3 -.3z",
+                x, y
+            )),
+        }
+    }
+
+    fn finish(self) -> String {
+        self.d
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qrcode_rust_shared::qr_conformance::rasterize_path;
+    use qrcode_rust_shared::QrBuilder;
+
+    #[test]
+    fn test_shape_paths() {
+        let mut m = QrMatrix::square(2);
+        m.set(1, 0, true);
+
+        // 空白边 1：模块 (1, 0) 位于网格 (2, 1)
+        assert_eq!(
+            module_path(&m, 1, ModuleShape::Circle),
+            "M2 1.5a.5 .5 0 1 0 1 0a.5 .5 0 1 0 -1 0z"
+        );
+        assert_eq!(
+            module_path(&m, 1, ModuleShape::Rounded),
+            "M2.3 1h.4a.3 .3 0 0 1 .3 .3v.4a.3 .3 0 0 1 -.3 .3h-.4a.3 .3 0 0 1 -.3 -.3v-.4a.3 .3 0 0 1 .3 -.3z"
+        );
+        assert_eq!(module_path(&m, 0, ModuleShape::Square), "M1 0h1v1h-1z");
+    }
+
+    #[test]
+    fn test_square_fills_dark_modules() {
+        let symbol = QrBuilder::new("Hello").build().unwrap();
+        let matrix = symbol.matrix();
+        for quiet_zone in [0, 4] {
+            let total = matrix.width() + quiet_zone * 2;
+            let d = module_path(matrix, quiet_zone as u32, ModuleShape::Square);
+            assert_eq!(
+                rasterize_path(&d, total),
+                matrix.with_quiet_zone(quiet_zone),
+                "quiet zone {quiet_zone}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_shape_subpaths_on_dark_modules() {
+        let symbol = QrBuilder::new("Hello").build().unwrap();
+        let matrix = symbol.matrix();
+        let quiet_zone = 4;
+        let expected: Vec<_> = matrix.dark_modules().collect();
+        for shape in [ModuleShape::Circle, ModuleShape::Rounded] {
+            // 每个子路径的起点 `M x y` 落在对应深色模块的格子内（不含空白边）
+            let origins: Vec<_> = module_path(matrix, quiet_zone as u32, shape)
+                .split('M')
+                .skip(1)
+                .map(|subpath| {
+                    let mut xy = subpath
+                        .split(|c: char| c != '.' && !c.is_ascii_digit())
+                        .take(2)
+                        .map(|n| n.parse::<f64>().unwrap().floor() as usize - quiet_zone);
+                    (xy.next().unwrap(), xy.next().unwrap())
+                })
+                .collect();
+            assert_eq!(origins, expected, "{shape:?}");
+        }
+    }
+}
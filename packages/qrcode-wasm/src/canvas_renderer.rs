@@ -1,11 +1,13 @@
 /*!
  * Canvas 渲染器
  * 直接操作 Canvas 像素数据，避免创建 SVG 字符串
+ * 二维码居中铺满画布，模块边长可以是小数（抗锯齿或取整到像素），支持 devicePixelRatio
  */
 
-use crate::qr_code::get_type_number;
+use crate::qr_code::get_type_number_bytes;
 use crate::qr_code_model::QRCodeModel;
-use crate::qr_rs_block::QRErrorCorrectLevel;
+use crate::CorrectLevel;
+use qrcode_rust_shared::{render_rgba_fit, QrMatrix, Scaling};
 use wasm_bindgen::prelude::*;
 
 /// Canvas 渲染器
@@ -15,6 +17,8 @@ pub struct CanvasRenderer {
     height: u32,
     color_dark: [u8; 4],
     color_light: [u8; 4],
+    device_pixel_ratio: f64,
+    scaling: Scaling,
 }
 
 #[wasm_bindgen]
@@ -26,9 +30,41 @@ impl CanvasRenderer {
             height,
             color_dark: [0, 0, 0, 255],        // 黑色
             color_light: [255, 255, 255, 255], // 白色
+            device_pixel_ratio: 1.0,
+            scaling: Scaling::Antialias,
         }
     }
 
+    /// 设置设备像素比（`window.devicePixelRatio`），输出像素尺寸为 CSS 尺寸乘以该值
+    pub fn set_device_pixel_ratio(&mut self, ratio: f64) {
+        self.device_pixel_ratio = if ratio.is_finite() && ratio > 0.0 {
+            ratio
+        } else {
+            1.0
+        };
+    }
+
+    /// 模块边长不是整数像素时是否抗锯齿（默认开启）；关闭后模块边界取整到像素，只有两种颜色
+    pub fn set_antialias(&mut self, antialias: bool) {
+        self.scaling = if antialias {
+            Scaling::Antialias
+        } else {
+            Scaling::Snap
+        };
+    }
+
+    /// 输出的像素宽度（Canvas 的 `width` 属性和 `ImageData` 宽度）
+    #[wasm_bindgen(getter)]
+    pub fn pixel_width(&self) -> u32 {
+        (self.width as f64 * self.device_pixel_ratio).round() as u32
+    }
+
+    /// 输出的像素高度
+    #[wasm_bindgen(getter)]
+    pub fn pixel_height(&self) -> u32 {
+        (self.height as f64 * self.device_pixel_ratio).round() as u32
+    }
+
     /// 设置颜色 (RGBA)
     #[allow(clippy::too_many_arguments)]
     pub fn set_colors(
@@ -47,127 +83,28 @@ impl CanvasRenderer {
     }
 
     /// 生成 QRCode 并返回像素数据 (RGBA)
-    /// 返回 Uint8Array，可以直接用于 ImageData
-    pub fn render(&self, text: &str, correct_level: i32) -> Result<Vec<u8>, JsValue> {
-        let level = match correct_level {
-            1 => QRErrorCorrectLevel::L,
-            0 => QRErrorCorrectLevel::M,
-            3 => QRErrorCorrectLevel::Q,
-            _ => QRErrorCorrectLevel::H,
-        };
-
-        let type_number = get_type_number(text, level);
-        let mut model = QRCodeModel::new(type_number, level);
-        model.add_data(text);
-        model.make();
-
-        let module_count = model.get_module_count() as u32;
-        let cell_size = self.width / module_count;
-
-        // 创建像素缓冲区
-        let mut pixels = vec![0u8; (self.width * self.height * 4) as usize];
-
-        // 填充背景色
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let idx = ((y * self.width + x) * 4) as usize;
-                pixels[idx] = self.color_light[0];
-                pixels[idx + 1] = self.color_light[1];
-                pixels[idx + 2] = self.color_light[2];
-                pixels[idx + 3] = self.color_light[3];
-            }
-        }
-
-        // 绘制 QRCode 模块
-        for row in 0..module_count {
-            for col in 0..module_count {
-                if model.is_dark(row as i32, col as i32) {
-                    let start_y = row * cell_size;
-                    let start_x = col * cell_size;
-
-                    for dy in 0..cell_size {
-                        for dx in 0..cell_size {
-                            let y = start_y + dy;
-                            let x = start_x + dx;
-
-                            if y < self.height && x < self.width {
-                                let idx = ((y * self.width + x) * 4) as usize;
-                                pixels[idx] = self.color_dark[0];
-                                pixels[idx + 1] = self.color_dark[1];
-                                pixels[idx + 2] = self.color_dark[2];
-                                pixels[idx + 3] = self.color_dark[3];
-                            }
-                        }
-                    }
-                }
-            }
-        }
+    /// 返回 Uint8Array，可以直接用于 `pixel_width × pixel_height` 的 ImageData
+    pub fn render(&self, text: &str, correct_level: CorrectLevel) -> Result<Vec<u8>, JsValue> {
+        Ok(self.render_matrix(&encode(text.as_bytes(), correct_level), 0))
+    }
 
-        Ok(pixels)
+    /// 渲染二进制数据（JS 侧传入 `Uint8Array`）
+    pub fn render_bytes(
+        &self,
+        data: &[u8],
+        correct_level: CorrectLevel,
+    ) -> Result<Vec<u8>, JsValue> {
+        Ok(self.render_matrix(&encode(data, correct_level), 0))
     }
 
     /// 生成带边距的 QRCode 像素数据
     pub fn render_with_quiet_zone(
         &self,
         text: &str,
-        correct_level: i32,
+        correct_level: CorrectLevel,
         quiet_zone: u32,
     ) -> Result<Vec<u8>, JsValue> {
-        let level = match correct_level {
-            1 => QRErrorCorrectLevel::L,
-            0 => QRErrorCorrectLevel::M,
-            3 => QRErrorCorrectLevel::Q,
-            _ => QRErrorCorrectLevel::H,
-        };
-
-        let type_number = get_type_number(text, level);
-        let mut model = QRCodeModel::new(type_number, level);
-        model.add_data(text);
-        model.make();
-
-        let module_count = model.get_module_count() as u32;
-        let total_modules = module_count + quiet_zone * 2;
-        let cell_size = self.width / total_modules;
-
-        let mut pixels = vec![0u8; (self.width * self.height * 4) as usize];
-
-        // 填充背景
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let idx = ((y * self.width + x) * 4) as usize;
-                pixels[idx] = self.color_light[0];
-                pixels[idx + 1] = self.color_light[1];
-                pixels[idx + 2] = self.color_light[2];
-                pixels[idx + 3] = self.color_light[3];
-            }
-        }
-
-        // 绘制 QRCode
-        for row in 0..module_count {
-            for col in 0..module_count {
-                if model.is_dark(row as i32, col as i32) {
-                    let start_y = (row + quiet_zone) * cell_size;
-                    let start_x = (col + quiet_zone) * cell_size;
-
-                    for dy in 0..cell_size {
-                        for dx in 0..cell_size {
-                            let y = start_y + dy;
-                            let x = start_x + dx;
-
-                            if y < self.height && x < self.width {
-                                let idx = ((y * self.width + x) * 4) as usize;
-                                pixels[idx] = self.color_dark[0];
-                                pixels[idx + 1] = self.color_dark[1];
-                                pixels[idx + 2] = self.color_dark[2];
-                                pixels[idx + 3] = self.color_dark[3];
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        Ok(pixels)
+        Ok(self.render_matrix(&encode(text.as_bytes(), correct_level), quiet_zone))
     }
 
     /// 获取推荐的 Canvas 尺寸
@@ -176,6 +113,32 @@ impl CanvasRenderer {
     }
 }
 
+impl CanvasRenderer {
+    /// 将任意实现生成的矩阵渲染为 RGBA 像素数据
+    pub fn render_matrix(&self, matrix: &QrMatrix, quiet_zone: u32) -> Vec<u8> {
+        // 二维码边长取画布较短的一边并居中，模块边长 = 边长 / 总模块数，可以是小数
+        render_rgba_fit(
+            matrix,
+            quiet_zone,
+            self.pixel_width(),
+            self.pixel_height(),
+            self.scaling,
+            self.color_dark,
+            self.color_light,
+        )
+    }
+}
+
+/// 按纠错级别编码字节数据
+pub(crate) fn encode(data: &[u8], correct_level: CorrectLevel) -> QrMatrix {
+    let level = correct_level.into();
+    let type_number = get_type_number_bytes(data, level);
+    let mut model = QRCodeModel::new(type_number, level);
+    model.add_data_bytes(data);
+    model.make();
+    model.to_matrix()
+}
+
 impl Default for CanvasRenderer {
     fn default() -> Self {
         Self::new(256, 256)
@@ -186,7 +149,7 @@ impl Default for CanvasRenderer {
 #[wasm_bindgen]
 pub fn render_qrcode_to_pixels(text: &str, width: u32, height: u32) -> Vec<u8> {
     let renderer = CanvasRenderer::new(width, height);
-    renderer.render(text, 2).unwrap_or_default()
+    renderer.render(text, CorrectLevel::H).unwrap_or_default()
 }
 
 /// 批量渲染到像素数组 (返回 js_sys::Array)
@@ -195,7 +158,7 @@ pub fn render_qrcode_batch_pixels(texts: Vec<String>, width: u32, height: u32) -
     let renderer = CanvasRenderer::new(width, height);
     let result = js_sys::Array::new();
     for text in texts {
-        let pixels = renderer.render(&text, 2).unwrap_or_default();
+        let pixels = renderer.render(&text, CorrectLevel::H).unwrap_or_default();
         result.push(&js_sys::Uint8Array::from(&pixels[..]));
     }
     result
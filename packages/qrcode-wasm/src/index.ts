@@ -455,7 +455,7 @@ export function generateBatchQRCodes(texts: string[], options: { correctLevel?:
  */
 export function generateBatchQRCodesCached(texts: string[], options: { correctLevel?: CorrectLevel; size?: number } = {}): string[] {
   return texts.map(text => {
-    getCachedQRCode(text, options.correctLevel || CorrectLevel.H);
+    getCachedQRCode(text, options.correctLevel ?? CorrectLevel.H);
     return generate_qrcode_fast(text, options.size || 256);
   });
 }
@@ -476,9 +476,9 @@ export function generateQRCodeAsync(
     const useCache = options.cache !== false;
     let qr: QRCodeCore;
     if (useCache) {
-      qr = getCachedQRCode(text, options.correctLevel || CorrectLevel.H);
+      qr = getCachedQRCode(text, options.correctLevel ?? CorrectLevel.H);
     } else {
-      qr = new QRCodeCore(text, options.correctLevel || CorrectLevel.H);
+      qr = new QRCodeCore(text, options.correctLevel ?? CorrectLevel.H);
     }
     const svg = options.styled
       ? qr.toStyledSVG({ size: options.size || 256, ...options.style })
@@ -520,9 +520,9 @@ export function generateQRCodeAsyncAdvanced(
     const useCache = options.cache !== false;
     let qr: QRCodeCore;
     if (useCache) {
-      qr = getCachedQRCode(text, options.correctLevel || CorrectLevel.H);
+      qr = getCachedQRCode(text, options.correctLevel ?? CorrectLevel.H);
     } else {
-      qr = new QRCodeCore(text, options.correctLevel || CorrectLevel.H);
+      qr = new QRCodeCore(text, options.correctLevel ?? CorrectLevel.H);
     }
     const svg = options.styled
       ? qr.toStyledSVG({ size: options.size || 256, ...options.style })
@@ -9,15 +9,23 @@ mod qr_util;
 mod utils;
 
 // 新增模块
+mod canvas_draw;
 mod canvas_renderer;
 mod qr_generator;
 mod qr_styled;
 
 use qr_code::{QRCode, QRCodeOptions};
-use qr_rs_block::QRErrorCorrectLevel;
+use qrcode_rust_shared::{
+    check_capacity, render_svg_rects, EcLevel, Mask, ModuleRole as SharedModuleRole,
+    QRErrorCorrectLevel, QrEncoder, QrMatrix, QrResult, QrSymbol, Version,
+};
 use wasm_bindgen::prelude::*;
 
 // 导出新增模块
+pub use canvas_draw::{
+    draw_qrcode_to_canvas, draw_qrcode_to_context, draw_qrcode_to_offscreen_canvas,
+    draw_qrcode_to_offscreen_context, CanvasStyle, ModuleShape,
+};
 pub use canvas_renderer::{render_qrcode_batch_pixels, render_qrcode_to_pixels, CanvasRenderer};
 #[cfg(feature = "parallel")]
 pub use qr_generator::generate_qrcode_parallel;
@@ -77,6 +85,76 @@ impl From<CorrectLevel> for QRErrorCorrectLevel {
     }
 }
 
+impl From<QRErrorCorrectLevel> for CorrectLevel {
+    fn from(level: QRErrorCorrectLevel) -> Self {
+        match level {
+            QRErrorCorrectLevel::L => CorrectLevel::L,
+            QRErrorCorrectLevel::M => CorrectLevel::M,
+            QRErrorCorrectLevel::Q => CorrectLevel::Q,
+            QRErrorCorrectLevel::H => CorrectLevel::H,
+        }
+    }
+}
+
+/// 解析纠错级别字符串（"L" / "M" / "Q" / "H"，不区分大小写），无效值抛出异常
+#[wasm_bindgen]
+pub fn parse_correct_level(level: &str) -> Result<CorrectLevel, JsValue> {
+    level
+        .parse::<QRErrorCorrectLevel>()
+        .map(CorrectLevel::from)
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// 模块角色
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleRole {
+    Finder = 0,
+    Separator = 1,
+    Alignment = 2,
+    Timing = 3,
+    FormatInfo = 4,
+    VersionInfo = 5,
+    DarkModule = 6,
+    Data = 7,
+    ErrorCorrection = 8,
+    Remainder = 9,
+}
+
+impl From<SharedModuleRole> for ModuleRole {
+    fn from(role: SharedModuleRole) -> Self {
+        match role {
+            SharedModuleRole::Finder => ModuleRole::Finder,
+            SharedModuleRole::Separator => ModuleRole::Separator,
+            SharedModuleRole::Alignment => ModuleRole::Alignment,
+            SharedModuleRole::Timing => ModuleRole::Timing,
+            SharedModuleRole::FormatInfo => ModuleRole::FormatInfo,
+            SharedModuleRole::VersionInfo => ModuleRole::VersionInfo,
+            SharedModuleRole::DarkModule => ModuleRole::DarkModule,
+            SharedModuleRole::Data => ModuleRole::Data,
+            SharedModuleRole::ErrorCorrection => ModuleRole::ErrorCorrection,
+            SharedModuleRole::Remainder => ModuleRole::Remainder,
+        }
+    }
+}
+
+impl From<ModuleRole> for SharedModuleRole {
+    fn from(role: ModuleRole) -> Self {
+        match role {
+            ModuleRole::Finder => SharedModuleRole::Finder,
+            ModuleRole::Separator => SharedModuleRole::Separator,
+            ModuleRole::Alignment => SharedModuleRole::Alignment,
+            ModuleRole::Timing => SharedModuleRole::Timing,
+            ModuleRole::FormatInfo => SharedModuleRole::FormatInfo,
+            ModuleRole::VersionInfo => SharedModuleRole::VersionInfo,
+            ModuleRole::DarkModule => SharedModuleRole::DarkModule,
+            ModuleRole::Data => SharedModuleRole::Data,
+            ModuleRole::ErrorCorrection => SharedModuleRole::ErrorCorrection,
+            ModuleRole::Remainder => SharedModuleRole::Remainder,
+        }
+    }
+}
+
 /// QRCode WASM 包装器（向后兼容）
 #[wasm_bindgen]
 pub struct QRCodeWasm {
@@ -109,6 +187,11 @@ impl QRCodeWasm {
         self.qr.make_code(text);
     }
 
+    /// 以字节模式编码原始二进制数据（JS 侧传入 `Uint8Array`）
+    pub fn make_code_bytes(&mut self, data: &[u8]) {
+        self.qr.make_code_bytes(data);
+    }
+
     /// 获取模块数量
     pub fn get_module_count(&self) -> i32 {
         self.qr.get_module_count()
@@ -145,45 +228,15 @@ impl QRCodeWasm {
 
     /// 获取 SVG 字符串
     pub fn get_svg(&self) -> String {
-        let count = self.qr.get_module_count();
-        if count == 0 {
-            return String::new();
+        match self.qr.to_matrix() {
+            Some(matrix) => render_svg_rects(
+                &matrix,
+                256,
+                &self.qr.options.color_dark,
+                &self.qr.options.color_light,
+            ),
+            None => String::new(),
         }
-
-        let size = 256;
-        let cell_size = size / count;
-        let actual_size = cell_size * count;
-        let offset = (size - actual_size) / 2; // 居中偏移
-
-        let mut svg = format!(
-            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {} {}" width="{}" height="{}">"#,
-            size, size, size, size
-        );
-
-        // 背景
-        svg.push_str(&format!(
-            r#"<rect width="{}" height="{}" fill="{}"/>"#,
-            size, size, self.qr.options.color_light
-        ));
-
-        // 使用 rect 绘制每个模块，确保定位图案正确显示
-        for row in 0..count {
-            for col in 0..count {
-                if self.qr.is_dark(row, col) {
-                    svg.push_str(&format!(
-                        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
-                        col * cell_size + offset,
-                        row * cell_size + offset,
-                        cell_size,
-                        cell_size,
-                        self.qr.options.color_dark
-                    ));
-                }
-            }
-        }
-
-        svg.push_str("</svg>");
-        svg
     }
 }
 
@@ -193,6 +246,50 @@ impl Default for QRCodeWasm {
     }
 }
 
+impl QrEncoder for QRCodeWasm {
+    fn encode(&mut self, data: &[u8]) -> QrResult<QrSymbol> {
+        let level = self.qr.options.correct_level;
+        // 超出版本 40 的数据会让核心 panic，先行检查
+        check_capacity(data.len(), Version::MAX, level)?;
+        self.qr.make_code_bytes(data);
+        let version = QrEncoder::version(self).expect("make_code_bytes sets a model");
+        check_capacity(data.len(), version, level)?;
+        QrSymbol::from_matrix(
+            version,
+            level,
+            QrEncoder::mask(self).expect("make sets a mask"),
+            QrEncoder::to_matrix(self),
+        )
+    }
+
+    fn module_count(&self) -> i32 {
+        self.qr.get_module_count()
+    }
+
+    fn is_dark(&self, row: i32, col: i32) -> bool {
+        // 核心越界时 panic，这里按接口约定返回 false
+        let count = self.qr.get_module_count();
+        (0..count).contains(&row) && (0..count).contains(&col) && self.qr.is_dark(row, col)
+    }
+
+    fn version(&self) -> Option<Version> {
+        let model = self.qr.model.as_ref()?;
+        Version::new(model.type_number).ok()
+    }
+
+    fn ec_level(&self) -> EcLevel {
+        self.qr.options.correct_level
+    }
+
+    fn mask(&self) -> Option<Mask> {
+        self.qr.model.as_ref()?.mask
+    }
+
+    fn to_matrix(&self) -> QrMatrix {
+        self.qr.to_matrix().unwrap_or_else(|| QrMatrix::square(0))
+    }
+}
+
 /// 生成 QRCode 并返回模块数据（向后兼容）
 #[wasm_bindgen]
 pub fn generate_qrcode(text: &str, correct_level: CorrectLevel) -> QRCodeWasm {
@@ -201,6 +298,14 @@ pub fn generate_qrcode(text: &str, correct_level: CorrectLevel) -> QRCodeWasm {
     qr
 }
 
+/// 生成二进制数据的 QRCode（`Uint8Array`）
+#[wasm_bindgen]
+pub fn generate_qrcode_bytes(data: &[u8], correct_level: CorrectLevel) -> QRCodeWasm {
+    let mut qr = QRCodeWasm::with_options(256, 256, correct_level);
+    qr.make_code_bytes(data);
+    qr
+}
+
 /// 获取版本号
 #[wasm_bindgen]
 pub fn version() -> String {
@@ -218,6 +323,7 @@ pub fn get_version_info() -> String {
             "batch_generation",
             "styled_qrcode",
             "canvas_renderer",
+            "canvas_draw",
             "instance_reuse"
         ]
     }"#,
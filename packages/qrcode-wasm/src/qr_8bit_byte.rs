@@ -10,22 +10,18 @@ use crate::qr_util::QRMode;
 pub struct QR8bitByte {
     #[allow(dead_code)]
     pub mode: QRMode,
-    #[allow(dead_code)]
-    pub data: String,
     pub parsed_data: Vec<u8>,
 }
 
 impl QR8bitByte {
     /// 创建新的 8bit 字节数据
-    pub fn new(data: &str) -> Self {
-        // 使用与 JS TextEncoder 一致的方式：直接使用 UTF-8 字节
-        // 不添加 BOM，与 qrcode-js-shared 保持一致
-        let parsed_data = data.as_bytes().to_vec();
-
+    ///
+    /// 文本数据由调用方按 UTF-8 转为字节（与 JS TextEncoder 一致，不添加 BOM），
+    /// 二进制数据直接传入
+    pub fn from_bytes(data: &[u8]) -> Self {
         QR8bitByte {
             mode: QRMode::MODE_8BIT_BYTE,
-            data: data.to_string(),
-            parsed_data,
+            parsed_data: data.to_vec(),
         }
     }
 
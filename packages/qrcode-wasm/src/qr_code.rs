@@ -3,7 +3,7 @@
  * 对应 JS 中的 QRCode
  */
 use crate::qr_code_model::QRCodeModel;
-use crate::qr_rs_block::QRErrorCorrectLevel;
+use qrcode_rust_shared::{QRErrorCorrectLevel, QrMatrix};
 
 /// QRCode 限制长度表
 const QR_CODE_LIMIT_LENGTH: &[[i32; 4]] = &[
@@ -50,9 +50,9 @@ const QR_CODE_LIMIT_LENGTH: &[[i32; 4]] = &[
 ];
 
 /// 获取类型编号
-pub fn get_type_number(s_text: &str, n_correct_level: QRErrorCorrectLevel) -> i32 {
+pub fn get_type_number_bytes(data: &[u8], n_correct_level: QRErrorCorrectLevel) -> i32 {
     let mut n_type = 1;
-    let length = get_utf8_length(s_text);
+    let length = data.len() as i32;
 
     for item in QR_CODE_LIMIT_LENGTH.iter() {
         let n_limit = match n_correct_level {
@@ -76,13 +76,6 @@ pub fn get_type_number(s_text: &str, n_correct_level: QRErrorCorrectLevel) -> i3
     n_type
 }
 
-/// 获取 UTF-8 长度
-fn get_utf8_length(s_text: &str) -> i32 {
-    // 使用与 JS TextEncoder 一致的方式计算 UTF-8 字节长度
-    // 不添加 BOM，与 qrcode-js-shared 保持一致
-    s_text.len() as i32
-}
-
 /// QRCode 选项
 #[derive(Debug, Clone)]
 pub struct QRCodeOptions {
@@ -144,12 +137,20 @@ impl QRCode {
 
     /// 生成 QRCode
     pub fn make_code(&mut self, text: &str) {
-        let type_number = get_type_number(text, self.options.correct_level);
+        // 使用与 JS TextEncoder 一致的方式：直接使用 UTF-8 字节
+        // 不添加 BOM，与 qrcode-js-shared 保持一致
+        self.make_code_bytes(text.as_bytes());
+        self.options.text = text.to_string();
+    }
+
+    /// 以字节模式编码原始二进制数据
+    pub fn make_code_bytes(&mut self, data: &[u8]) {
+        let type_number = get_type_number_bytes(data, self.options.correct_level);
         let mut model = QRCodeModel::new(type_number, self.options.correct_level);
-        model.add_data(text);
+        model.add_data_bytes(data);
         model.make();
         self.model = Some(model);
-        self.options.text = text.to_string();
+        self.options.text.clear();
     }
 
     /// 获取模块数据
@@ -157,6 +158,11 @@ impl QRCode {
         self.model.as_ref().map(|m| &m.modules)
     }
 
+    /// 获取位压缩矩阵
+    pub fn to_matrix(&self) -> Option<QrMatrix> {
+        self.model.as_ref().map(|m| m.to_matrix())
+    }
+
     /// 获取模块数量
     pub fn get_module_count(&self) -> i32 {
         self.model.as_ref().map_or(0, |m| m.get_module_count())
@@ -5,9 +5,13 @@
 use crate::qr_8bit_byte::QR8bitByte;
 use crate::qr_bit_buffer::QRBitBuffer;
 use crate::qr_polynomial::QRPolynomial;
-use crate::qr_rs_block::{get_rs_blocks, QRErrorCorrectLevel};
+use crate::qr_rs_block::get_rs_blocks;
 use crate::qr_util;
 use crate::qr_util::{QRMaskPattern, QRMode};
+use qrcode_rust_shared::qr_template::{format_info_bits, format_info_positions};
+use qrcode_rust_shared::{
+    Mask, ModuleRole, QRErrorCorrectLevel, QrMatrix, RoleMap, Version, VersionTemplate,
+};
 
 pub const PAD0: u8 = 0xEC;
 pub const PAD1: u8 = 0x11;
@@ -20,6 +24,10 @@ pub struct QRCodeModel {
     pub module_count: i32,
     pub data_cache: Option<Vec<u8>>,
     pub data_list: Vec<QR8bitByte>,
+    /// 每个模块的角色，由编码过程记录
+    pub roles: RoleMap,
+    /// 选定的掩码，`make` 之前为 `None`
+    pub mask: Option<Mask>,
 }
 
 impl QRCodeModel {
@@ -32,13 +40,14 @@ impl QRCodeModel {
             module_count: 0,
             data_cache: None,
             data_list: Vec::new(),
+            roles: RoleMap::default(),
+            mask: None,
         }
     }
 
     /// 添加数据
-    pub fn add_data(&mut self, data: &str) {
-        let new_data = QR8bitByte::new(data);
-        self.data_list.push(new_data);
+    pub fn add_data_bytes(&mut self, data: &[u8]) {
+        self.data_list.push(QR8bitByte::from_bytes(data));
         self.data_cache = None;
     }
 
@@ -55,28 +64,35 @@ impl QRCodeModel {
         self.module_count
     }
 
+    /// 转换为共享的位压缩矩阵
+    pub fn to_matrix(&self) -> QrMatrix {
+        QrMatrix::from_option_rows(&self.modules)
+    }
+
     /// 生成 QRCode
     pub fn make(&mut self) {
         let best_mask_pattern = self.get_best_mask_pattern();
         self.make_impl(false, best_mask_pattern);
+        self.mask = Mask::new(best_mask_pattern as u8).ok();
     }
 
     /// 内部实现
     fn make_impl(&mut self, test: bool, mask_pattern: QRMaskPattern) {
         self.module_count = self.type_number * 4 + 17;
 
-        // 初始化模块矩阵为 None
-        self.modules = vec![vec![None; self.module_count as usize]; self.module_count as usize];
-
-        self.setup_position_probe_pattern(0, 0); // 左上角
-        self.setup_position_probe_pattern(self.module_count - 7, 0); // 左下角
-        self.setup_position_probe_pattern(0, self.module_count - 7); // 右上角
-        self.setup_position_adjust_pattern();
-        self.setup_timing_pattern();
+        // 功能图案和版本信息从按版本缓存的模板复制，数据区为 None
+        let template = VersionTemplate::get(
+            Version::new(self.type_number).expect("type number selected within 1..=40"),
+        );
+        self.modules = template.to_option_rows();
+        self.roles.clone_from(template.roles());
         self.setup_type_info(test, mask_pattern);
 
-        if self.type_number >= 7 {
-            self.setup_type_number(test);
+        if test {
+            // 评估掩码时版本信息按浅色计算
+            for (row, col) in self.roles.iter_role(ModuleRole::VersionInfo) {
+                self.modules[row as usize][col as usize] = Some(false);
+            }
         }
 
         if self.data_cache.is_none() {
@@ -88,126 +104,58 @@ impl QRCodeModel {
         }
 
         let data_cache = self.data_cache.as_ref().unwrap().clone();
-        self.map_data(&data_cache, mask_pattern);
-    }
-
-    /// 设置位置探测图案
-    fn setup_position_probe_pattern(&mut self, row: i32, col: i32) {
-        for r in -1..=7 {
-            if row + r <= -1 || self.module_count <= row + r {
-                continue;
-            }
-            for c in -1..=7 {
-                if col + c <= -1 || self.module_count <= col + c {
-                    continue;
-                }
-                let is_dark = ((0..=6).contains(&r) && (c == 0 || c == 6))
-                    || ((0..=6).contains(&c) && (r == 0 || r == 6))
-                    || ((2..=4).contains(&r) && (2..=4).contains(&c));
-                self.modules[(row + r) as usize][(col + c) as usize] = Some(is_dark);
-            }
-        }
+        let data_count = get_rs_blocks(self.type_number, self.error_correct_level)
+            .iter()
+            .map(|b| b.data_count as usize)
+            .sum();
+        self.map_data(&data_cache, data_count, mask_pattern);
     }
 
     /// 获取最佳遮罩模式
+    ///
+    /// 以遮罩 0 生成一次（test 模式，格式信息与版本信息按浅色计算），
+    /// 其余遮罩通过异或模板中的遮罩位图得到，按 qrcode.js 的失分规则选择。
     fn get_best_mask_pattern(&mut self) -> QRMaskPattern {
+        self.make_impl(true, QRMaskPattern::PATTERN000);
+
+        let template = VersionTemplate::get(
+            Version::new(self.type_number).expect("type number selected within 1..=40"),
+        );
+        let mut base = QrMatrix::from_option_rows(&self.modules);
+        base.xor_with(template.mask_pattern(Mask::default()));
+
         let mut min_lost_point = 0;
         let mut pattern = QRMaskPattern::PATTERN000;
 
-        for i in 0..8 {
-            self.make_impl(true, QRMaskPattern::from_i32(i));
-            let lost_point = qr_util::get_lost_point_option(&self.modules);
-            if i == 0 || min_lost_point > lost_point {
+        for mask in Mask::ALL {
+            let mut candidate = base.clone();
+            candidate.xor_with(template.mask_pattern(mask));
+            let lost_point = qr_util::get_lost_point(&candidate);
+            if mask.get() == 0 || min_lost_point > lost_point {
                 min_lost_point = lost_point;
-                pattern = QRMaskPattern::from_i32(i);
+                pattern = QRMaskPattern::from_i32(mask.get() as i32);
             }
         }
 
         pattern
     }
 
-    /// 设置时序图案
-    fn setup_timing_pattern(&mut self) {
-        for r in 8..self.module_count - 8 {
-            if self.modules[r as usize][6].is_some() {
-                continue;
-            }
-            self.modules[r as usize][6] = Some(r % 2 == 0);
-        }
-        for c in 8..self.module_count - 8 {
-            if self.modules[6][c as usize].is_some() {
-                continue;
-            }
-            self.modules[6][c as usize] = Some(c % 2 == 0);
-        }
-    }
-
-    /// 设置位置调整图案
-    fn setup_position_adjust_pattern(&mut self) {
-        let pos = qr_util::get_pattern_position(self.type_number);
-        for i in 0..pos.len() {
-            for j in 0..pos.len() {
-                let row = pos[i];
-                let col = pos[j];
-                if self.modules[row as usize][col as usize].is_some() {
-                    continue;
-                }
-                for r in -2..=2 {
-                    for c in -2..=2 {
-                        let is_dark = r == -2 || r == 2 || c == -2 || c == 2 || (r == 0 && c == 0);
-                        self.modules[(row + r) as usize][(col + c) as usize] = Some(is_dark);
-                    }
-                }
-            }
-        }
-    }
-
-    /// 设置类型编号
-    fn setup_type_number(&mut self, test: bool) {
-        let bits = qr_util::get_bch_type_number(self.type_number);
-        for i in 0..18 {
-            let mod_val = !test && ((bits >> i) & 1) == 1;
-            self.modules[(i / 3) as usize][(self.module_count - 8 - 3 + (i % 3)) as usize] =
-                Some(mod_val);
-        }
-        for i in 0..18 {
-            let mod_val = !test && ((bits >> i) & 1) == 1;
-            self.modules[(self.module_count - 8 - 3 + (i % 3)) as usize][(i / 3) as usize] =
-                Some(mod_val);
-        }
-    }
-
-    /// 设置类型信息
+    /// 设置类型信息（格式信息查表）
     fn setup_type_info(&mut self, test: bool, mask_pattern: QRMaskPattern) {
-        let data = ((self.error_correct_level as i32) << 3) | (mask_pattern as i32);
-        let bits = qr_util::get_bch_type_info(data);
+        let mask = Mask::new(mask_pattern as u8).expect("mask pattern within 0..=7");
+        let bits = format_info_bits(self.error_correct_level, mask);
 
         for i in 0..15 {
             let mod_val = !test && ((bits >> i) & 1) == 1;
-            if i < 6 {
-                self.modules[i as usize][8] = Some(mod_val);
-            } else if i < 8 {
-                self.modules[(i + 1) as usize][8] = Some(mod_val);
-            } else {
-                self.modules[(self.module_count - 15 + i) as usize][8] = Some(mod_val);
-            }
-        }
-
-        for i in 0..15 {
-            let mod_val = !test && ((bits >> i) & 1) == 1;
-            if i < 8 {
-                self.modules[8][(self.module_count - i - 1) as usize] = Some(mod_val);
-            } else if i < 9 {
-                self.modules[8][(15 - i - 1 + 1) as usize] = Some(mod_val);
-            } else {
-                self.modules[8][(15 - i - 1) as usize] = Some(mod_val);
-            }
+            let ((r1, c1), (r2, c2)) = format_info_positions(self.module_count, i);
+            self.modules[r1 as usize][c1 as usize] = Some(mod_val);
+            self.modules[r2 as usize][c2 as usize] = Some(mod_val);
         }
         self.modules[(self.module_count - 8) as usize][8] = Some(!test);
     }
 
     /// 映射数据 - 关键修复：只写入 None 位置
-    fn map_data(&mut self, data: &[u8], mask_pattern: QRMaskPattern) {
+    fn map_data(&mut self, data: &[u8], data_count: usize, mask_pattern: QRMaskPattern) {
         let mut inc = -1;
         let mut row = self.module_count - 1;
         let mut bit_index = 7;
@@ -232,6 +180,11 @@ impl QRCodeModel {
                             dark = !dark;
                         }
                         self.modules[row as usize][(col - c) as usize] = Some(dark);
+                        self.roles.set(
+                            row,
+                            col - c,
+                            ModuleRole::for_codeword(byte_index, data_count, data.len()),
+                        );
                         bit_index = if bit_index == 0 { 7 } else { bit_index - 1 };
                         if bit_index == 7 {
                             byte_index += 1;
@@ -388,3 +341,41 @@ impl QRMaskPattern {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qr_code::get_type_number_bytes;
+
+    #[test]
+    fn test_best_mask_pattern_matches_qrcode_js() {
+        // 与 qrcode.js 失分规则选出的遮罩一致（ISO 惩罚分可能选出不同的遮罩）
+        let long = "x".repeat(300);
+        let cases: [(&str, [i32; 4]); 5] = [
+            ("Hello World", [7, 7, 3, 1]),
+            ("https://github.com/veaba/wasm-qrcode", [4, 6, 7, 0]),
+            ("0123456789", [7, 7, 2, 7]),
+            ("测试中文内容", [5, 5, 1, 6]),
+            (&long, [2, 2, 7, 3]),
+        ];
+        let levels = [
+            QRErrorCorrectLevel::L,
+            QRErrorCorrectLevel::M,
+            QRErrorCorrectLevel::Q,
+            QRErrorCorrectLevel::H,
+        ];
+        for (text, masks) in cases {
+            for (level, expected) in levels.into_iter().zip(masks) {
+                let mut model =
+                    QRCodeModel::new(get_type_number_bytes(text.as_bytes(), level), level);
+                model.add_data_bytes(text.as_bytes());
+                model.make();
+                assert_eq!(
+                    model.mask.map(Mask::get),
+                    Some(expected as u8),
+                    "{text:?} {level:?}"
+                );
+            }
+        }
+    }
+}
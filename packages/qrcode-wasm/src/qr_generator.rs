@@ -3,9 +3,11 @@
  * 支持实例复用和批量生成
  */
 
-use crate::qr_code::{get_type_number, QRCodeOptions};
+use crate::qr_code::{get_type_number_bytes, QRCodeOptions};
 use crate::qr_code_model::QRCodeModel;
-use crate::qr_rs_block::QRErrorCorrectLevel;
+use crate::{CorrectLevel, ModuleRole};
+use js_sys::Uint8Array;
+use qrcode_rust_shared::{parse_color, png_data_url, render_png, render_svg_rects};
 use wasm_bindgen::prelude::*;
 
 /// 可复用的 QRCode 生成器
@@ -30,19 +32,12 @@ impl QRCodeGenerator {
     }
 
     /// 使用选项创建
-    pub fn with_options(width: i32, height: i32, correct_level: i32) -> Self {
-        let level = match correct_level {
-            1 => QRErrorCorrectLevel::L,
-            0 => QRErrorCorrectLevel::M,
-            3 => QRErrorCorrectLevel::Q,
-            _ => QRErrorCorrectLevel::H,
-        };
-
+    pub fn with_options(width: i32, height: i32, correct_level: CorrectLevel) -> Self {
         QRCodeGenerator {
             options: QRCodeOptions {
                 width,
                 height,
-                correct_level: level,
+                correct_level: correct_level.into(),
                 ..Default::default()
             },
             model: None,
@@ -51,28 +46,27 @@ impl QRCodeGenerator {
     }
 
     /// 设置选项
-    pub fn set_options(&mut self, width: i32, height: i32, correct_level: i32) {
-        let level = match correct_level {
-            1 => QRErrorCorrectLevel::L,
-            0 => QRErrorCorrectLevel::M,
-            3 => QRErrorCorrectLevel::Q,
-            _ => QRErrorCorrectLevel::H,
-        };
+    pub fn set_options(&mut self, width: i32, height: i32, correct_level: CorrectLevel) {
         self.options.width = width;
         self.options.height = height;
-        self.options.correct_level = level;
+        self.options.correct_level = correct_level.into();
     }
 
     /// 生成 QRCode（复用实例）
     pub fn generate(&mut self, text: &str) -> Result<(), JsValue> {
-        let type_number = get_type_number(text, self.options.correct_level);
+        self.generate_bytes(text.as_bytes())
+    }
+
+    /// 生成二进制数据的 QRCode（JS 侧传入 `Uint8Array`）
+    pub fn generate_bytes(&mut self, data: &[u8]) -> Result<(), JsValue> {
+        let type_number = get_type_number_bytes(data, self.options.correct_level);
 
         // 检查是否可以复用现有模型
         let need_recreate = match &self.model {
             None => true,
             Some(model) => {
                 model.type_number != type_number
-                    || model.error_correct_level as i32 != self.options.correct_level as i32
+                    || model.error_correct_level != self.options.correct_level
             }
         };
 
@@ -83,7 +77,7 @@ impl QRCodeGenerator {
         if let Some(ref mut model) = self.model {
             model.data_list.clear();
             model.data_cache = None;
-            model.add_data(text);
+            model.add_data_bytes(data);
             model.make();
             Ok(())
         } else {
@@ -101,52 +95,69 @@ impl QRCodeGenerator {
         self.model.as_ref().is_some_and(|m| m.is_dark(row, col))
     }
 
+    /// 获取指定位置模块的角色，越界或未生成时返回 undefined
+    pub fn module_role(&self, row: i32, col: i32) -> Option<ModuleRole> {
+        self.model
+            .as_ref()
+            .and_then(|m| m.roles.get(row, col))
+            .map(ModuleRole::from)
+    }
+
+    /// 获取指定角色的所有模块坐标，按 `[row0, col0, row1, col1, ...]` 排列
+    pub fn modules_with_role(&self, role: ModuleRole) -> Vec<u32> {
+        match self.model {
+            Some(ref model) => model
+                .roles
+                .iter_role(role.into())
+                .flat_map(|(row, col)| [row as u32, col as u32])
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
     /// 获取 SVG 字符串（优化版本）
     pub fn get_svg(&self) -> String {
-        let count = self.get_module_count();
-        if count == 0 {
-            return String::new();
+        match self.model {
+            Some(ref model) => render_svg_rects(
+                &model.to_matrix(),
+                self.options.width.max(self.options.height),
+                &self.options.color_dark,
+                &self.options.color_light,
+            ),
+            None => String::new(),
         }
+    }
 
-        let size = self.options.width.max(self.options.height);
-        let cell_size = size / count;
-        let actual_size = cell_size * count;
-        let offset = (size - actual_size) / 2; // 居中偏移
-
-        // 使用 String::with_capacity 预分配内存
-        let estimated_capacity = (count * count * 100) as usize;
-        let mut svg = String::with_capacity(estimated_capacity);
-
-        // SVG 头部
-        svg.push_str(&format!(
-            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {0} {0}" width="{0}" height="{0}">"#,
-            size
-        ));
-
-        // 背景
-        svg.push_str(&format!(
-            r#"<rect width="{0}" height="{0}" fill="{1}"/>"#,
-            size, self.options.color_light
-        ));
-
-        // 使用 rect 绘制每个模块，确保定位图案正确显示
-        for row in 0..count {
-            for col in 0..count {
-                if self.is_dark(row, col) {
-                    svg.push_str(&format!(
-                        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
-                        col * cell_size + offset,
-                        row * cell_size + offset,
-                        cell_size,
-                        cell_size,
-                        self.options.color_dark
-                    ));
-                }
-            }
-        }
+    /// 获取 PNG 文件字节（`Uint8Array`），未生成时为空
+    ///
+    /// 与 `get_svg` 一致不加空白边，每个模块取整数像素（`max(width, height) / 模块数`，至少 1），
+    /// 颜色支持 `#rrggbbaa` 等带透明度的写法。
+    pub fn get_png(&self) -> Result<Vec<u8>, JsValue> {
+        let Some(ref model) = self.model else {
+            return Ok(Vec::new());
+        };
+        let dark =
+            parse_color(&self.options.color_dark).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let light = parse_color(&self.options.color_light)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let module_px = self.options.width.max(self.options.height) / model.module_count.max(1);
+        Ok(render_png(
+            &model.to_matrix(),
+            module_px.max(1) as u32,
+            0,
+            dark,
+            light,
+        ))
+    }
 
-        svg.push_str("</svg>");
-        svg
+    /// 获取 PNG 的 data URL，可直接用作 `<img src>` 或下载链接，未生成时为空字符串
+    pub fn get_png_data_url(&self) -> Result<String, JsValue> {
+        let png = self.get_png()?;
+        Ok(if png.is_empty() {
+            String::new()
+        } else {
+            png_data_url(&png)
+        })
     }
 
     /// 获取模块数据作为 JSON
@@ -190,6 +201,21 @@ impl QRCodeGenerator {
         results
     }
 
+    /// 批量生成二进制数据的 QRCode
+    pub fn generate_batch_bytes(&mut self, items: Vec<Uint8Array>) -> Vec<String> {
+        let mut results = Vec::with_capacity(items.len());
+
+        for item in items {
+            if self.generate_bytes(&item.to_vec()).is_ok() {
+                results.push(self.get_svg());
+            } else {
+                results.push(String::new());
+            }
+        }
+
+        results
+    }
+
     /// 生成并返回 Uint8Array（原始模块数据）
     pub fn get_modules_raw(&self) -> Option<Vec<u8>> {
         self.model.as_ref().map(|model| {
@@ -219,22 +245,25 @@ impl Default for QRCodeGenerator {
 
 /// 批量生成工具函数
 #[wasm_bindgen]
-pub fn generate_qrcode_batch(texts: Vec<String>, correct_level: i32) -> Vec<String> {
-    let level = match correct_level {
-        1 => QRErrorCorrectLevel::L,
-        0 => QRErrorCorrectLevel::M,
-        3 => QRErrorCorrectLevel::Q,
-        _ => QRErrorCorrectLevel::H,
-    };
-
-    let mut generator = QRCodeGenerator::with_options(256, 256, level as i32);
+pub fn generate_qrcode_batch(texts: Vec<String>, correct_level: CorrectLevel) -> Vec<String> {
+    let mut generator = QRCodeGenerator::with_options(256, 256, correct_level);
     generator.generate_batch(texts)
 }
 
+/// 批量生成工具函数（二进制数据）
+#[wasm_bindgen]
+pub fn generate_qrcode_batch_bytes(
+    items: Vec<Uint8Array>,
+    correct_level: CorrectLevel,
+) -> Vec<String> {
+    let mut generator = QRCodeGenerator::with_options(256, 256, correct_level);
+    generator.generate_batch_bytes(items)
+}
+
 /// 快速生成单个 QRCode
 #[wasm_bindgen]
 pub fn generate_qrcode_fast(text: &str, size: i32) -> String {
-    let mut generator = QRCodeGenerator::with_options(size, size, 2);
+    let mut generator = QRCodeGenerator::with_options(size, size, CorrectLevel::H);
     if generator.generate(text).is_ok() {
         generator.get_svg()
     } else {
@@ -251,7 +280,7 @@ pub fn generate_qrcode_parallel(texts: Vec<String>, size: i32) -> Vec<String> {
     texts
         .par_iter()
         .map(|text| {
-            let mut generator = QRCodeGenerator::with_options(size, size, 2);
+            let mut generator = QRCodeGenerator::with_options(size, size, CorrectLevel::H);
             generator.generate(text).ok();
             generator.get_svg()
         })
@@ -263,3 +292,45 @@ pub fn generate_qrcode_parallel(texts: Vec<String>, size: i32) -> Vec<String> {
 pub fn is_parallel_supported() -> bool {
     cfg!(feature = "parallel")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qrcode_rust_shared::ModuleRole as SharedModuleRole;
+
+    #[test]
+    fn test_module_roles_flattened() {
+        let mut gen = QRCodeGenerator::new();
+        assert_eq!(gen.module_role(0, 0), None);
+        assert!(gen.modules_with_role(ModuleRole::Finder).is_empty());
+
+        gen.generate("Hello World").unwrap();
+        let count = gen.get_module_count();
+        assert_eq!(gen.module_role(0, 0), Some(ModuleRole::Finder));
+        assert_eq!(gen.module_role(6, 8), Some(ModuleRole::Timing));
+        assert_eq!(gen.module_role(count - 8, 8), Some(ModuleRole::DarkModule));
+        assert_eq!(gen.module_role(count, 0), None);
+
+        // [row, col] 成对排列，行优先
+        assert_eq!(
+            gen.modules_with_role(ModuleRole::DarkModule),
+            [count as u32 - 8, 8]
+        );
+        let finder = gen.modules_with_role(ModuleRole::Finder);
+        assert_eq!(finder.len(), 3 * 49 * 2);
+        assert_eq!(&finder[..4], [0, 0, 0, 1]);
+        for pair in finder.chunks_exact(2) {
+            let (row, col) = (pair[0] as i32, pair[1] as i32);
+            assert_eq!(gen.module_role(row, col), Some(ModuleRole::Finder));
+        }
+    }
+
+    #[test]
+    fn test_module_role_conversions() {
+        for (i, &shared) in SharedModuleRole::ALL.iter().enumerate() {
+            let role = ModuleRole::from(shared);
+            assert_eq!(role as usize, i);
+            assert_eq!(SharedModuleRole::from(role), shared);
+        }
+    }
+}
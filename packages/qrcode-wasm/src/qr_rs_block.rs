@@ -3,6 +3,8 @@
  * 对应 JS 中的 QRRSBlock
  */
 
+use qrcode_rust_shared::QRErrorCorrectLevel;
+
 /// RS 块结构
 #[derive(Debug, Clone)]
 pub struct QRRSBlock {
@@ -19,16 +21,6 @@ impl QRRSBlock {
     }
 }
 
-/// 错误纠正级别
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
-pub enum QRErrorCorrectLevel {
-    L = 1,
-    M = 0,
-    Q = 3,
-    #[default]
-    H = 2,
-}
-
 /// RS 块表 - 使用固定长度的数组
 const RS_BLOCK_TABLE: &[&[i32]] = &[
     // L, M, Q, H
@@ -226,12 +218,7 @@ fn get_rs_block_table(
     type_number: i32,
     error_correct_level: QRErrorCorrectLevel,
 ) -> &'static [i32] {
-    let index = match error_correct_level {
-        QRErrorCorrectLevel::L => (type_number - 1) * 4,
-        QRErrorCorrectLevel::M => (type_number - 1) * 4 + 1,
-        QRErrorCorrectLevel::Q => (type_number - 1) * 4 + 2,
-        QRErrorCorrectLevel::H => (type_number - 1) * 4 + 3,
-    };
+    let index = (type_number - 1) * 4 + error_correct_level.ordinal() as i32;
 
     if index >= 0 && (index as usize) < RS_BLOCK_TABLE.len() {
         RS_BLOCK_TABLE[index as usize]
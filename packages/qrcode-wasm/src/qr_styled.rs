@@ -3,9 +3,12 @@
  * 支持 Logo、圆角、渐变等效果
  */
 
-use crate::qr_code::get_type_number;
+use crate::qr_code::get_type_number_bytes;
 use crate::qr_code_model::QRCodeModel;
-use crate::qr_rs_block::QRErrorCorrectLevel;
+use crate::CorrectLevel;
+use qrcode_rust_shared::{
+    parse_color, png_data_url, render, render_png, ModuleRole, QrMatrix, Renderer,
+};
 use wasm_bindgen::prelude::*;
 
 /// QRCode 样式选项
@@ -109,18 +112,21 @@ impl StyledQRCode {
     }
 
     /// 生成 QRCode
-    pub fn generate(&mut self, text: &str, correct_level: i32) -> Result<(), JsValue> {
-        let level = match correct_level {
-            1 => QRErrorCorrectLevel::L,
-            0 => QRErrorCorrectLevel::M,
-            3 => QRErrorCorrectLevel::Q,
-            _ => QRErrorCorrectLevel::H,
-        };
+    pub fn generate(&mut self, text: &str, correct_level: CorrectLevel) -> Result<(), JsValue> {
+        self.generate_bytes(text.as_bytes(), correct_level)
+    }
 
-        let type_number = get_type_number(text, level);
+    /// 生成二进制数据的 QRCode（JS 侧传入 `Uint8Array`）
+    pub fn generate_bytes(
+        &mut self,
+        data: &[u8],
+        correct_level: CorrectLevel,
+    ) -> Result<(), JsValue> {
+        let level = correct_level.into();
+        let type_number = get_type_number_bytes(data, level);
 
         let mut model = QRCodeModel::new(type_number, level);
-        model.add_data(text);
+        model.add_data_bytes(data);
         model.make();
 
         self.model = Some(model);
@@ -129,13 +135,88 @@ impl StyledQRCode {
 
     /// 获取 SVG（带样式）
     pub fn get_styled_svg(&self) -> String {
-        if self.model.is_none() {
-            return String::new();
+        match self.model {
+            Some(ref model) => self.render_matrix(&model.to_matrix()),
+            None => String::new(),
+        }
+    }
+
+    /// 获取 PNG 文件字节（`Uint8Array`），未生成时为空
+    ///
+    /// 包含空白边与 Logo 留白区域，每个模块取整数像素；
+    /// 圆角和渐变只在 SVG 中生效，PNG 使用 `color_dark` 单色。
+    pub fn get_png(&self) -> Result<Vec<u8>, JsValue> {
+        let Some(ref model) = self.model else {
+            return Ok(Vec::new());
+        };
+        let dark =
+            parse_color(&self.style.color_dark).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let light =
+            parse_color(&self.style.color_light).map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+        let mut matrix = model.to_matrix();
+        let count = matrix.width() as i32;
+        if self.style.has_logo {
+            let logo_cells = (count as f64 * self.style.logo_ratio) as i32;
+            let logo_start = (count - logo_cells) / 2;
+            for row in logo_start..logo_start + logo_cells {
+                for col in logo_start..logo_start + logo_cells {
+                    matrix.set(col as usize, row as usize, false);
+                }
+            }
+        }
+
+        let quiet_zone = self.style.quiet_zone.max(0);
+        let module_px = (self.style.width / (count + quiet_zone * 2)).max(1);
+        Ok(render_png(
+            &matrix,
+            module_px as u32,
+            quiet_zone as u32,
+            dark,
+            light,
+        ))
+    }
+
+    /// 获取 PNG 的 data URL，可直接用作 `<img src>` 或下载链接，未生成时为空字符串
+    pub fn get_png_data_url(&self) -> Result<String, JsValue> {
+        let png = self.get_png()?;
+        Ok(if png.is_empty() {
+            String::new()
+        } else {
+            png_data_url(&png)
+        })
+    }
+
+    /// 获取模块数量
+    pub fn get_module_count(&self) -> i32 {
+        self.model.as_ref().map_or(0, |m| m.get_module_count())
+    }
+
+    /// 获取 Logo 区域信息（用于外部添加 Logo）
+    pub fn get_logo_area(&self) -> Option<Vec<i32>> {
+        if !self.style.has_logo {
+            return None;
         }
 
-        let model = self.model.as_ref().unwrap();
-        let count = model.module_count;
-        let quiet_zone = self.style.quiet_zone;
+        let count = self.get_module_count();
+        let logo_cells = (count as f64 * self.style.logo_ratio) as i32;
+        let logo_start = (count - logo_cells) / 2;
+        let cell_size = self.style.width / (count + self.style.quiet_zone * 2);
+
+        Some(vec![
+            logo_start * cell_size,
+            logo_start * cell_size,
+            logo_cells * cell_size,
+            logo_cells * cell_size,
+        ])
+    }
+}
+
+impl StyledQRCode {
+    /// 将任意实现生成的矩阵渲染为带样式的 SVG
+    pub fn render_matrix(&self, matrix: &QrMatrix) -> String {
+        let count = matrix.width() as i32;
+        let quiet_zone = self.style.quiet_zone.max(0);
         let total_count = count + quiet_zone * 2;
 
         let size = self.style.width;
@@ -198,50 +279,21 @@ impl StyledQRCode {
         };
 
         // 绘制模块
-        if self.style.border_radius > 0 {
-            // 使用圆角矩形
-            for row in 0..count {
-                for col in 0..count {
-                    // 跳过 Logo 区域
-                    if let Some((start, end)) = logo_area {
-                        if row >= start && row < end && col >= start && col < end {
-                            continue;
-                        }
-                    }
-
-                    if model.is_dark(row, col) {
-                        let x = (col + quiet_zone) * cell_size + offset;
-                        let y = (row + quiet_zone) * cell_size + offset;
-                        let radius = self.style.border_radius.min(cell_size / 4);
-                        svg.push_str(&format!(
-                            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" rx="{}" ry="{}"/>"#,
-                            x, y, cell_size, cell_size, fill_color, radius, radius
-                        ));
-                    }
-                }
-            }
-        } else {
-            // 使用 rect 绘制每个模块，确保定位图案正确显示
-            for row in 0..count {
-                for col in 0..count {
-                    // 跳过 Logo 区域
-                    if let Some((start, end)) = logo_area {
-                        if row >= start && row < end && col >= start && col < end {
-                            continue;
-                        }
-                    }
-
-                    if model.is_dark(row, col) {
-                        let x = (col + quiet_zone) * cell_size + offset;
-                        let y = (row + quiet_zone) * cell_size + offset;
-                        svg.push_str(&format!(
-                            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
-                            x, y, cell_size, cell_size, fill_color
-                        ));
-                    }
-                }
-            }
-        }
+        let radius =
+            (self.style.border_radius > 0).then(|| self.style.border_radius.min(cell_size / 4));
+        svg.push_str(&render(
+            matrix,
+            quiet_zone as usize,
+            None,
+            StyledModules {
+                cell_size,
+                offset,
+                fill: fill_color,
+                radius,
+                logo: logo_area.map(|(start, end)| (start + quiet_zone, end + quiet_zone)),
+                svg: String::with_capacity(matrix.count_dark() * 80),
+            },
+        ));
 
         // 如果有 Logo，添加 Logo 占位区域
         if self.style.has_logo {
@@ -269,29 +321,52 @@ impl StyledQRCode {
         svg.push_str("</svg>");
         svg
     }
+}
 
-    /// 获取模块数量
-    pub fn get_module_count(&self) -> i32 {
-        self.model.as_ref().map_or(0, |m| m.get_module_count())
-    }
+/// 样式化模块后端：每个深色模块一个 `<rect>`（可带圆角），跳过 Logo 区域
+struct StyledModules<'a> {
+    cell_size: i32,
+    offset: i32,
+    fill: &'a str,
+    radius: Option<i32>,
+    /// Logo 区域在网格中的起止坐标（含空白边偏移）
+    logo: Option<(i32, i32)>,
+    svg: String,
+}
 
-    /// 获取 Logo 区域信息（用于外部添加 Logo）
-    pub fn get_logo_area(&self) -> Option<Vec<i32>> {
-        if !self.style.has_logo {
-            return None;
+impl Renderer for StyledModules<'_> {
+    type Output = String;
+
+    fn draw_module(&mut self, x: usize, y: usize, dark: bool, _role: Option<ModuleRole>) {
+        if !dark {
+            return;
+        }
+        let (x, y) = (x as i32, y as i32);
+        if let Some((start, end)) = self.logo {
+            if (start..end).contains(&x) && (start..end).contains(&y) {
+                return;
+            }
         }
 
-        let count = self.get_module_count();
-        let logo_cells = (count as f64 * self.style.logo_ratio) as i32;
-        let logo_start = (count - logo_cells) / 2;
-        let cell_size = self.style.width / (count + self.style.quiet_zone * 2);
+        let (x, y, size) = (
+            x * self.cell_size + self.offset,
+            y * self.cell_size + self.offset,
+            self.cell_size,
+        );
+        match self.radius {
+            Some(radius) => self.svg.push_str(&format!(
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" rx="{}" ry="{}"/>"#,
+                x, y, size, size, self.fill, radius, radius
+            )),
+            None => self.svg.push_str(&format!(
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                x, y, size, size, self.fill
+            )),
+        }
+    }
 
-        Some(vec![
-            logo_start * cell_size,
-            logo_start * cell_size,
-            logo_cells * cell_size,
-            logo_cells * cell_size,
-        ])
+    fn finish(self) -> String {
+        self.svg
     }
 }
 
@@ -309,7 +384,7 @@ pub fn generate_rounded_qrcode(text: &str, size: i32, radius: i32) -> String {
     style.set_border_radius(radius);
 
     let mut qr = StyledQRCode::with_style(style);
-    if qr.generate(text, 2).is_ok() {
+    if qr.generate(text, CorrectLevel::H).is_ok() {
         qr.get_styled_svg()
     } else {
         String::new()
@@ -324,7 +399,7 @@ pub fn generate_qrcode_with_logo_area(text: &str, size: i32, logo_ratio: f64) ->
     style.set_logo(true, logo_ratio);
 
     let mut qr = StyledQRCode::with_style(style);
-    if qr.generate(text, 2).is_ok() {
+    if qr.generate(text, CorrectLevel::H).is_ok() {
         qr.get_styled_svg()
     } else {
         String::new()
@@ -339,7 +414,7 @@ pub fn generate_gradient_qrcode(text: &str, size: i32, color1: &str, color2: &st
     style.set_gradient(true, color1, color2);
 
     let mut qr = StyledQRCode::with_style(style);
-    if qr.generate(text, 2).is_ok() {
+    if qr.generate(text, CorrectLevel::H).is_ok() {
         qr.get_styled_svg()
     } else {
         String::new()
@@ -355,7 +430,7 @@ pub fn generate_wechat_style_qrcode(text: &str, size: i32) -> String {
     style.set_border_radius(4);
 
     let mut qr = StyledQRCode::with_style(style);
-    if qr.generate(text, 2).is_ok() {
+    if qr.generate(text, CorrectLevel::H).is_ok() {
         qr.get_styled_svg()
     } else {
         String::new()
@@ -372,7 +447,7 @@ pub fn generate_douyin_style_qrcode(text: &str, size: i32) -> String {
     style.set_border_radius(6);
 
     let mut qr = StyledQRCode::with_style(style);
-    if qr.generate(text, 2).is_ok() {
+    if qr.generate(text, CorrectLevel::H).is_ok() {
         qr.get_styled_svg()
     } else {
         String::new()
@@ -389,7 +464,7 @@ pub fn generate_alipay_style_qrcode(text: &str, size: i32) -> String {
     style.set_logo(true, 0.15);
 
     let mut qr = StyledQRCode::with_style(style);
-    if qr.generate(text, 2).is_ok() {
+    if qr.generate(text, CorrectLevel::H).is_ok() {
         qr.get_styled_svg()
     } else {
         String::new()
@@ -405,7 +480,7 @@ pub fn generate_xiaohongshu_style_qrcode(text: &str, size: i32) -> String {
     style.set_border_radius(12);
 
     let mut qr = StyledQRCode::with_style(style);
-    if qr.generate(text, 2).is_ok() {
+    if qr.generate(text, CorrectLevel::H).is_ok() {
         qr.get_styled_svg()
     } else {
         String::new()
@@ -422,7 +497,7 @@ pub fn generate_cyberpunk_style_qrcode(text: &str, size: i32) -> String {
     style.set_border_radius(2);
 
     let mut qr = StyledQRCode::with_style(style);
-    if qr.generate(text, 2).is_ok() {
+    if qr.generate(text, CorrectLevel::H).is_ok() {
         qr.get_styled_svg()
     } else {
         String::new()
@@ -438,7 +513,7 @@ pub fn generate_retro_style_qrcode(text: &str, size: i32) -> String {
     style.set_border_radius(0);
 
     let mut qr = StyledQRCode::with_style(style);
-    if qr.generate(text, 2).is_ok() {
+    if qr.generate(text, CorrectLevel::H).is_ok() {
         qr.get_styled_svg()
     } else {
         String::new()
@@ -455,7 +530,7 @@ pub fn generate_minimal_style_qrcode(text: &str, size: i32) -> String {
     style.set_quiet_zone(2);
 
     let mut qr = StyledQRCode::with_style(style);
-    if qr.generate(text, 2).is_ok() {
+    if qr.generate(text, CorrectLevel::H).is_ok() {
         qr.get_styled_svg()
     } else {
         String::new()
@@ -4,6 +4,8 @@
  */
 use crate::qr_math;
 use crate::qr_polynomial::QRPolynomial;
+use qrcode_rust_shared::qr_mask::{high_bits, same_color_blocks, window, FINDER_CORE};
+use qrcode_rust_shared::QrMatrix;
 
 /// 遮罩模式
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -31,93 +33,6 @@ pub enum QRMode {
 // 重新导出 QRErrorCorrectLevel
 // QRErrorCorrectLevel is re-exported from qr_rs_block
 
-/// 位置调整图案位置表
-const PATTERN_POSITION_TABLE: [&[i32]; 40] = [
-    &[],
-    &[6, 18],
-    &[6, 22],
-    &[6, 26],
-    &[6, 30],
-    &[6, 34],
-    &[6, 22, 38],
-    &[6, 24, 42],
-    &[6, 26, 46],
-    &[6, 28, 50],
-    &[6, 30, 54],
-    &[6, 32, 58],
-    &[6, 34, 62],
-    &[6, 26, 46, 66],
-    &[6, 26, 48, 70],
-    &[6, 26, 50, 74],
-    &[6, 30, 54, 78],
-    &[6, 30, 56, 82],
-    &[6, 30, 58, 86],
-    &[6, 34, 62, 90],
-    &[6, 28, 50, 72, 94],
-    &[6, 26, 50, 74, 98],
-    &[6, 30, 54, 78, 102],
-    &[6, 28, 54, 80, 106],
-    &[6, 32, 58, 84, 110],
-    &[6, 30, 58, 86, 114],
-    &[6, 34, 62, 90, 118],
-    &[6, 26, 50, 74, 98, 122],
-    &[6, 30, 54, 78, 102, 126],
-    &[6, 26, 52, 78, 104, 130],
-    &[6, 30, 56, 82, 108, 134],
-    &[6, 34, 60, 86, 112, 138],
-    &[6, 30, 58, 86, 114, 142],
-    &[6, 34, 62, 90, 118, 146],
-    &[6, 30, 54, 78, 102, 126, 150],
-    &[6, 24, 50, 76, 102, 128, 154],
-    &[6, 28, 54, 80, 106, 132, 158],
-    &[6, 32, 58, 84, 110, 136, 162],
-    &[6, 26, 54, 82, 110, 138, 166],
-    &[6, 30, 58, 86, 114, 142, 170],
-];
-
-const G15: i32 = (1 << 10) | (1 << 8) | (1 << 5) | (1 << 4) | (1 << 2) | (1 << 1) | (1 << 0);
-const G18: i32 =
-    (1 << 12) | (1 << 11) | (1 << 10) | (1 << 9) | (1 << 8) | (1 << 5) | (1 << 2) | (1 << 0);
-const G15_MASK: i32 = (1 << 14) | (1 << 12) | (1 << 10) | (1 << 4) | (1 << 1);
-
-/// 获取 BCH 类型信息
-pub fn get_bch_type_info(data: i32) -> i32 {
-    let mut d = data << 10;
-    while get_bch_digit(d) - get_bch_digit(G15) >= 0 {
-        d ^= G15 << (get_bch_digit(d) - get_bch_digit(G15));
-    }
-    ((data << 10) | d) ^ G15_MASK
-}
-
-/// 获取 BCH 类型编号
-pub fn get_bch_type_number(data: i32) -> i32 {
-    let mut d = data << 12;
-    while get_bch_digit(d) - get_bch_digit(G18) >= 0 {
-        d ^= G18 << (get_bch_digit(d) - get_bch_digit(G18));
-    }
-    (data << 12) | d
-}
-
-/// 获取 BCH 数字位数
-fn get_bch_digit(data: i32) -> i32 {
-    let mut digit = 0;
-    let mut data = data;
-    while data != 0 {
-        digit += 1;
-        data >>= 1;
-    }
-    digit
-}
-
-/// 获取位置调整图案位置
-pub fn get_pattern_position(type_number: i32) -> &'static [i32] {
-    if (1..=40).contains(&type_number) {
-        PATTERN_POSITION_TABLE[(type_number - 1) as usize]
-    } else {
-        &[]
-    }
-}
-
 /// 获取遮罩值
 pub fn get_mask(mask_pattern: QRMaskPattern, i: i32, j: i32) -> bool {
     match mask_pattern {
@@ -171,104 +86,190 @@ pub fn get_length_in_bits(mode: QRMode, type_num: i32) -> usize {
 }
 
 /// 获取失分（用于选择最佳遮罩模式）
-// `get_lost_point` removed — use `get_lost_point_option` instead.
-/// 获取失分（用于选择最佳遮罩模式）- Option<bool> 版本
-pub fn get_lost_point_option(modules: &[Vec<Option<bool>>]) -> i32 {
-    let module_count = modules.len() as i32;
+///
+/// 规则与 qrcode.js 的 `QRUtil.getLostPoint` 一致（与 ISO 的 N1-N4 不同，
+/// 保证与 JS 版本选出相同的遮罩），在 64 位压缩行上按位计算，
+/// 列方向在转置后复用按行的逻辑。
+pub fn get_lost_point(matrix: &QrMatrix) -> i32 {
+    let size = matrix.width();
+    if size == 0 {
+        return 0;
+    }
+    let columns = matrix.transpose();
     let mut lost_point = 0;
 
-    // LEVEL1
-    for row in 0..module_count {
-        for col in 0..module_count {
-            let mut same_count = 0;
-            let dark = modules[row as usize][col as usize].unwrap_or(false);
-            for r in -1..=1 {
-                if row + r < 0 || module_count <= row + r {
-                    continue;
-                }
-                for c in -1..=1 {
-                    if col + c < 0 || module_count <= col + c {
-                        continue;
-                    }
-                    if r == 0 && c == 0 {
-                        continue;
-                    }
-                    if dark == modules[(row + r) as usize][(col + c) as usize].unwrap_or(false) {
-                        same_count += 1;
+    // LEVEL1: 周围 8 个模块中同色的超过 5 个，得 3 + (同色数 - 5) 分
+    for y in 0..size {
+        let rows = [y.checked_sub(1), Some(y), (y + 1 < size).then_some(y + 1)];
+        for start in (0..size).step_by(64) {
+            let center = window(matrix.row(y), start);
+            // 左邻在第 0 列、右邻在最后一列时越界，不计入
+            let has_left = if start == 0 { !(1 << 63) } else { u64::MAX };
+            let has_right = high_bits(size - 1 - start);
+
+            // 逐位计数器，counter[k] 为同色数的第 k 位
+            let mut counter = [0u64; 4];
+            for &row in rows.iter().flatten() {
+                let line = matrix.row(row);
+                let left = match start {
+                    0 => window(line, 0) >> 1,
+                    _ => window(line, start - 1),
+                };
+                // 本行的正中一格是模块自身
+                let has_middle = if row == y { 0 } else { u64::MAX };
+                let neighbours = [
+                    (left, has_left),
+                    (window(line, start), has_middle),
+                    (window(line, start + 1), has_right),
+                ];
+                for (neighbour, valid) in neighbours {
+                    let mut carry = !(center ^ neighbour) & valid;
+                    for bit in counter.iter_mut() {
+                        let next = *bit & carry;
+                        *bit ^= carry;
+                        carry = next;
                     }
                 }
             }
-            if same_count > 5 {
-                lost_point += 3 + same_count - 5;
-            }
+
+            // 同色数为 6、7、8 时得 4、5、6 分
+            let hit = (counter[3] | (counter[2] & counter[1])) & high_bits(size - start);
+            lost_point += 8 * (counter[3] & hit).count_ones() as i32
+                + 4 * (counter[2] & hit).count_ones() as i32
+                + 2 * (counter[1] & hit).count_ones() as i32
+                + (counter[0] & hit).count_ones() as i32
+                - 2 * hit.count_ones() as i32;
         }
     }
 
-    // LEVEL2
-    for row in 0..module_count - 1 {
-        for col in 0..module_count - 1 {
-            let mut count = 0;
-            if modules[row as usize][col as usize].unwrap_or(false) {
-                count += 1;
-            }
-            if modules[(row + 1) as usize][col as usize].unwrap_or(false) {
-                count += 1;
-            }
-            if modules[row as usize][(col + 1) as usize].unwrap_or(false) {
-                count += 1;
-            }
-            if modules[(row + 1) as usize][(col + 1) as usize].unwrap_or(false) {
-                count += 1;
-            }
-            if count == 0 || count == 4 {
-                lost_point += 3;
+    // LEVEL2: 2x2 同色块，每个 3 分（与 ISO 的 N2 相同）
+    lost_point += 3 * same_color_blocks(matrix) as i32;
+
+    // LEVEL3: 行与列中的 1011101，每处 40 分（不检查两侧的浅色）
+    if size >= 7 {
+        for i in 0..size {
+            for line in [matrix.row(i), columns.row(i)] {
+                for start in (0..=size - 7).step_by(64) {
+                    let mut found = high_bits(size - 6 - start);
+                    for k in 0..7 {
+                        let w = window(line, start + k);
+                        found &= if FINDER_CORE >> (63 - k) & 1 == 1 {
+                            w
+                        } else {
+                            !w
+                        };
+                    }
+                    lost_point += 40 * found.count_ones() as i32;
+                }
             }
         }
     }
 
-    // LEVEL3
-    for row in 0..module_count {
-        for col in 0..module_count - 6 {
-            if modules[row as usize][col as usize].unwrap_or(false)
-                && !modules[row as usize][(col + 1) as usize].unwrap_or(false)
-                && modules[row as usize][(col + 2) as usize].unwrap_or(false)
-                && modules[row as usize][(col + 3) as usize].unwrap_or(false)
-                && modules[row as usize][(col + 4) as usize].unwrap_or(false)
-                && !modules[row as usize][(col + 5) as usize].unwrap_or(false)
-                && modules[row as usize][(col + 6) as usize].unwrap_or(false)
-            {
-                lost_point += 40;
+    // LEVEL4: 深色百分比（整数截断）每偏离 50 五个点得 10 分
+    let module_count = size as i32;
+    let dark_count = matrix.count_dark() as i32;
+    let ratio = ((100 * dark_count) / module_count / module_count - 50).abs() / 5;
+    lost_point += ratio * 10;
+
+    lost_point
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qrcode_rust_shared::{Mask, QRErrorCorrectLevel, QrBuilder, VersionTemplate};
+
+    /// 逐模块的朴素实现（即 qrcode.js 的原始循环），用于校验按位计算的结果
+    fn reference_lost_point(matrix: &QrMatrix) -> i32 {
+        let module_count = matrix.width() as i32;
+        let dark = |row: i32, col: i32| matrix.get(col as usize, row as usize);
+        let mut lost_point = 0;
+
+        for row in 0..module_count {
+            for col in 0..module_count {
+                let mut same_count = 0;
+                for r in -1..=1 {
+                    for c in -1..=1 {
+                        let (nr, nc) = (row + r, col + c);
+                        if (r, c) == (0, 0)
+                            || !(0..module_count).contains(&nr)
+                            || !(0..module_count).contains(&nc)
+                        {
+                            continue;
+                        }
+                        if dark(row, col) == dark(nr, nc) {
+                            same_count += 1;
+                        }
+                    }
+                }
+                if same_count > 5 {
+                    lost_point += 3 + same_count - 5;
+                }
             }
         }
-    }
 
-    for col in 0..module_count {
-        for row in 0..module_count - 6 {
-            if modules[row as usize][col as usize].unwrap_or(false)
-                && !modules[(row + 1) as usize][col as usize].unwrap_or(false)
-                && modules[(row + 2) as usize][col as usize].unwrap_or(false)
-                && modules[(row + 3) as usize][col as usize].unwrap_or(false)
-                && modules[(row + 4) as usize][col as usize].unwrap_or(false)
-                && !modules[(row + 5) as usize][col as usize].unwrap_or(false)
-                && modules[(row + 6) as usize][col as usize].unwrap_or(false)
-            {
-                lost_point += 40;
+        for row in 0..module_count - 1 {
+            for col in 0..module_count - 1 {
+                let count = [(0, 0), (1, 0), (0, 1), (1, 1)]
+                    .iter()
+                    .filter(|&&(r, c)| dark(row + r, col + c))
+                    .count();
+                if count == 0 || count == 4 {
+                    lost_point += 3;
+                }
             }
         }
-    }
 
-    // LEVEL4
-    let mut dark_count = 0;
-    for col in 0..module_count {
-        for row in 0..module_count {
-            if modules[row as usize][col as usize].unwrap_or(false) {
-                dark_count += 1;
+        let pattern = [true, false, true, true, true, false, true];
+        for a in 0..module_count {
+            for b in 0..module_count - 6 {
+                for at in [|a, b| (a, b), |a, b| (b, a)] {
+                    if (0..7).all(|k| {
+                        let (row, col) = at(a, b + k);
+                        dark(row, col) == pattern[k as usize]
+                    }) {
+                        lost_point += 40;
+                    }
+                }
             }
         }
+
+        let dark_count = matrix.count_dark() as i32;
+        let ratio = ((100 * dark_count) / module_count / module_count - 50).abs() / 5;
+        lost_point + ratio * 10
     }
 
-    let ratio = ((100 * dark_count) / module_count / module_count - 50).abs() / 5;
-    lost_point += ratio * 10;
+    #[test]
+    fn test_lost_point_matches_reference() {
+        // 真实符号的 8 个掩码候选，从版本 1 到版本 40（177 宽，跨三个压缩字）
+        for (text, level) in [
+            ("Hello World", QRErrorCorrectLevel::H),
+            (
+                "https://github.com/veaba/wasm-qrcode",
+                QRErrorCorrectLevel::Q,
+            ),
+            (&"7".repeat(120), QRErrorCorrectLevel::H),
+            (&"x".repeat(2900), QRErrorCorrectLevel::L),
+        ] {
+            let symbol = QrBuilder::new(text).ec_level(level).build().unwrap();
+            let template = VersionTemplate::get(symbol.version());
+            for mask in Mask::ALL {
+                let mut m = symbol.matrix().clone();
+                m.xor_with(template.mask_pattern(mask));
+                assert_eq!(
+                    get_lost_point(&m),
+                    reference_lost_point(&m),
+                    "{} mask {mask}",
+                    symbol.version()
+                );
+            }
+        }
+    }
 
-    lost_point
+    #[test]
+    fn test_lost_point_uniform() {
+        // 全浅色 21x21：LEVEL1 只有 19 * 19 个内部模块得 6 分，LEVEL2 400 * 3，LEVEL4 100
+        let m = QrMatrix::square(21);
+        assert_eq!(get_lost_point(&m), 361 * 6 + 400 * 3 + 100);
+    }
 }